@@ -0,0 +1,68 @@
+//! Column operations at increasing numbers of rows. Each operation only touches the column's own
+//! values, so the time should grow linearly with the number of rows.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use retl::{DataFrame, DataType, Field, Value};
+
+const ROWS: &[usize] = &[1_000, 10_000, 100_000];
+
+fn dataframe(rows: usize) -> DataFrame<'static> {
+    let data = (0..rows)
+        .map(|i| {
+            vec![
+                Value::from(i as i64),
+                Value::from(format!("row {}", i)),
+                Value::from(i as f64 / 2.0),
+            ]
+        })
+        .collect();
+
+    DataFrame::new(&["a", "b", "c"], data)
+}
+
+fn column_ops(c: &mut Criterion) {
+    let mut group = c.benchmark_group("columns");
+
+    for rows in ROWS {
+        let df = dataframe(*rows);
+        group.throughput(Throughput::Elements(*rows as u64));
+
+        group.bench_with_input(BenchmarkId::new("add_column", rows), &df, |b, df| {
+            b.iter_batched(
+                || df.clone(),
+                |mut df| df.add_column(Field::with_type("d", DataType::Int64)),
+                BatchSize::LargeInput,
+            )
+        });
+
+        group.bench_with_input(BenchmarkId::new("remove_column", rows), &df, |b, df| {
+            b.iter_batched(
+                || df.clone(),
+                |mut df| df.remove_column(1),
+                BatchSize::LargeInput,
+            )
+        });
+
+        group.bench_with_input(BenchmarkId::new("map_column", rows), &df, |b, df| {
+            b.iter_batched(
+                || df.clone(),
+                |mut df| {
+                    df.map_column("a", |v| {
+                        *v = Value::Null;
+                        Ok(())
+                    })
+                },
+                BatchSize::LargeInput,
+            )
+        });
+
+        group.bench_with_input(BenchmarkId::new("column_values", rows), &df, |b, df| {
+            b.iter(|| df.column_values("c").map(|values| values.count()))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, column_ops);
+criterion_main!(benches);
@@ -1,3 +1,4 @@
+use chrono::FixedOffset;
 use retl::source::csv::CsvSource;
 use retl::{ops::convert::Convert, DataFrame, DataType};
 
@@ -20,9 +21,14 @@ fn main() {
 
     df.convert_column(
         "sale_date",
-        Convert::ParseDateTime("%a %B %e %H:%M:%S EDT %Y"),
+        Convert::ParseDateTime("%a %B %e %H:%M:%S %Z %Y"),
     )
     .expect("failed to convert sale_date to date");
+    df.convert_column(
+        "sale_date",
+        Convert::ToTimezone(FixedOffset::east_opt(0).unwrap()),
+    )
+    .expect("failed to convert sale_date to UTC");
 
     df.cast_column("price", DataType::Uint32)
         .expect("failed to cast price into u32");
@@ -0,0 +1,786 @@
+use crate::{
+    value::number::{Num, Number},
+    DataType, Value,
+};
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A bitmap with a bit for each value of a column, set when the value isn't null
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bitmap {
+    bits: Vec<u64>,
+    len: usize,
+}
+
+// usize::is_multiple_of is too recent to rely on, so the remainders are checked instead
+#[allow(clippy::manual_is_multiple_of)]
+impl Bitmap {
+    pub fn new() -> Bitmap {
+        Bitmap::default()
+    }
+
+    /// A bitmap of the length with every bit set to the value
+    pub fn with_len(len: usize, value: bool) -> Bitmap {
+        let word = if value { u64::MAX } else { 0 };
+        let mut bitmap = Bitmap {
+            bits: vec![word; len.div_ceil(64)],
+            len,
+        };
+        bitmap.clear_unused();
+        bitmap
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> Option<bool> {
+        if i >= self.len {
+            return None;
+        }
+
+        Some(self.bits[i / 64] & (1 << (i % 64)) != 0)
+    }
+
+    /// Set the bit, panics if it is out of bounds
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(i < self.len, "bit {} is out of bounds", i);
+
+        if value {
+            self.bits[i / 64] |= 1 << (i % 64);
+        } else {
+            self.bits[i / 64] &= !(1 << (i % 64));
+        }
+    }
+
+    pub fn push(&mut self, value: bool) {
+        if self.len % 64 == 0 {
+            self.bits.push(0);
+        }
+
+        self.len += 1;
+        self.set(self.len - 1, value);
+    }
+
+    /// The number of set bits
+    pub fn count_ones(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            self.len = len;
+            self.bits.truncate(len.div_ceil(64));
+            self.clear_unused();
+        }
+    }
+
+    /// Unset the bits of the last word that are past the end, so they aren't counted
+    fn clear_unused(&mut self) {
+        if self.len % 64 != 0 {
+            if let Some(last) = self.bits.last_mut() {
+                *last &= (1 << (self.len % 64)) - 1;
+            }
+        }
+    }
+}
+
+impl std::iter::FromIterator<bool> for Bitmap {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bitmap = Bitmap::new();
+        for value in iter {
+            bitmap.push(value);
+        }
+        bitmap
+    }
+}
+
+/// Strings stored once each, with a code for each row that is the position of the row's string
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Dictionary {
+    values: Vec<String>,
+    index: HashMap<String, u32>,
+    codes: Vec<u32>,
+}
+
+impl Dictionary {
+    /// The distinct strings, in the order they were first stored
+    pub fn values(&self) -> &[String] {
+        &self.values
+    }
+
+    /// The code of each row, the code of a null row is meaningless
+    pub fn codes(&self) -> &[u32] {
+        &self.codes
+    }
+
+    pub fn get(&self, row: usize) -> Option<&str> {
+        self.codes
+            .get(row)
+            .and_then(|code| self.values.get(*code as usize))
+            .map(String::as_str)
+    }
+
+    /// Drop the strings that no row that isn't null has, renumbering the codes
+    fn compact(&mut self, validity: &Bitmap) {
+        let Dictionary {
+            mut values, codes, ..
+        } = std::mem::take(self);
+        let mut renumbered = vec![None; values.len()];
+
+        for (row, code) in codes.into_iter().enumerate() {
+            let code = match validity.get(row) {
+                Some(true) => *renumbered[code as usize]
+                    .get_or_insert_with(|| self.code(std::mem::take(&mut values[code as usize]))),
+                _ => 0,
+            };
+            self.codes.push(code);
+        }
+    }
+
+    fn code(&mut self, s: String) -> u32 {
+        if let Some(code) = self.index.get(&s) {
+            return *code;
+        }
+
+        let code = self.values.len() as u32;
+        self.index.insert(s.clone(), code);
+        self.values.push(s);
+        code
+    }
+}
+
+/// A type of value that is stored in a vector of its own type, with a default placeholder in the
+/// rows that are null
+trait Typed: Sized + Default {
+    fn dtype() -> DataType;
+
+    /// True when the value is of the type
+    fn fits(value: &Value) -> bool;
+
+    /// The inner value, giving the value back when it isn't of the type
+    fn from_value(value: Value) -> Result<Self, Value>;
+
+    fn to_value(&self) -> Value;
+}
+
+macro_rules! impl_typed {
+    (number $ty:ty, $num:ident, $dtype:expr) => {
+        impl Typed for $ty {
+            fn dtype() -> DataType {
+                $dtype
+            }
+
+            fn fits(value: &Value) -> bool {
+                matches!(value, Value::Number(Number(Num::$num(_))))
+            }
+
+            fn from_value(value: Value) -> Result<Self, Value> {
+                match value {
+                    Value::Number(Number(Num::$num(n))) => Ok(n),
+                    value => Err(value),
+                }
+            }
+
+            fn to_value(&self) -> Value {
+                Value::Number(Number(Num::$num(*self)))
+            }
+        }
+    };
+    (value $ty:ty, $variant:ident, $dtype:expr) => {
+        impl Typed for $ty {
+            fn dtype() -> DataType {
+                $dtype
+            }
+
+            fn fits(value: &Value) -> bool {
+                matches!(value, Value::$variant(_))
+            }
+
+            fn from_value(value: Value) -> Result<Self, Value> {
+                match value {
+                    Value::$variant(v) => Ok(v),
+                    value => Err(value),
+                }
+            }
+
+            fn to_value(&self) -> Value {
+                Value::$variant(*self)
+            }
+        }
+    };
+}
+
+impl_typed!(value bool, Bool, DataType::Bool);
+impl_typed!(number u8, Uint8, DataType::Uint8);
+impl_typed!(number u16, Uint16, DataType::Uint16);
+impl_typed!(number u32, Uint32, DataType::Uint32);
+impl_typed!(number u64, Uint64, DataType::Uint64);
+impl_typed!(number i8, Int8, DataType::Int8);
+impl_typed!(number i16, Int16, DataType::Int16);
+impl_typed!(number i32, Int32, DataType::Int32);
+impl_typed!(number i64, Int64, DataType::Int64);
+impl_typed!(number f32, Float, DataType::Float);
+impl_typed!(number f64, Double, DataType::Double);
+impl_typed!(number Decimal, Decimal, DataType::Decimal(None));
+impl_typed!(value NaiveDate, Date, DataType::Date);
+impl_typed!(value NaiveTime, Time, DataType::Time);
+impl_typed!(value NaiveDateTime, Timestamp, DataType::Timestamp);
+impl_typed!(value DateTime<FixedOffset>, TimestampTz, DataType::TimestampTz);
+
+macro_rules! data {
+    ($($variant:ident($ty:ty),)*) => {
+        /// The storage of a column's values. Nulls are stored as a placeholder, and marked in the
+        /// column's validity.
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        enum Data {
+            $($variant(Vec<$ty>),)*
+            String(Dictionary),
+            /// Values of any other type, or of more than one type
+            Value(Vec<Value>),
+        }
+
+        impl Data {
+            fn for_type(dtype: &DataType) -> Data {
+                match dtype {
+                    DataType::String => Data::String(Dictionary::default()),
+                    // decimals of every size are stored the same
+                    DataType::Decimal(_) => Data::Decimal(vec![]),
+                    $(dtype if *dtype == <$ty>::dtype() => Data::$variant(vec![]),)*
+                    _ => Data::Value(vec![]),
+                }
+            }
+
+            fn for_value(value: &Value) -> Data {
+                match value {
+                    Value::String(_) => Data::String(Dictionary::default()),
+                    $(value if <$ty>::fits(value) => Data::$variant(vec![]),)*
+                    _ => Data::Value(vec![]),
+                }
+            }
+
+            fn dtype(&self) -> Option<DataType> {
+                match self {
+                    $(Data::$variant(_) => Some(<$ty>::dtype()),)*
+                    Data::String(_) => Some(DataType::String),
+                    Data::Value(_) => None,
+                }
+            }
+
+            fn len(&self) -> usize {
+                match self {
+                    $(Data::$variant(values) => values.len(),)*
+                    Data::String(dictionary) => dictionary.codes.len(),
+                    Data::Value(values) => values.len(),
+                }
+            }
+
+            /// True when the value is null, or of the type stored
+            fn fits(&self, value: &Value) -> bool {
+                match (self, value) {
+                    (_, Value::Null) | (Data::Value(_), _) => true,
+                    $((Data::$variant(_), value) => <$ty>::fits(value),)*
+                    (Data::String(_), value) => matches!(value, Value::String(_)),
+                }
+            }
+
+            /// The value of the row, which has to be in bounds and not null
+            fn get(&self, row: usize) -> Value {
+                match self {
+                    $(Data::$variant(values) => values[row].to_value(),)*
+                    Data::String(dictionary) => {
+                        Value::from(dictionary.get(row).unwrap_or_default())
+                    }
+                    Data::Value(values) => values[row].clone(),
+                }
+            }
+
+            /// Store the value, giving it back when it doesn't fit
+            fn push(&mut self, value: Value) -> Result<(), Value> {
+                match (self, value) {
+                    (Data::Value(values), value) => values.push(value),
+                    $(
+                        (Data::$variant(values), Value::Null) => values.push(<$ty>::default()),
+                        (Data::$variant(values), value) => values.push(<$ty>::from_value(value)?),
+                    )*
+                    (Data::String(dictionary), Value::Null) => dictionary.codes.push(0),
+                    (Data::String(dictionary), Value::String(s)) => {
+                        let code = dictionary.code(s);
+                        dictionary.codes.push(code);
+                    }
+                    (Data::String(_), value) => return Err(value),
+                }
+
+                Ok(())
+            }
+
+            /// Replace the value of the row, giving it back when it doesn't fit
+            fn set(&mut self, row: usize, value: Value) -> Result<(), Value> {
+                match (self, value) {
+                    (Data::Value(values), value) => values[row] = value,
+                    $(
+                        (Data::$variant(values), Value::Null) => values[row] = <$ty>::default(),
+                        (Data::$variant(values), value) => values[row] = <$ty>::from_value(value)?,
+                    )*
+                    (Data::String(dictionary), Value::Null) => dictionary.codes[row] = 0,
+                    (Data::String(dictionary), Value::String(s)) => {
+                        dictionary.codes[row] = dictionary.code(s);
+                    }
+                    (Data::String(_), value) => return Err(value),
+                }
+
+                Ok(())
+            }
+
+            fn truncate(&mut self, len: usize) {
+                match self {
+                    $(Data::$variant(values) => values.truncate(len),)*
+                    Data::String(dictionary) if len == 0 => *dictionary = Dictionary::default(),
+                    Data::String(dictionary) => dictionary.codes.truncate(len),
+                    Data::Value(values) => values.truncate(len),
+                }
+            }
+        }
+    };
+}
+
+data! {
+    Bool(bool),
+    Uint8(u8),
+    Uint16(u16),
+    Uint32(u32),
+    Uint64(u64),
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    Float(f32),
+    Double(f64),
+    Decimal(Decimal),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    Timestamp(NaiveDateTime),
+    TimestampTz(DateTime<FixedOffset>),
+}
+
+/// The values of one of a dataframe's columns, with a bitmap of the values that aren't null.
+/// Booleans, numbers, dates and times are stored in a vector of their own type, and strings with
+/// each distinct string stored once. Columns of other types, or with values of more than one type,
+/// store the values themselves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Column {
+    data: Data,
+    validity: Bitmap,
+}
+
+impl std::default::Default for Column {
+    fn default() -> Self {
+        Column {
+            data: Data::Value(vec![]),
+            validity: Bitmap::new(),
+        }
+    }
+}
+
+/// Columns are equal when they have the same values, however the values are stored
+impl PartialEq for Column {
+    fn eq(&self, other: &Column) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Column {
+    pub fn new() -> Column {
+        Column::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Column {
+        Column {
+            data: Data::Value(Vec::with_capacity(capacity)),
+            validity: Bitmap::new(),
+        }
+    }
+
+    /// An empty column that stores values of the type
+    pub fn with_type(dtype: &DataType) -> Column {
+        Column {
+            data: Data::for_type(dtype),
+            validity: Bitmap::new(),
+        }
+    }
+
+    /// A column with the value repeated len times
+    pub fn repeat(value: Value, len: usize) -> Column {
+        let mut column = Column::new();
+        for _ in 0..len {
+            column.push(value.clone());
+        }
+        column
+    }
+
+    /// A column of the values of a field of the type. The values are stored as the type when they
+    /// are all of it, columns of type any are stored like from_values.
+    pub fn with_values(dtype: &DataType, values: Vec<Value>) -> Column {
+        if let DataType::Any = dtype {
+            return Column::from_values(values);
+        }
+
+        let mut column = Column::with_type(dtype);
+        column.extend(values);
+        column
+    }
+
+    /// A column of the values, stored as the type of the first value that isn't null when all of
+    /// the values are of that type
+    pub fn from_values(values: Vec<Value>) -> Column {
+        let data = values
+            .iter()
+            .find(|v| !v.is_null())
+            .map(Data::for_value)
+            .filter(|data| values.iter().all(|v| data.fits(v)))
+            .unwrap_or_else(|| Data::Value(Vec::with_capacity(values.len())));
+
+        let mut column = Column {
+            data,
+            validity: Bitmap::new(),
+        };
+        column.extend(values);
+        column
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The type the values are stored as, None when the column stores values of any type
+    pub fn dtype(&self) -> Option<DataType> {
+        self.data.dtype()
+    }
+
+    pub fn null_count(&self) -> usize {
+        self.len() - self.validity.count_ones()
+    }
+
+    /// True when the value at the row is null, or the row is out of bounds
+    pub fn is_null(&self, row: usize) -> bool {
+        !self.validity.get(row).unwrap_or(false)
+    }
+
+    pub fn validity(&self) -> &Bitmap {
+        &self.validity
+    }
+
+    pub fn get(&self, row: usize) -> Option<Value> {
+        match self.validity.get(row)? {
+            true => Some(self.data.get(row)),
+            false => Some(Value::Null),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Value> + '_ {
+        (0..self.len()).filter_map(move |row| self.get(row))
+    }
+
+    pub fn into_values(self) -> Vec<Value> {
+        match self.data {
+            Data::Value(values) => values,
+            _ => self.iter().collect(),
+        }
+    }
+
+    pub fn as_bools(&self) -> Option<&[bool]> {
+        match &self.data {
+            Data::Bool(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_int32s(&self) -> Option<&[i32]> {
+        match &self.data {
+            Data::Int32(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_int64s(&self) -> Option<&[i64]> {
+        match &self.data {
+            Data::Int64(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_doubles(&self) -> Option<&[f64]> {
+        match &self.data {
+            Data::Double(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_dictionary(&self) -> Option<&Dictionary> {
+        match &self.data {
+            Data::String(dictionary) => Some(dictionary),
+            _ => None,
+        }
+    }
+
+    pub fn push(&mut self, value: Value) {
+        // an empty column takes the type of the first value pushed
+        if let Data::Value(values) = &self.data {
+            if values.is_empty() && !value.is_null() {
+                self.data = Data::for_value(&value);
+            }
+        }
+
+        self.validity.push(!value.is_null());
+        if let Err(value) = self.data.push(value) {
+            self.store_values();
+            let _ = self.data.push(value);
+        }
+    }
+
+    /// Replace the value at the row, panics if it is out of bounds
+    pub fn set(&mut self, row: usize, value: Value) {
+        self.validity.set(row, !value.is_null());
+        if let Err(value) = self.data.set(row, value) {
+            self.store_values();
+            let _ = self.data.set(row, value);
+        }
+    }
+
+    /// Call the function with each value to change it in place, stopping at the first error.
+    /// Values changed before the error are kept. Afterwards the values are stored as their type
+    /// when they are all of the same type, and strings no longer in the column are dropped.
+    pub fn try_for_each_mut<F, E>(&mut self, mut func: F) -> Result<(), E>
+    where
+        F: FnMut(&mut Value) -> Result<(), E>,
+    {
+        let result = match &mut self.data {
+            Data::Value(values) => {
+                let validity = &mut self.validity;
+                values.iter_mut().enumerate().try_for_each(|(row, value)| {
+                    let result = func(value);
+                    validity.set(row, !value.is_null());
+                    result
+                })
+            }
+            _ => (0..self.len()).try_for_each(|row| {
+                let mut value = self.get(row).unwrap_or(Value::Null);
+                let result = func(&mut value);
+                self.set(row, value);
+                result
+            }),
+        };
+
+        self.compact();
+        result
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+        self.validity.truncate(len);
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Switch to storing the values themselves, for a value that doesn't fit the current storage
+    fn store_values(&mut self) {
+        if let Data::Value(_) = self.data {
+            return;
+        }
+
+        let values = (0..self.len())
+            .map(|row| self.get(row).unwrap_or(Value::Null))
+            .collect();
+        self.data = Data::Value(values);
+    }
+
+    /// Store the values as their type if they are all of the same type, and drop the strings that
+    /// aren't in any row
+    fn compact(&mut self) {
+        match &mut self.data {
+            Data::Value(values) if values.iter().any(|v| !v.is_null()) => {
+                *self = Column::from_values(std::mem::take(values));
+            }
+            Data::String(dictionary) => dictionary.compact(&self.validity),
+            _ => {}
+        }
+    }
+}
+
+impl std::iter::FromIterator<Value> for Column {
+    fn from_iter<I: IntoIterator<Item = Value>>(iter: I) -> Self {
+        Column::from_values(iter.into_iter().collect())
+    }
+}
+
+impl Extend<Value> for Column {
+    fn extend<I: IntoIterator<Item = Value>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::TypeOf;
+
+    #[test]
+    fn it_tracks_nulls() {
+        let mut column = Column::from_values(vec![Value::from(1), Value::Null]);
+        assert_eq!(column.null_count(), 1);
+        assert!(column.is_null(1));
+        assert!(column.is_null(5));
+
+        column.push(Value::Null);
+        column.set(1, Value::from(2));
+        assert_eq!(column.null_count(), 1);
+        assert!(!column.is_null(1));
+
+        column
+            .try_for_each_mut(|v| {
+                *v = Value::Null;
+                Ok::<(), ()>(())
+            })
+            .unwrap();
+        assert_eq!(column.null_count(), 3);
+
+        let mut bitmap = Bitmap::with_len(130, true);
+        assert_eq!(bitmap.count_ones(), 130);
+        bitmap.truncate(65);
+        assert_eq!(bitmap.count_ones(), 65);
+        bitmap.push(false);
+        assert_eq!(bitmap.get(65), Some(false));
+        assert_eq!(bitmap.get(66), None);
+    }
+
+    #[test]
+    fn it_stores_values_as_their_type() {
+        let mut column = Column::from_values(vec![Value::from(1), Value::Null, Value::from(3)]);
+        assert_eq!(column.dtype(), Some(DataType::Int32));
+        assert_eq!(column.as_int32s().unwrap()[2], 3);
+        assert_eq!(column.get(1), Some(Value::Null));
+
+        // a value of another type falls back to storing the values
+        column.push(Value::from("x"));
+        assert_eq!(column.dtype(), None);
+        assert_eq!(
+            column.iter().collect::<Vec<Value>>(),
+            vec![
+                Value::from(1),
+                Value::Null,
+                Value::from(3),
+                Value::from("x")
+            ]
+        );
+
+        column
+            .try_for_each_mut(|v| {
+                *v = Value::from(v.to_string().len() as i64);
+                Ok::<(), ()>(())
+            })
+            .unwrap();
+        assert_eq!(column.as_int64s(), Some(&[1i64, 4, 1, 1][..]));
+
+        let column = Column::from_values(vec!["a".into(), "b".into(), "a".into()]);
+        let dictionary = column.as_dictionary().unwrap();
+        assert_eq!(dictionary.values(), ["a", "b"]);
+        assert_eq!(dictionary.codes(), [0, 1, 0]);
+        assert_eq!(
+            column,
+            vec!["a", "b", "a"]
+                .into_iter()
+                .map(Value::from)
+                .collect::<Column>()
+        );
+    }
+
+    #[test]
+    fn it_stores_fixed_width_types_as_their_type() {
+        let date = NaiveDate::from_ymd_opt(2019, 9, 5).unwrap();
+        let timestamp = date.and_hms_opt(18, 14, 4).unwrap();
+        let values: Vec<Value> = vec![
+            Value::from(1u8),
+            Value::from(1u16),
+            Value::from(1u32),
+            Value::from(1u64),
+            Value::from(1i8),
+            Value::from(1i16),
+            Value::from(1.5f32),
+            Value::Date(date),
+            Value::Time(timestamp.time()),
+            Value::Timestamp(timestamp),
+            Value::TimestampTz(DateTime::parse_from_rfc3339("2019-09-05T18:14:04+02:00").unwrap()),
+        ];
+
+        for value in values {
+            let column = Column::from_values(vec![Value::Null, value.clone()]);
+            assert_eq!(column.dtype(), Some(value.type_of()));
+            assert_eq!(column.iter().collect::<Vec<Value>>(), [Value::Null, value]);
+        }
+
+        let decimal = Value::Number(Number(Num::Decimal("123.45".parse().unwrap())));
+        let mut column = Column::with_type(&DataType::Decimal(Some((10, 2))));
+        column.push(decimal.clone());
+        assert_eq!(column.dtype(), Some(DataType::Decimal(None)));
+        assert_eq!(column.get(0), Some(decimal));
+    }
+
+    #[test]
+    fn it_stores_values_as_the_fields_type() {
+        let column = Column::with_values(&DataType::Int64, vec![Value::Null, Value::from(1i64)]);
+        assert_eq!(column.as_int64s(), Some(&[0i64, 1][..]));
+
+        // values that aren't of the type are stored as themselves
+        let column = Column::with_values(&DataType::Int64, vec![Value::from(1i32)]);
+        assert_eq!(column.dtype(), None);
+        assert_eq!(column.get(0), Some(Value::from(1i32)));
+
+        let column = Column::with_values(&DataType::Any, vec![Value::from(1i32)]);
+        assert_eq!(column.dtype(), Some(DataType::Int32));
+    }
+
+    #[test]
+    fn it_drops_replaced_strings() {
+        let mut column = Column::from_values(vec!["a".into(), "b".into(), Value::Null, "a".into()]);
+
+        column
+            .try_for_each_mut(|v| {
+                if *v == Value::from("a") {
+                    *v = Value::from("c");
+                }
+                Ok::<(), ()>(())
+            })
+            .unwrap();
+
+        let dictionary = column.as_dictionary().unwrap();
+        assert_eq!(dictionary.values(), ["c", "b"]);
+        assert_eq!(dictionary.codes(), [0, 1, 0, 0]);
+        assert_eq!(
+            column.iter().collect::<Vec<Value>>(),
+            [
+                Value::from("c"),
+                Value::from("b"),
+                Value::Null,
+                Value::from("c")
+            ]
+        );
+    }
+}
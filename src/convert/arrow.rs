@@ -0,0 +1,771 @@
+use crate::convert::avro::{
+    date_to_days, days_to_date, micros_to_time, time_to_micros, DECIMAL_PRECISION, DECIMAL_SCALE,
+};
+use crate::{
+    column::Column, ops::cast, value::map::Map, value::number::Num, DataFrame, DataType, Field,
+    Number, Schema, Value,
+};
+
+use arrow::array::{
+    Array, ArrayRef, AsArray, BinaryArray, BooleanArray, Date32Array, Decimal128Array,
+    Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array, ListArray, MapArray,
+    NullArray, RecordBatch, RecordBatchOptions, StringArray, StructArray, Time64MicrosecondArray,
+    TimestampMicrosecondArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+};
+use arrow::buffer::{NullBuffer, OffsetBuffer};
+use arrow::datatypes::{
+    DataType as ArrowType, Date64Type, Decimal128Type, Field as ArrowField, Fields, Float32Type,
+    Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, Schema as ArrowSchema,
+    Time32MillisecondType, Time32SecondType, Time64MicrosecondType, Time64NanosecondType, TimeUnit,
+    TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+    TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use chrono::{DateTime, FixedOffset, NaiveTime};
+use rust_decimal::Decimal;
+use snafu::{ResultExt, Snafu};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The timezone of timestamps with a timezone, they are written in UTC
+pub const TIMEZONE: &str = "+00:00";
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Field {} has the unsupported Arrow type {}", field, dtype))]
+    UnsupportedType { field: String, dtype: String },
+
+    #[snafu(display("Failed to convert the values of {}: {}", column, message))]
+    ValueError { column: String, message: String },
+
+    #[snafu(display("Failed to create the record batch: {}", source))]
+    BatchError { source: arrow::error::ArrowError },
+
+    #[snafu(display("The Arrow schema is invalid: {}", source))]
+    SchemaError { source: crate::schema::Error },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Schema {
+    /// Convert the schema into an Arrow schema, fields of type any are strings. The schema's name
+    /// and the docs are kept in the metadata.
+    pub fn to_arrow_schema(&self) -> ArrowSchema {
+        let fields = self
+            .fields()
+            .iter()
+            .map(|field| field_to_arrow(field, &field.dtype))
+            .collect::<Vec<ArrowField>>();
+
+        ArrowSchema::new(fields).with_metadata(metadata(&self.name, &self.doc))
+    }
+
+    /// Create a schema from an Arrow schema
+    pub fn from_arrow_schema(arrow: &ArrowSchema) -> Result<Schema> {
+        let fields = arrow
+            .fields()
+            .iter()
+            .map(|field| {
+                let mut f = Field::with_type(
+                    field.name(),
+                    arrow_to_type(field.name(), field.data_type())?,
+                )
+                .with_nullable(field.is_nullable());
+                f.doc = field.metadata().get("doc").cloned();
+                Ok(f)
+            })
+            .collect::<Result<Vec<Field>>>()?;
+
+        let mut schema = Schema::with_fields(fields).context(SchemaError)?;
+        schema.name = arrow.metadata().get("name").cloned();
+        schema.doc = arrow.metadata().get("doc").cloned();
+
+        Ok(schema)
+    }
+}
+
+impl<'a> DataFrame<'a> {
+    /// Convert the dataframe into an Arrow record batch, with its schema converted by
+    /// Schema::to_arrow_schema. Columns of type any are of the type their values are stored as,
+    /// or strings when they are of more than one type. Values are cast into their field's type, and
+    /// nulls in fields that aren't nullable are an error.
+    pub fn to_record_batch(&self) -> Result<RecordBatch> {
+        let mut fields = vec![];
+        let mut arrays = vec![];
+
+        for (field, column) in self.schema.fields().iter().zip(self.columns.iter()) {
+            let dtype = match &field.dtype {
+                DataType::Any => column.dtype().unwrap_or(DataType::Any),
+                dtype => dtype.clone(),
+            };
+
+            let values = column
+                .iter()
+                .enumerate()
+                .map(|(row, value)| match (&dtype, &value) {
+                    // decimals are rescaled when the array is built, which fails instead of
+                    // rounding
+                    (DataType::Decimal(_), Value::Number(_)) => Ok(value),
+                    _ if dtype.matches(&value) => Ok(value),
+                    _ => cast::try_cast(value.clone(), &dtype).map_err(|e| {
+                        format!("{} on row {} can't be cast to {}: {}", value, row, dtype, e)
+                    }),
+                })
+                .collect::<std::result::Result<Vec<Value>, String>>()
+                .and_then(|values| to_array(&values, &dtype))
+                .map_err(|message| Error::ValueError {
+                    column: field.name.clone(),
+                    message,
+                })?;
+
+            fields.push(field_to_arrow(field, &dtype));
+            arrays.push(values);
+        }
+
+        let schema =
+            ArrowSchema::new(fields).with_metadata(metadata(&self.schema.name, &self.schema.doc));
+        let options = RecordBatchOptions::new().with_row_count(Some(self.dim.1));
+
+        RecordBatch::try_new_with_options(Arc::new(schema), arrays, &options).context(BatchError)
+    }
+
+    /// Create a dataframe from an Arrow record batch
+    pub fn from_record_batch(batch: &RecordBatch) -> Result<DataFrame<'a>> {
+        DataFrame::from_record_batches(&batch.schema(), std::slice::from_ref(batch))
+    }
+
+    /// Create a dataframe from Arrow record batches of the schema, the rows of each batch are
+    /// added in order
+    pub fn from_record_batches(
+        schema: &ArrowSchema,
+        batches: &[RecordBatch],
+    ) -> Result<DataFrame<'a>> {
+        let schema = Schema::from_arrow_schema(schema)?;
+        let mut columns = vec![vec![]; schema.len()];
+        let mut rows = 0;
+
+        for batch in batches {
+            rows += batch.num_rows();
+
+            for ((values, array), field) in
+                columns.iter_mut().zip(batch.columns()).zip(schema.fields())
+            {
+                let batch_values =
+                    array_values(array.as_ref()).map_err(|message| Error::ValueError {
+                        column: field.name.clone(),
+                        message,
+                    })?;
+                values.extend(batch_values);
+            }
+        }
+
+        let columns = columns
+            .into_iter()
+            .zip(schema.fields())
+            .map(|(values, field)| Column::with_values(field.dtype(), values))
+            .collect::<Vec<Column>>();
+
+        let mut df = DataFrame::from_columns(schema, columns);
+        df.dim.1 = rows;
+
+        Ok(df)
+    }
+}
+
+fn metadata(name: &Option<String>, doc: &Option<String>) -> HashMap<String, String> {
+    let mut metadata = HashMap::new();
+
+    if let Some(name) = name {
+        metadata.insert("name".to_string(), name.clone());
+    }
+
+    if let Some(doc) = doc {
+        metadata.insert("doc".to_string(), doc.clone());
+    }
+
+    metadata
+}
+
+fn field_to_arrow(field: &Field, dtype: &DataType) -> ArrowField {
+    let arrow = ArrowField::new(&field.name, type_to_arrow(dtype), field.nullable);
+
+    match &field.doc {
+        Some(doc) => arrow.with_metadata(metadata(&None, &Some(doc.clone()))),
+        None => arrow,
+    }
+}
+
+/// The field of the entries of maps, maps' keys are strings
+fn map_entries(dtype: &DataType) -> ArrowField {
+    let fields = vec![
+        ArrowField::new("keys", ArrowType::Utf8, false),
+        ArrowField::new("values", type_to_arrow(dtype), true),
+    ];
+
+    ArrowField::new("entries", ArrowType::Struct(Fields::from(fields)), false)
+}
+
+fn struct_fields(fields: &[(String, DataType)]) -> Fields {
+    fields
+        .iter()
+        .map(|(name, dtype)| ArrowField::new(name, type_to_arrow(dtype), true))
+        .collect()
+}
+
+/// The Arrow type of the data type. Decimals without a size have the same precision and scale
+/// as in Avro, times are in microseconds and timestamps are in microseconds in UTC.
+fn type_to_arrow(dtype: &DataType) -> ArrowType {
+    match dtype {
+        DataType::Null => ArrowType::Null,
+        DataType::Bool => ArrowType::Boolean,
+        DataType::String | DataType::Any => ArrowType::Utf8,
+        DataType::Binary => ArrowType::Binary,
+        DataType::Uint8 => ArrowType::UInt8,
+        DataType::Uint16 => ArrowType::UInt16,
+        DataType::Uint32 => ArrowType::UInt32,
+        DataType::Uint64 => ArrowType::UInt64,
+        DataType::Int8 => ArrowType::Int8,
+        DataType::Int16 => ArrowType::Int16,
+        DataType::Int32 => ArrowType::Int32,
+        DataType::Int64 => ArrowType::Int64,
+        DataType::Float => ArrowType::Float32,
+        DataType::Double => ArrowType::Float64,
+        DataType::Decimal(size) => {
+            let (precision, scale) = size.unwrap_or((DECIMAL_PRECISION, DECIMAL_SCALE));
+            ArrowType::Decimal128(precision as u8, scale as i8)
+        }
+        DataType::Date => ArrowType::Date32,
+        DataType::Time => ArrowType::Time64(TimeUnit::Microsecond),
+        DataType::Timestamp => ArrowType::Timestamp(TimeUnit::Microsecond, None),
+        DataType::TimestampTz => ArrowType::Timestamp(TimeUnit::Microsecond, Some(TIMEZONE.into())),
+        DataType::Array(element) => ArrowType::List(Arc::new(ArrowField::new(
+            "item",
+            type_to_arrow(element),
+            true,
+        ))),
+        DataType::Map(element) => ArrowType::Map(Arc::new(map_entries(element)), false),
+        DataType::Struct(fields) => ArrowType::Struct(struct_fields(fields)),
+    }
+}
+
+fn arrow_to_type(field: &str, arrow: &ArrowType) -> Result<DataType> {
+    let dtype = match arrow {
+        ArrowType::Null => DataType::Null,
+        ArrowType::Boolean => DataType::Bool,
+        ArrowType::Utf8 | ArrowType::LargeUtf8 => DataType::String,
+        ArrowType::Binary | ArrowType::LargeBinary | ArrowType::FixedSizeBinary(_) => {
+            DataType::Binary
+        }
+        ArrowType::UInt8 => DataType::Uint8,
+        ArrowType::UInt16 => DataType::Uint16,
+        ArrowType::UInt32 => DataType::Uint32,
+        ArrowType::UInt64 => DataType::Uint64,
+        ArrowType::Int8 => DataType::Int8,
+        ArrowType::Int16 => DataType::Int16,
+        ArrowType::Int32 => DataType::Int32,
+        ArrowType::Int64 => DataType::Int64,
+        ArrowType::Float32 => DataType::Float,
+        ArrowType::Float64 => DataType::Double,
+        ArrowType::Decimal128(precision, scale) if *scale >= 0 => {
+            DataType::decimal(u32::from(*precision), *scale as u32)
+        }
+        ArrowType::Date32 | ArrowType::Date64 => DataType::Date,
+        ArrowType::Time32(_) | ArrowType::Time64(_) => DataType::Time,
+        ArrowType::Timestamp(_, None) => DataType::Timestamp,
+        ArrowType::Timestamp(_, Some(_)) => DataType::TimestampTz,
+        ArrowType::List(element) | ArrowType::LargeList(element) => {
+            DataType::array(arrow_to_type(field, element.data_type())?)
+        }
+        ArrowType::Map(entries, _) => match entries.data_type() {
+            ArrowType::Struct(fields) if fields.len() == 2 => {
+                DataType::map(arrow_to_type(field, fields[1].data_type())?)
+            }
+            _ => DataType::map(DataType::Any),
+        },
+        ArrowType::Struct(fields) => DataType::Struct(
+            fields
+                .iter()
+                .map(|f| Ok((f.name().clone(), arrow_to_type(f.name(), f.data_type())?)))
+                .collect::<Result<Vec<(String, DataType)>>>()?,
+        ),
+        ArrowType::Dictionary(_, values) => arrow_to_type(field, values)?,
+        other => {
+            return Err(Error::UnsupportedType {
+                field: field.to_string(),
+                dtype: other.to_string(),
+            })
+        }
+    };
+
+    Ok(dtype)
+}
+
+fn nulls(values: &[Value]) -> Option<NullBuffer> {
+    Some(NullBuffer::from(
+        values.iter().map(|v| !v.is_null()).collect::<Vec<bool>>(),
+    ))
+}
+
+fn numbers<T, F: Fn(&Number) -> T>(values: &[Value], f: F) -> Vec<Option<T>> {
+    values
+        .iter()
+        .map(|v| match v {
+            Value::Number(n) => Some(f(n)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The unscaled value of the number at the scale, an error when the number has more decimal
+/// places than the scale or more digits than the precision
+fn decimal_mantissa(n: &Number, precision: u32, scale: u32) -> std::result::Result<i128, String> {
+    let original = match n.clone().into_decimal().map_err(|e| e.to_string())?.0 {
+        Num::Decimal(d) => d,
+        _ => return Err(format!("{} isn't a decimal", n)),
+    };
+
+    let mut decimal = original;
+    decimal.rescale(scale);
+    if decimal != original || decimal.scale() != scale {
+        return Err(format!(
+            "{} can't be written with {} decimal places without losing precision",
+            original, scale
+        ));
+    }
+
+    let mantissa = decimal.mantissa();
+    if mantissa.unsigned_abs() >= 10u128.pow(precision) {
+        return Err(format!("{} has more than {} digits", original, precision));
+    }
+
+    Ok(mantissa)
+}
+
+/// Build an array of the type from values that are already of the type
+fn to_array(values: &[Value], dtype: &DataType) -> std::result::Result<ArrayRef, String> {
+    let array: ArrayRef = match dtype {
+        DataType::Null => Arc::new(NullArray::new(values.len())),
+        DataType::Bool => Arc::new(BooleanArray::from(
+            values
+                .iter()
+                .map(|v| match v {
+                    Value::Bool(b) => Some(*b),
+                    _ => None,
+                })
+                .collect::<Vec<Option<bool>>>(),
+        )),
+        DataType::String => Arc::new(StringArray::from(
+            values
+                .iter()
+                .map(|v| match v {
+                    Value::String(s) => Some(s.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<Option<&str>>>(),
+        )),
+        DataType::Any => Arc::new(StringArray::from(
+            values
+                .iter()
+                .map(|v| match v {
+                    Value::Null => None,
+                    v => Some(v.to_string()),
+                })
+                .collect::<Vec<Option<String>>>(),
+        )),
+        DataType::Binary => Arc::new(BinaryArray::from(
+            values
+                .iter()
+                .map(|v| match v {
+                    Value::Binary(b) => Some(b.as_slice()),
+                    _ => None,
+                })
+                .collect::<Vec<Option<&[u8]>>>(),
+        )),
+        DataType::Uint8 => Arc::new(UInt8Array::from(numbers(values, Number::as_u8))),
+        DataType::Uint16 => Arc::new(UInt16Array::from(numbers(values, Number::as_u16))),
+        DataType::Uint32 => Arc::new(UInt32Array::from(numbers(values, Number::as_u32))),
+        DataType::Uint64 => Arc::new(UInt64Array::from(numbers(values, Number::as_u64))),
+        DataType::Int8 => Arc::new(Int8Array::from(numbers(values, Number::as_i8))),
+        DataType::Int16 => Arc::new(Int16Array::from(numbers(values, Number::as_i16))),
+        DataType::Int32 => Arc::new(Int32Array::from(numbers(values, Number::as_i32))),
+        DataType::Int64 => Arc::new(Int64Array::from(numbers(values, Number::as_i64))),
+        DataType::Float => Arc::new(Float32Array::from(numbers(values, Number::as_f32))),
+        DataType::Double => Arc::new(Float64Array::from(numbers(values, Number::as_f64))),
+        DataType::Decimal(size) => {
+            let (precision, scale) = size.unwrap_or((DECIMAL_PRECISION, DECIMAL_SCALE));
+            let values = values
+                .iter()
+                .enumerate()
+                .map(|(row, v)| match v {
+                    Value::Number(n) => decimal_mantissa(n, precision, scale)
+                        .map(Some)
+                        .map_err(|e| format!("{} on row {}", e, row)),
+                    _ => Ok(None),
+                })
+                .collect::<std::result::Result<Vec<Option<i128>>, String>>()?;
+
+            Arc::new(
+                Decimal128Array::from(values)
+                    .with_precision_and_scale(precision as u8, scale as i8)
+                    .map_err(|e| e.to_string())?,
+            )
+        }
+        DataType::Date => Arc::new(Date32Array::from(
+            values
+                .iter()
+                .map(|v| match v {
+                    Value::Date(d) => Some(date_to_days(d)),
+                    _ => None,
+                })
+                .collect::<Vec<Option<i32>>>(),
+        )),
+        DataType::Time => Arc::new(Time64MicrosecondArray::from(
+            values
+                .iter()
+                .map(|v| match v {
+                    Value::Time(t) => Some(time_to_micros(t)),
+                    _ => None,
+                })
+                .collect::<Vec<Option<i64>>>(),
+        )),
+        DataType::Timestamp => Arc::new(TimestampMicrosecondArray::from(
+            values
+                .iter()
+                .map(|v| match v {
+                    Value::Timestamp(d) => Some(d.and_utc().timestamp_micros()),
+                    _ => None,
+                })
+                .collect::<Vec<Option<i64>>>(),
+        )),
+        DataType::TimestampTz => Arc::new(
+            TimestampMicrosecondArray::from(
+                values
+                    .iter()
+                    .map(|v| match v {
+                        Value::TimestampTz(d) => Some(d.timestamp_micros()),
+                        _ => None,
+                    })
+                    .collect::<Vec<Option<i64>>>(),
+            )
+            .with_timezone(TIMEZONE),
+        ),
+        DataType::Array(element) => {
+            let items = values
+                .iter()
+                .map(|v| match v {
+                    Value::Array(items) => items.as_slice(),
+                    _ => &[],
+                })
+                .collect::<Vec<&[Value]>>();
+
+            let offsets = OffsetBuffer::from_lengths(items.iter().map(|items| items.len()));
+            let child = to_array(&items.concat(), element)?;
+            let field = Arc::new(ArrowField::new("item", type_to_arrow(element), true));
+
+            Arc::new(
+                ListArray::try_new(field, offsets, child, nulls(values))
+                    .map_err(|e| e.to_string())?,
+            )
+        }
+        DataType::Map(element) => {
+            let mut lengths = vec![];
+            let mut keys = vec![];
+            let mut items = vec![];
+
+            for value in values {
+                match value {
+                    Value::Map(map) => {
+                        lengths.push(map.len());
+                        for (key, item) in map.iter() {
+                            keys.push(key.as_str());
+                            items.push(item.clone());
+                        }
+                    }
+                    _ => lengths.push(0),
+                }
+            }
+
+            let entries = match map_entries(element).data_type() {
+                ArrowType::Struct(fields) => StructArray::try_new(
+                    fields.clone(),
+                    vec![
+                        Arc::new(StringArray::from(keys)),
+                        to_array(&items, element)?,
+                    ],
+                    None,
+                )
+                .map_err(|e| e.to_string())?,
+                _ => unreachable!("map entries are structs"),
+            };
+
+            Arc::new(
+                MapArray::try_new(
+                    Arc::new(map_entries(element)),
+                    OffsetBuffer::from_lengths(lengths),
+                    entries,
+                    nulls(values),
+                    false,
+                )
+                .map_err(|e| e.to_string())?,
+            )
+        }
+        DataType::Struct(fields) => {
+            let children = fields
+                .iter()
+                .map(|(name, dtype)| {
+                    let values = values
+                        .iter()
+                        .map(|v| match v {
+                            Value::Map(map) => map.get(name).cloned().unwrap_or(Value::Null),
+                            _ => Value::Null,
+                        })
+                        .collect::<Vec<Value>>();
+                    to_array(&values, dtype)
+                })
+                .collect::<std::result::Result<Vec<ArrayRef>, String>>()?;
+
+            Arc::new(
+                StructArray::try_new_with_length(
+                    struct_fields(fields),
+                    children,
+                    nulls(values),
+                    values.len(),
+                )
+                .map_err(|e| e.to_string())?,
+            )
+        }
+    };
+
+    Ok(array)
+}
+
+/// The values of the array, dictionaries are looked up once for the whole array
+fn array_values(array: &dyn Array) -> std::result::Result<Vec<Value>, String> {
+    if let ArrowType::Dictionary(_, _) = array.data_type() {
+        let dictionary = array.as_any_dictionary();
+        let values = array_values(dictionary.values().as_ref())?;
+
+        return Ok(dictionary
+            .normalized_keys()
+            .into_iter()
+            .enumerate()
+            .map(|(row, key)| match array.is_null(row) {
+                true => Value::Null,
+                false => values.get(key).cloned().unwrap_or(Value::Null),
+            })
+            .collect());
+    }
+
+    (0..array.len())
+        .map(|row| array_value(array, row))
+        .collect()
+}
+
+fn timestamp(n: i64, unit: &TimeUnit, timezone: &Option<Arc<str>>) -> Option<Value> {
+    let date = match unit {
+        TimeUnit::Second => DateTime::from_timestamp(n, 0),
+        TimeUnit::Millisecond => DateTime::from_timestamp_millis(n),
+        TimeUnit::Microsecond => DateTime::from_timestamp_micros(n),
+        TimeUnit::Nanosecond => Some(DateTime::from_timestamp_nanos(n)),
+    }?;
+
+    match timezone.as_deref() {
+        None => Some(Value::Timestamp(date.naive_utc())),
+        Some("UTC") | Some("Z") => Some(Value::TimestampTz(date.fixed_offset())),
+        Some(timezone) => {
+            let offset = timezone.parse::<FixedOffset>().ok()?;
+            Some(Value::TimestampTz(date.with_timezone(&offset)))
+        }
+    }
+}
+
+/// The value of the row of the array
+fn array_value(array: &dyn Array, row: usize) -> std::result::Result<Value, String> {
+    if array.is_null(row) {
+        return Ok(Value::Null);
+    }
+
+    let out_of_range = || format!("the value on row {} is out of range", row);
+
+    let value = match array.data_type() {
+        ArrowType::Null => Value::Null,
+        ArrowType::Boolean => Value::Bool(array.as_boolean().value(row)),
+        ArrowType::Utf8 => Value::from(array.as_string::<i32>().value(row)),
+        ArrowType::LargeUtf8 => Value::from(array.as_string::<i64>().value(row)),
+        ArrowType::Binary => Value::Binary(array.as_binary::<i32>().value(row).to_vec()),
+        ArrowType::LargeBinary => Value::Binary(array.as_binary::<i64>().value(row).to_vec()),
+        ArrowType::FixedSizeBinary(_) => {
+            Value::Binary(array.as_fixed_size_binary().value(row).to_vec())
+        }
+        ArrowType::UInt8 => Value::from(array.as_primitive::<UInt8Type>().value(row)),
+        ArrowType::UInt16 => Value::from(array.as_primitive::<UInt16Type>().value(row)),
+        ArrowType::UInt32 => Value::from(array.as_primitive::<UInt32Type>().value(row)),
+        ArrowType::UInt64 => Value::from(array.as_primitive::<UInt64Type>().value(row)),
+        ArrowType::Int8 => Value::from(array.as_primitive::<Int8Type>().value(row)),
+        ArrowType::Int16 => Value::from(array.as_primitive::<Int16Type>().value(row)),
+        ArrowType::Int32 => Value::from(array.as_primitive::<Int32Type>().value(row)),
+        ArrowType::Int64 => Value::from(array.as_primitive::<Int64Type>().value(row)),
+        ArrowType::Float32 => Value::from(array.as_primitive::<Float32Type>().value(row)),
+        ArrowType::Float64 => Value::from(array.as_primitive::<Float64Type>().value(row)),
+        ArrowType::Decimal128(_, scale) => {
+            let n = array.as_primitive::<Decimal128Type>().value(row);
+            let d = Decimal::try_from_i128_with_scale(n, *scale as u32)
+                .map_err(|_| format!("the decimal on row {} doesn't fit in 96 bits", row))?;
+            Value::Number(Number(Num::Decimal(d)))
+        }
+        ArrowType::Date32 => {
+            let days = array
+                .as_primitive::<arrow::datatypes::Date32Type>()
+                .value(row);
+            Value::Date(days_to_date(days).ok_or_else(out_of_range)?)
+        }
+        ArrowType::Date64 => {
+            let millis = array.as_primitive::<Date64Type>().value(row);
+            let date = DateTime::from_timestamp_millis(millis).ok_or_else(out_of_range)?;
+            Value::Date(date.date_naive())
+        }
+        ArrowType::Time32(unit) => {
+            let n = i64::from(match unit {
+                TimeUnit::Second => array.as_primitive::<Time32SecondType>().value(row),
+                _ => array.as_primitive::<Time32MillisecondType>().value(row),
+            });
+            let micros = match unit {
+                TimeUnit::Second => n * 1_000_000,
+                _ => n * 1000,
+            };
+            Value::Time(micros_to_time(micros).ok_or_else(out_of_range)?)
+        }
+        ArrowType::Time64(unit) => {
+            let time = match unit {
+                TimeUnit::Nanosecond => {
+                    let n = array.as_primitive::<Time64NanosecondType>().value(row);
+                    NaiveTime::from_num_seconds_from_midnight_opt(
+                        (n / 1_000_000_000) as u32,
+                        (n % 1_000_000_000) as u32,
+                    )
+                }
+                _ => micros_to_time(array.as_primitive::<Time64MicrosecondType>().value(row)),
+            };
+            Value::Time(time.ok_or_else(out_of_range)?)
+        }
+        ArrowType::Timestamp(unit, timezone) => {
+            let n = match unit {
+                TimeUnit::Second => array.as_primitive::<TimestampSecondType>().value(row),
+                TimeUnit::Millisecond => {
+                    array.as_primitive::<TimestampMillisecondType>().value(row)
+                }
+                TimeUnit::Microsecond => {
+                    array.as_primitive::<TimestampMicrosecondType>().value(row)
+                }
+                TimeUnit::Nanosecond => array.as_primitive::<TimestampNanosecondType>().value(row),
+            };
+            timestamp(n, unit, timezone).ok_or_else(out_of_range)?
+        }
+        ArrowType::List(_) => {
+            Value::Array(array_values(array.as_list::<i32>().value(row).as_ref())?)
+        }
+        ArrowType::LargeList(_) => {
+            Value::Array(array_values(array.as_list::<i64>().value(row).as_ref())?)
+        }
+        ArrowType::Struct(_) => {
+            let array = array.as_struct();
+            let mut map = Map::new();
+            for (name, column) in array.column_names().into_iter().zip(array.columns()) {
+                map.insert(name, array_value(column.as_ref(), row)?);
+            }
+            Value::Map(map)
+        }
+        ArrowType::Map(_, _) => {
+            let entries = array.as_map().value(row);
+            let keys = array_values(entries.column(0).as_ref())?;
+            let values = array_values(entries.column(1).as_ref())?;
+
+            let mut map = Map::new();
+            for (key, value) in keys.into_iter().zip(values) {
+                map.insert(key.to_string(), value);
+            }
+            Value::Map(map)
+        }
+        ArrowType::Dictionary(_, _) => {
+            let dictionary = array.as_any_dictionary();
+            let key = dictionary.normalized_keys()[row];
+            array_value(dictionary.values().as_ref(), key)?
+        }
+        other => return Err(format!("values of type {} can't be read", other)),
+    };
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::row;
+
+    #[test]
+    fn it_converts_types() {
+        let dtypes = vec![
+            DataType::Bool,
+            DataType::Uint16,
+            DataType::Int64,
+            DataType::decimal(10, 2),
+            DataType::Date,
+            DataType::Time,
+            DataType::Timestamp,
+            DataType::TimestampTz,
+            DataType::Binary,
+            DataType::array(DataType::Int32),
+            DataType::map(DataType::String),
+            DataType::Struct(vec![("x".to_string(), DataType::Double)]),
+        ];
+
+        for dtype in dtypes {
+            assert_eq!(arrow_to_type("a", &type_to_arrow(&dtype)).unwrap(), dtype);
+        }
+
+        assert!(matches!(
+            arrow_to_type("a", &ArrowType::Float16),
+            Err(Error::UnsupportedType { .. })
+        ));
+    }
+
+    #[test]
+    fn it_converts_record_batches() {
+        // columns of type any are of their values' type, or strings when the types differ
+        let df = DataFrame::new(
+            &["a", "b"],
+            vec![row![1, "x"], row![2, Value::Null], row![3, true]],
+        );
+
+        let batch = df.to_record_batch().unwrap();
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(batch.schema().field(0).data_type(), &ArrowType::Int32);
+        assert_eq!(batch.schema().field(1).data_type(), &ArrowType::Utf8);
+
+        let converted = DataFrame::from_record_batch(&batch).unwrap();
+        assert_eq!(converted.row(1).unwrap(), row![2, Value::Null]);
+        assert_eq!(converted.row(2).unwrap(), row![3, "true"]);
+    }
+
+    #[test]
+    fn it_rejects_decimals_that_dont_fit() {
+        let schema =
+            Schema::with_fields(vec![Field::with_type("d", DataType::decimal(4, 2))]).unwrap();
+        let decimal = |s: &str| Value::Number(Number(Num::Decimal(s.parse().unwrap())));
+
+        let df = DataFrame::with_data(schema, vec![vec![decimal("12.34")]]);
+        let batch = df.to_record_batch().unwrap();
+        assert_eq!(
+            DataFrame::from_record_batch(&batch)
+                .unwrap()
+                .row(0)
+                .unwrap(),
+            vec![decimal("12.34")]
+        );
+
+        for value in &["1.234", "123.4"] {
+            let values = vec![decimal("1.5"), Value::Null, decimal(value)];
+            let message = to_array(&values, &DataType::decimal(4, 2)).unwrap_err();
+            assert!(message.contains("row 2"), "{}", message);
+        }
+    }
+}
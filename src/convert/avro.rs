@@ -0,0 +1,770 @@
+use crate::{
+    ops::cast, value::map::Map, value::number::Num, DataType, Field, Number, Schema, Value,
+};
+
+use apache_avro::{types::Value as AvroValue, Decimal as AvroDecimal, Schema as AvroSchema};
+use chrono::{DateTime, NaiveDate, NaiveTime, Timelike};
+use rust_decimal::Decimal;
+use serde_json::{json, Value as JsonValue};
+use snafu::{ResultExt, Snafu};
+use std::convert::TryFrom;
+
+/// The precision of decimals without a size in exported schemas, the most digits a decimal can hold
+pub const DECIMAL_PRECISION: u32 = 28;
+
+/// The scale of decimals without a size in exported schemas, decimals with more decimal places
+/// can't be written
+pub const DECIMAL_SCALE: u32 = 10;
+
+/// The precision of the decimals uint64s are written as, since they don't fit in a long
+pub const UINT64_PRECISION: u32 = 20;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("The Avro schema is invalid: {}", source))]
+    InvalidAvroSchema { source: apache_avro::Error },
+
+    #[snafu(display("Expected an Avro record schema"))]
+    NotARecord,
+
+    #[snafu(display("Field {} has the unsupported Avro type {}", field, dtype))]
+    UnsupportedType { field: String, dtype: String },
+
+    #[snafu(display("The default of field {} is invalid: {}", field, message))]
+    InvalidDefault { field: String, message: String },
+
+    #[snafu(display("The Avro schema's fields are invalid: {}", source))]
+    SchemaError { source: crate::schema::Error },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The Avro type used for values of any type: arrays, maps and fields of type any. Logical types
+/// can't be in the union as well since readers treat them as the long they're stored as, so dates,
+/// times, timestamps and uint64s that don't fit in a long can't be written as any.
+fn any_type() -> JsonValue {
+    json!(["null", "boolean", "long", "double", "string", "bytes"])
+}
+
+/// Whether the union is any_type
+fn is_any_type(variants: &[AvroSchema]) -> bool {
+    variants
+        == [
+            AvroSchema::Null,
+            AvroSchema::Boolean,
+            AvroSchema::Long,
+            AvroSchema::Double,
+            AvroSchema::String,
+            AvroSchema::Bytes,
+        ]
+}
+
+impl Schema {
+    /// Convert the schema into an Avro record schema. Nullable fields are a union with null,
+    /// decimals are the decimal logical type, dates are the date logical type, times are
+    /// time-micros and timestamps are local-timestamp-millis, or timestamp-millis when they have a
+    /// timezone. Uint64s are decimals without decimal places, so they read back as decimals.
+    pub fn to_avro_schema(&self) -> Result<AvroSchema> {
+        let fields = self
+            .fields()
+            .iter()
+            .map(field_to_avro)
+            .collect::<Result<Vec<JsonValue>>>()?;
+
+        let mut record = json!({
+            "type": "record",
+            "name": self.name.clone().unwrap_or_else(|| "record".to_string()),
+            "fields": fields,
+        });
+
+        if let Some(doc) = &self.doc {
+            record["doc"] = json!(doc);
+        }
+
+        AvroSchema::parse(&record).context(InvalidAvroSchema)
+    }
+
+    /// Create a schema from an Avro record schema
+    pub fn from_avro_schema(avro: &AvroSchema) -> Result<Schema> {
+        let record = match avro {
+            AvroSchema::Record(record) => record,
+            _ => return Err(Error::NotARecord),
+        };
+
+        let fields = record
+            .fields
+            .iter()
+            .map(|field| {
+                let (dtype, nullable) = avro_to_dtype(&field.name, &field.schema)?;
+
+                let default = match &field.default {
+                    Some(default) => Some(
+                        default_from_avro(default, first_branch(&field.schema)).map_err(
+                            |message| Error::InvalidDefault {
+                                field: field.name.clone(),
+                                message,
+                            },
+                        )?,
+                    ),
+                    None => None,
+                };
+
+                let mut f = Field::with_type(&field.name, dtype).with_nullable(nullable);
+                f.default = default;
+                f.doc = field.doc.clone();
+                Ok(f)
+            })
+            .collect::<Result<Vec<Field>>>()?;
+
+        let mut schema = Schema::with_fields(fields).context(SchemaError)?;
+        schema.name = Some(record.name.name().to_string());
+        schema.doc = record.doc.clone();
+
+        Ok(schema)
+    }
+}
+
+fn field_to_avro(field: &Field) -> Result<JsonValue> {
+    let dtype = type_to_avro(&field.dtype, &field.name);
+    let default =
+        match &field.default {
+            Some(default) => Some(default_to_avro(default, &field.dtype).ok_or_else(|| {
+                Error::InvalidDefault {
+                    field: field.name.clone(),
+                    message: format!(
+                        "{} can't be the default of a {} field",
+                        default, field.dtype
+                    ),
+                }
+            })?),
+            None => None,
+        };
+
+    // the default of a union has to match its first type
+    let dtype = match (&field.dtype, field.nullable, &default) {
+        (DataType::Any, _, _) | (DataType::Null, _, _) | (_, false, _) => dtype,
+        (_, true, Some(default)) if !default.is_null() => json!([dtype, "null"]),
+        (_, true, _) => json!(["null", dtype]),
+    };
+
+    let mut avro = json!({ "name": field.name, "type": dtype });
+
+    if let Some(doc) = &field.doc {
+        avro["doc"] = json!(doc);
+    }
+
+    if let Some(default) = default {
+        avro["default"] = default;
+    }
+
+    Ok(avro)
+}
+
+/// The Avro type of the data type, records of structs are named after their path
+fn type_to_avro(dtype: &DataType, path: &str) -> JsonValue {
+    // elements of arrays, and values of maps and structs can be null
+    let nullable = |dtype: &DataType, path: &str| match dtype {
+        DataType::Any | DataType::Null => type_to_avro(dtype, path),
+        dtype => json!(["null", type_to_avro(dtype, path)]),
+    };
+
+    match dtype {
+        DataType::Null => json!("null"),
+        DataType::Bool => json!("boolean"),
+        DataType::String => json!("string"),
+        DataType::Binary => json!("bytes"),
+        DataType::Uint8 | DataType::Uint16 | DataType::Int8 | DataType::Int16 | DataType::Int32 => {
+            json!("int")
+        }
+        DataType::Uint32 | DataType::Int64 => json!("long"),
+        DataType::Uint64 => type_to_avro(&DataType::decimal(UINT64_PRECISION, 0), path),
+        DataType::Float => json!("float"),
+        DataType::Double => json!("double"),
+        DataType::Decimal(size) => {
+            let (precision, scale) = size.unwrap_or((DECIMAL_PRECISION, DECIMAL_SCALE));
+            json!({
+                "type": "bytes",
+                "logicalType": "decimal",
+                "precision": precision,
+                "scale": scale,
+            })
+        }
+        DataType::Date => json!({ "type": "int", "logicalType": "date" }),
+        DataType::Time => json!({ "type": "long", "logicalType": "time-micros" }),
+        DataType::Timestamp => json!({ "type": "long", "logicalType": "local-timestamp-millis" }),
+        DataType::TimestampTz => json!({ "type": "long", "logicalType": "timestamp-millis" }),
+        DataType::Array(element) => json!({ "type": "array", "items": nullable(element, path) }),
+        DataType::Map(element) => json!({ "type": "map", "values": nullable(element, path) }),
+        DataType::Struct(fields) => {
+            let fields = fields
+                .iter()
+                .map(|(name, dtype)| {
+                    json!({
+                        "name": name,
+                        "type": nullable(dtype, &format!("{}_{}", path, name)),
+                        "default": null,
+                    })
+                })
+                .collect::<Vec<JsonValue>>();
+            json!({ "type": "record", "name": path, "fields": fields })
+        }
+        DataType::Any => any_type(),
+    }
+}
+
+/// The Avro JSON encoding of a field's default
+fn default_to_avro(value: &Value, dtype: &DataType) -> Option<JsonValue> {
+    let json = match (value, dtype) {
+        (Value::Null, _) => JsonValue::Null,
+        (Value::Bool(b), DataType::Bool) => json!(b),
+        (Value::String(s), DataType::String) => json!(s),
+        (Value::Binary(b), DataType::Binary) => json!(latin1(b)),
+        (Value::Date(d), DataType::Date) => json!(date_to_days(d)),
+        (Value::Time(t), DataType::Time) => json!(time_to_micros(t)),
+        (Value::Timestamp(d), DataType::Timestamp) => json!(d.and_utc().timestamp_millis()),
+        (Value::TimestampTz(d), DataType::TimestampTz) => json!(d.timestamp_millis()),
+        (Value::Number(n), DataType::Decimal(size)) => {
+            let (_, scale) = size.unwrap_or((DECIMAL_PRECISION, DECIMAL_SCALE));
+            json!(latin1(&decimal_bytes(n, scale).ok()?))
+        }
+        (Value::Number(n), DataType::Uint64) => json!(latin1(&decimal_bytes(n, 0).ok()?)),
+        (Value::Number(n), DataType::Float) | (Value::Number(n), DataType::Double) => {
+            json!(n.clone().into_double().ok()?.as_f64())
+        }
+        (Value::Number(n), dtype) if dtype.is_numeric() => {
+            json!(n.clone().into_int64().ok()?.as_i64())
+        }
+        _ => return None,
+    };
+
+    Some(json)
+}
+
+/// Avro encodes bytes in JSON as a string with a character for each byte
+fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| char::from(*b)).collect()
+}
+
+/// The unscaled value of the number at the scale as big endian two's complement bytes, an error
+/// when the number has more decimal places than the scale or too many digits to be rescaled
+fn decimal_bytes(n: &Number, scale: u32) -> std::result::Result<Vec<u8>, String> {
+    let original = match n.clone().into_decimal().map_err(|e| e.to_string())?.0 {
+        Num::Decimal(d) => d,
+        _ => return Err(format!("{} isn't a decimal", n)),
+    };
+
+    let mut decimal = original;
+    decimal.rescale(scale);
+    if decimal != original || decimal.scale() != scale {
+        return Err(format!(
+            "{} can't be written with {} decimal places without losing precision",
+            original, scale
+        ));
+    }
+
+    let bytes = decimal.mantissa().to_be_bytes();
+
+    // strip the leading bytes that only extend the sign
+    let start = (0..bytes.len() - 1)
+        .find(|&i| {
+            !((bytes[i] == 0 && bytes[i + 1] & 0x80 == 0)
+                || (bytes[i] == 0xff && bytes[i + 1] & 0x80 != 0))
+        })
+        .unwrap_or(bytes.len() - 1);
+
+    Ok(bytes[start..].to_vec())
+}
+
+fn decimal_from_bytes(bytes: &[u8], scale: usize) -> Option<Decimal> {
+    if bytes.is_empty() || bytes.len() > 16 {
+        return None;
+    }
+
+    let fill = if bytes[0] & 0x80 != 0 { 0xff } else { 0 };
+    let mut buf = [fill; 16];
+    buf[16 - bytes.len()..].copy_from_slice(bytes);
+
+    Decimal::try_from_i128_with_scale(i128::from_be_bytes(buf), scale as u32).ok()
+}
+
+/// The schema of the union's first type, or the schema itself
+fn first_branch(schema: &AvroSchema) -> &AvroSchema {
+    match schema {
+        AvroSchema::Union(union) => union.variants().first().unwrap_or(schema),
+        _ => schema,
+    }
+}
+
+fn avro_to_dtype(field: &str, schema: &AvroSchema) -> Result<(DataType, bool)> {
+    let dtype = match schema {
+        AvroSchema::Null => return Ok((DataType::Null, true)),
+        AvroSchema::Boolean => DataType::Bool,
+        AvroSchema::Int => DataType::Int32,
+        AvroSchema::Long => DataType::Int64,
+        AvroSchema::Float => DataType::Float,
+        AvroSchema::Double => DataType::Double,
+        AvroSchema::Bytes | AvroSchema::Fixed(_) => DataType::Binary,
+        AvroSchema::String | AvroSchema::Enum(_) | AvroSchema::Uuid(_) => DataType::String,
+        AvroSchema::Array(array) => DataType::array(avro_to_dtype(field, &array.items)?.0),
+        AvroSchema::Map(map) => DataType::map(avro_to_dtype(field, &map.types)?.0),
+        AvroSchema::Record(record) => DataType::Struct(
+            record
+                .fields
+                .iter()
+                .map(|f| Ok((f.name.clone(), avro_to_dtype(&f.name, &f.schema)?.0)))
+                .collect::<Result<Vec<(String, DataType)>>>()?,
+        ),
+        // references to named types aren't resolved
+        AvroSchema::Ref { .. } => DataType::map(DataType::Any),
+        AvroSchema::Decimal(d) => DataType::decimal(d.precision as u32, d.scale as u32),
+        AvroSchema::BigDecimal => DataType::Decimal(None),
+        AvroSchema::Date => DataType::Date,
+        AvroSchema::TimeMillis | AvroSchema::TimeMicros => DataType::Time,
+        AvroSchema::TimestampMillis | AvroSchema::TimestampMicros | AvroSchema::TimestampNanos => {
+            DataType::TimestampTz
+        }
+        AvroSchema::LocalTimestampMillis
+        | AvroSchema::LocalTimestampMicros
+        | AvroSchema::LocalTimestampNanos => DataType::Timestamp,
+        AvroSchema::Union(union) => {
+            let types = union
+                .variants()
+                .iter()
+                .filter(|v| **v != AvroSchema::Null)
+                .collect::<Vec<&AvroSchema>>();
+            let nullable = types.len() != union.variants().len();
+
+            return match types.as_slice() {
+                [single] => avro_to_dtype(field, single).map(|(dtype, _)| (dtype, nullable)),
+                [] => Ok((DataType::Null, true)),
+                _ => Ok((DataType::Any, nullable)),
+            };
+        }
+        other => {
+            return Err(Error::UnsupportedType {
+                field: field.to_string(),
+                dtype: format!("{:?}", other),
+            })
+        }
+    };
+
+    Ok((dtype, false))
+}
+
+fn default_from_avro(json: &JsonValue, schema: &AvroSchema) -> std::result::Result<Value, String> {
+    let bytes = |json: &JsonValue| -> std::result::Result<Vec<u8>, String> {
+        json.as_str()
+            .ok_or_else(|| "expected bytes encoded as a string".to_string())?
+            .chars()
+            .map(|c| u8::try_from(u32::from(c)).map_err(|e| e.to_string()))
+            .collect()
+    };
+
+    let value = match (schema, json) {
+        (_, JsonValue::Null) => Value::Null,
+        (AvroSchema::Bytes, json) | (AvroSchema::Fixed(_), json) => Value::Binary(bytes(json)?),
+        (AvroSchema::Decimal(d), json) => decimal_from_bytes(&bytes(json)?, d.scale)
+            .map(|d| Value::Number(Number(Num::Decimal(d))))
+            .ok_or_else(|| "the decimal doesn't fit in 128 bits".to_string())?,
+        (AvroSchema::Int, json) | (AvroSchema::Long, json) => {
+            let dtype = match schema {
+                AvroSchema::Int => DataType::Int32,
+                _ => DataType::Int64,
+            };
+            cast::into_number(Value::from(json.clone()), &dtype).map_err(|e| e.to_string())?
+        }
+        (AvroSchema::Float, json) => cast::into_number(Value::from(json.clone()), &DataType::Float)
+            .map_err(|e| e.to_string())?,
+        (AvroSchema::Date, JsonValue::Number(n)) => Value::Date(
+            days_to_date(n.as_i64().ok_or("expected a number of days")? as i32)
+                .ok_or("the date is out of range")?,
+        ),
+        (AvroSchema::TimeMillis, JsonValue::Number(n)) => {
+            let n = n.as_i64().ok_or("expected a time")?;
+            Value::Time(micros_to_time(n * 1000).ok_or("the time is out of range")?)
+        }
+        (AvroSchema::TimeMicros, JsonValue::Number(n)) => {
+            let n = n.as_i64().ok_or("expected a time")?;
+            Value::Time(micros_to_time(n).ok_or("the time is out of range")?)
+        }
+        (s, JsonValue::Number(n)) if timestamp(s, 0).is_some() => {
+            let n = n.as_i64().ok_or("expected a timestamp")?;
+            timestamp(s, n).ok_or("the timestamp is out of range")?
+        }
+        (_, json) => Value::from(json.clone()),
+    };
+
+    Ok(value)
+}
+
+pub(crate) fn days_to_date(days: i32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(1970, 1, 1)?.checked_add_signed(chrono::Duration::days(i64::from(days)))
+}
+
+pub(crate) fn date_to_days(date: &NaiveDate) -> i32 {
+    date.signed_duration_since(NaiveDate::from_ymd_opt(1970, 1, 1).unwrap())
+        .num_days() as i32
+}
+
+pub(crate) fn micros_to_time(micros: i64) -> Option<NaiveTime> {
+    let seconds = u32::try_from(micros / 1_000_000).ok()?;
+    let nanos = u32::try_from(micros % 1_000_000 * 1000).ok()?;
+    NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanos)
+}
+
+pub(crate) fn time_to_micros(time: &NaiveTime) -> i64 {
+    i64::from(time.num_seconds_from_midnight()) * 1_000_000 + i64::from(time.nanosecond() / 1000)
+}
+
+/// Convert the timestamp in the schema's unit into a timestamp, local timestamps are naive and
+/// the others are in UTC
+fn timestamp(schema: &AvroSchema, n: i64) -> Option<Value> {
+    let date = match schema {
+        AvroSchema::TimestampMillis | AvroSchema::LocalTimestampMillis => {
+            DateTime::from_timestamp_millis(n)
+        }
+        AvroSchema::TimestampMicros | AvroSchema::LocalTimestampMicros => {
+            DateTime::from_timestamp_micros(n)
+        }
+        AvroSchema::TimestampNanos | AvroSchema::LocalTimestampNanos => {
+            Some(DateTime::from_timestamp_nanos(n))
+        }
+        _ => None,
+    }?;
+
+    match schema {
+        AvroSchema::LocalTimestampMillis
+        | AvroSchema::LocalTimestampMicros
+        | AvroSchema::LocalTimestampNanos => Some(Value::Timestamp(date.naive_utc())),
+        _ => Some(Value::from(date)),
+    }
+}
+
+/// Convert the value into an Avro value of the schema
+pub(crate) fn to_avro_value(
+    value: &Value,
+    schema: &AvroSchema,
+) -> std::result::Result<AvroValue, String> {
+    let mismatch = || format!("{} can't be written as {:?}", value, schema);
+
+    if let AvroSchema::Union(union) = schema {
+        let unsupported = match value {
+            Value::Date(_) | Value::Time(_) | Value::Timestamp(_) | Value::TimestampTz(_) => true,
+            Value::Number(n) => n.is_u64() && n.clone().into_int64().is_err(),
+            _ => false,
+        };
+
+        if unsupported && is_any_type(union.variants()) {
+            return Err(format!(
+                "{} can't be written as a value of type any, cast the column to the value's type \
+                 first",
+                value
+            ));
+        }
+    }
+
+    let avro = match (schema, value) {
+        (AvroSchema::Union(union), value) => {
+            return union
+                .variants()
+                .iter()
+                .enumerate()
+                .find_map(|(i, variant)| {
+                    to_avro_value(value, variant)
+                        .ok()
+                        .map(|v| AvroValue::Union(i as u32, Box::new(v)))
+                })
+                .ok_or_else(mismatch);
+        }
+        (AvroSchema::Null, Value::Null) => AvroValue::Null,
+        (AvroSchema::Boolean, Value::Bool(b)) => AvroValue::Boolean(*b),
+        (AvroSchema::String, Value::String(s)) => AvroValue::String(s.clone()),
+        (AvroSchema::Bytes, Value::Binary(b)) => AvroValue::Bytes(b.clone()),
+        (AvroSchema::Int, Value::Number(n)) => {
+            AvroValue::Int(n.clone().into_int32().map_err(|e| e.to_string())?.as_i32())
+        }
+        (AvroSchema::Long, Value::Number(n)) => {
+            AvroValue::Long(n.clone().into_int64().map_err(|e| e.to_string())?.as_i64())
+        }
+        (AvroSchema::Float, Value::Number(n)) => {
+            AvroValue::Float(n.clone().into_float().map_err(|e| e.to_string())?.as_f32())
+        }
+        (AvroSchema::Double, Value::Number(n)) => match n.inner() {
+            Num::Int64(i) => AvroValue::Double(*i as f64),
+            Num::Uint64(i) => AvroValue::Double(*i as f64),
+            _ => AvroValue::Double(n.clone().into_double().map_err(|e| e.to_string())?.as_f64()),
+        },
+        (AvroSchema::Decimal(d), Value::Number(n)) => {
+            AvroValue::Decimal(AvroDecimal::from(decimal_bytes(n, d.scale as u32)?))
+        }
+        (AvroSchema::Date, Value::Date(d)) => AvroValue::Date(date_to_days(d)),
+        (AvroSchema::TimeMicros, Value::Time(t)) => AvroValue::TimeMicros(time_to_micros(t)),
+        (AvroSchema::LocalTimestampMillis, Value::Timestamp(d)) => {
+            AvroValue::LocalTimestampMillis(d.and_utc().timestamp_millis())
+        }
+        (AvroSchema::TimestampMillis, Value::TimestampTz(d)) => {
+            AvroValue::TimestampMillis(d.timestamp_millis())
+        }
+        (AvroSchema::Array(array), Value::Array(items)) => AvroValue::Array(
+            items
+                .iter()
+                .map(|item| to_avro_value(item, &array.items))
+                .collect::<std::result::Result<Vec<AvroValue>, String>>()?,
+        ),
+        (AvroSchema::Map(map), Value::Map(values)) => AvroValue::Map(
+            values
+                .iter()
+                .map(|(k, v)| to_avro_value(v, &map.types).map(|v| (k.clone(), v)))
+                .collect::<std::result::Result<_, String>>()?,
+        ),
+        (AvroSchema::Record(record), Value::Map(values)) => {
+            if let Some(key) = values
+                .keys()
+                .find(|k| !record.fields.iter().any(|f| &f.name == *k))
+            {
+                return Err(format!("the record doesn't have the field {}", key));
+            }
+
+            AvroValue::Record(
+                record
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        let value = values.get(f.name.as_str()).unwrap_or(&Value::Null);
+                        to_avro_value(value, &f.schema).map(|v| (f.name.clone(), v))
+                    })
+                    .collect::<std::result::Result<_, String>>()?,
+            )
+        }
+        _ => return Err(mismatch()),
+    };
+
+    Ok(avro)
+}
+
+/// Convert the Avro value of the schema into a value
+pub(crate) fn from_avro_value(
+    value: AvroValue,
+    schema: &AvroSchema,
+) -> std::result::Result<Value, String> {
+    let value = match (value, schema) {
+        (AvroValue::Union(i, value), AvroSchema::Union(union)) => {
+            let variant = union
+                .variants()
+                .get(i as usize)
+                .ok_or_else(|| format!("the union has no type at {}", i))?;
+            return from_avro_value(*value, variant);
+        }
+        (AvroValue::Union(_, value), schema) => return from_avro_value(*value, schema),
+        (AvroValue::Null, _) => Value::Null,
+        (AvroValue::Boolean(b), _) => Value::Bool(b),
+        (AvroValue::Int(n), _) => Value::from(n),
+        (AvroValue::Long(n), _) => Value::from(n),
+        (AvroValue::Float(n), _) => Value::Number(Number(Num::Float(n))),
+        (AvroValue::Double(n), _) => Value::from(n),
+        (AvroValue::Bytes(b), _) | (AvroValue::Fixed(_, b), _) => Value::Binary(b),
+        (AvroValue::String(s), _) | (AvroValue::Enum(_, s), _) => Value::String(s),
+        (AvroValue::Uuid(u), _) => Value::String(u.to_string()),
+        (AvroValue::Decimal(d), AvroSchema::Decimal(schema)) => {
+            let bytes = Vec::<u8>::try_from(d).map_err(|e| e.to_string())?;
+            decimal_from_bytes(&bytes, schema.scale)
+                .map(|d| Value::Number(Number(Num::Decimal(d))))
+                .ok_or_else(|| "the decimal doesn't fit in 128 bits".to_string())?
+        }
+        (AvroValue::BigDecimal(d), _) => {
+            let s = d.to_string();
+            Decimal::from_str_exact(&s)
+                .or_else(|_| Decimal::from_scientific(&s))
+                .map(|d| Value::Number(Number(Num::Decimal(d))))
+                .map_err(|e| e.to_string())?
+        }
+        (AvroValue::Date(days), _) => {
+            Value::Date(days_to_date(days).ok_or("the date is out of range")?)
+        }
+        (AvroValue::TimeMillis(n), _) => {
+            Value::Time(micros_to_time(i64::from(n) * 1000).ok_or("the time is out of range")?)
+        }
+        (AvroValue::TimeMicros(n), _) => {
+            Value::Time(micros_to_time(n).ok_or("the time is out of range")?)
+        }
+        (AvroValue::TimestampMillis(n), _) => {
+            timestamp(&AvroSchema::TimestampMillis, n).ok_or("the timestamp is out of range")?
+        }
+        (AvroValue::TimestampMicros(n), _) => {
+            timestamp(&AvroSchema::TimestampMicros, n).ok_or("the timestamp is out of range")?
+        }
+        (AvroValue::TimestampNanos(n), _) => {
+            timestamp(&AvroSchema::TimestampNanos, n).ok_or("the timestamp is out of range")?
+        }
+        (AvroValue::LocalTimestampMillis(n), _) => timestamp(&AvroSchema::LocalTimestampMillis, n)
+            .ok_or("the timestamp is out of range")?,
+        (AvroValue::LocalTimestampMicros(n), _) => timestamp(&AvroSchema::LocalTimestampMicros, n)
+            .ok_or("the timestamp is out of range")?,
+        (AvroValue::LocalTimestampNanos(n), _) => {
+            timestamp(&AvroSchema::LocalTimestampNanos, n).ok_or("the timestamp is out of range")?
+        }
+        (AvroValue::Array(items), schema) => {
+            let items_schema = match schema {
+                AvroSchema::Array(array) => &array.items,
+                schema => schema,
+            };
+            Value::Array(
+                items
+                    .into_iter()
+                    .map(|item| from_avro_value(item, items_schema))
+                    .collect::<std::result::Result<Vec<Value>, String>>()?,
+            )
+        }
+        (AvroValue::Map(values), schema) => {
+            let values_schema = match schema {
+                AvroSchema::Map(map) => &map.types,
+                schema => schema,
+            };
+            let mut map = Map::new();
+            for (key, value) in values {
+                map.insert(key, from_avro_value(value, values_schema)?);
+            }
+            Value::Map(map)
+        }
+        (AvroValue::Record(fields), schema) => {
+            let mut map = Map::new();
+            for (i, (key, value)) in fields.into_iter().enumerate() {
+                let field_schema = match schema {
+                    AvroSchema::Record(record) => {
+                        record.fields.get(i).map(|f| &f.schema).unwrap_or(schema)
+                    }
+                    schema => schema,
+                };
+                map.insert(key, from_avro_value(value, field_schema)?);
+            }
+            Value::Map(map)
+        }
+        (value, _) => return Err(format!("{:?} is not supported", value)),
+    };
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_converts_schemas() {
+        let mut schema = Schema::with_fields(vec![
+            Field::with_type("id", DataType::Int64).with_nullable(false),
+            Field::with_type("name", DataType::String).with_default(Value::from("unknown")),
+            Field::with_type("price", DataType::decimal(12, 2)).with_nullable(false),
+            Field::with_type("created", DataType::TimestampTz),
+            Field::with_type("day", DataType::Date),
+            Field::with_type("at", DataType::Time),
+            Field::with_type("local", DataType::Timestamp),
+            Field::with_type("data", DataType::Binary).with_default(Value::Binary(vec![0, 255])),
+            Field::with_type("tags", DataType::array(DataType::Int64)),
+            Field::with_type(
+                "address",
+                DataType::structure(vec![
+                    ("city", DataType::String),
+                    ("zip", DataType::decimal(5, 0)),
+                ]),
+            ),
+            Field::new("anything"),
+        ])
+        .unwrap();
+        schema.name = Some("products".to_string());
+        schema.doc = Some("The products".to_string());
+
+        let avro = schema.to_avro_schema().unwrap();
+        let json: JsonValue = serde_json::from_str(&avro.canonical_form()).unwrap();
+        assert_eq!(json["fields"][1]["type"], json!(["string", "null"]));
+        match &avro {
+            AvroSchema::Record(record) => assert!(matches!(
+                &record.fields[2].schema,
+                AvroSchema::Decimal(d) if d.precision == 12 && d.scale == 2
+            )),
+            _ => panic!("expected a record"),
+        }
+
+        let back = Schema::from_avro_schema(&avro).unwrap();
+        assert_eq!(back.name, schema.name);
+        assert_eq!(back.doc, schema.doc);
+        assert_eq!(back.field_names(), schema.field_names());
+        assert_eq!(back[1].default(), Some(&Value::from("unknown")));
+        assert_eq!(back[7].default(), Some(&Value::Binary(vec![0, 255])));
+
+        for (a, b) in back.fields().iter().zip(schema.fields()) {
+            assert_eq!(a.dtype(), b.dtype());
+            assert_eq!(a.is_nullable(), b.is_nullable());
+        }
+
+        let invalid = Schema::with_fields(vec![
+            Field::with_type("a", DataType::Bool).with_default(Value::from(1))
+        ])
+        .unwrap();
+        assert!(matches!(
+            invalid.to_avro_schema(),
+            Err(Error::InvalidDefault { .. })
+        ));
+    }
+
+    #[test]
+    fn it_reads_avro_schema_files() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/users.avsc");
+        let avro = AvroSchema::parse_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let schema = Schema::from_avro_schema(&avro).unwrap();
+
+        assert_eq!(schema.name, Some("user".to_string()));
+        assert_eq!(schema[0].doc, Some("The user's id".to_string()));
+        assert!(schema[2].is_nullable());
+        assert_eq!(schema[2].default(), Some(&Value::Null));
+        assert_eq!(schema[3].dtype(), &DataType::decimal(10, 2));
+        assert_eq!(schema[4].dtype(), &DataType::TimestampTz);
+        assert_eq!(schema[5].default(), Some(&Value::Bool(true)));
+        assert_eq!(schema[6].dtype(), &DataType::array(DataType::String));
+
+        assert!(matches!(
+            Schema::from_avro_schema(&AvroSchema::String),
+            Err(Error::NotARecord)
+        ));
+    }
+
+    #[test]
+    fn it_converts_decimals() {
+        for &(mantissa, scale) in &[(12345i64, 2u32), (-5, 0), (0, 0), (-129, 1)] {
+            let n = Number(Num::Decimal(Decimal::new(mantissa, scale)));
+            let bytes = decimal_bytes(&n, DECIMAL_SCALE).unwrap();
+            assert_eq!(
+                decimal_from_bytes(&bytes, DECIMAL_SCALE as usize).map(|d| d.normalize()),
+                Some(Decimal::new(mantissa, scale).normalize())
+            );
+        }
+
+        assert_eq!(
+            decimal_bytes(&Number::from(1u8), DECIMAL_SCALE)
+                .unwrap()
+                .len(),
+            5
+        );
+
+        // digits past the scale would be lost
+        let n = Number(Num::Decimal(Decimal::new(123_456_789_012, 11)));
+        assert!(decimal_bytes(&n, DECIMAL_SCALE).is_err());
+        assert!(decimal_bytes(&Number::from(u64::MAX), 0).is_ok());
+    }
+
+    #[test]
+    fn it_rejects_values_of_type_any_without_an_avro_type() {
+        let any = AvroSchema::parse(&any_type()).unwrap();
+        let date = chrono::NaiveDate::from_ymd_opt(2020, 1, 2).unwrap();
+
+        for value in &[Value::Date(date), Value::from(u64::MAX)] {
+            let message = to_avro_value(value, &any).unwrap_err();
+            assert!(message.contains("type any"), "{}", message);
+        }
+
+        assert!(matches!(
+            to_avro_value(&Value::from(u64::from(u32::MAX)), &any),
+            Ok(AvroValue::Union(2, _))
+        ));
+    }
+}
@@ -0,0 +1,299 @@
+use crate::ops::cast::{into_number, try_cast};
+use crate::value::number::{Num, Number};
+use crate::{DataType, Field, Schema, Value};
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Which side of the field the value is against, the rest of the field is padding
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Justify {
+    Left,
+    Right,
+}
+
+/// A field of a fixed width record, the field is `width` characters long starting at the
+/// zero based `start` character of the record
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedField {
+    field: Field,
+    start: usize,
+    width: usize,
+    justify: Justify,
+    pad: char,
+    trim: bool,
+    implied_decimals: u32,
+    format: Option<String>,
+}
+
+impl FixedField {
+    /// Numbers are right justified and everything else is left justified, padded with spaces
+    pub fn new(field: Field, start: usize, width: usize) -> FixedField {
+        let justify = match field.dtype.is_numeric() {
+            true => Justify::Right,
+            false => Justify::Left,
+        };
+
+        FixedField {
+            field,
+            start,
+            width,
+            justify,
+            pad: ' ',
+            trim: true,
+            implied_decimals: 0,
+            format: None,
+        }
+    }
+
+    pub fn with_justify(mut self, justify: Justify) -> FixedField {
+        self.justify = justify;
+        self
+    }
+
+    /// The character the value is padded with, numbers padded with zeros keep their sign first
+    pub fn with_pad(mut self, pad: char) -> FixedField {
+        self.pad = pad;
+        self
+    }
+
+    /// Whether whitespace is trimmed from values when they are read, the padding is always
+    /// removed
+    pub fn with_trim(mut self, trim: bool) -> FixedField {
+        self.trim = trim;
+        self
+    }
+
+    /// The number of the digits that are after the decimal point, which isn't written
+    pub fn with_implied_decimals(mut self, decimals: u32) -> FixedField {
+        self.implied_decimals = decimals;
+        self
+    }
+
+    /// The strftime style format of dates, times and timestamps, which are ISO 8601 by default
+    pub fn with_format<S: Into<String>>(mut self, format: S) -> FixedField {
+        self.format = Some(format.into());
+        self
+    }
+
+    pub fn field(&self) -> &Field {
+        &self.field
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The index of the character after the field
+    pub fn end(&self) -> usize {
+        self.start + self.width
+    }
+
+    /// Read the value from the field's text, the field is null when it's blank or only padding
+    pub(crate) fn parse(&self, text: &str) -> Result<Value, String> {
+        if text.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+
+        let text = match self.justify {
+            Justify::Left => text.trim_end_matches(self.pad),
+            Justify::Right => text.trim_start_matches(self.pad),
+        };
+        let text = if self.trim { text.trim() } else { text };
+
+        let text = match text.is_empty() {
+            // a number padded with zeros is only padding when it's zero
+            true if self.field.dtype.is_numeric() && self.pad == '0' => "0",
+            true => return Ok(Value::Null),
+            false => text,
+        };
+
+        let value = match &self.field.dtype {
+            DataType::String | DataType::Any => Value::from(text),
+            DataType::Bool => match text.to_uppercase().as_str() {
+                "Y" | "YES" | "T" | "TRUE" | "1" => Value::Bool(true),
+                "N" | "NO" | "F" | "FALSE" | "0" => Value::Bool(false),
+                _ => return Err(format!("{} isn't a boolean", text)),
+            },
+            dtype if dtype.is_numeric() => {
+                let number = match self.implied_decimals {
+                    0 => Value::from(text),
+                    scale => {
+                        let mut decimal = Decimal::from_str(text).map_err(|e| e.to_string())?;
+                        if !text.contains('.') {
+                            decimal
+                                .set_scale(scale + decimal.scale())
+                                .map_err(|e| e.to_string())?;
+                        }
+                        Value::Number(Number::from(Num::Decimal(decimal)))
+                    }
+                };
+                into_number(number, dtype).map_err(|e| e.to_string())?
+            }
+            dtype if dtype.is_temporal() => match &self.format {
+                Some(format) => parse_temporal(text, format, dtype)
+                    .ok_or_else(|| format!("{} isn't a {} formatted as {}", text, dtype, format))?,
+                None => try_cast(Value::from(text), dtype).map_err(|e| e.to_string())?,
+            },
+            dtype => return Err(format!("{} can't be read from fixed width text", dtype)),
+        };
+
+        Ok(value)
+    }
+
+    /// Pad the text to the width of the field, or none when the text is wider than the field
+    pub(crate) fn pad(&self, text: &str) -> Option<String> {
+        let padding = self
+            .pad
+            .to_string()
+            .repeat(self.width.checked_sub(text.chars().count())?);
+
+        let text = match (self.justify, text.strip_prefix('-')) {
+            // the sign of a number padded with zeros comes before the zeros
+            (Justify::Right, Some(digits)) if self.pad == '0' => {
+                format!("-{}{}", padding, digits)
+            }
+            (Justify::Right, _) => format!("{}{}", padding, text),
+            (Justify::Left, _) => format!("{}{}", text, padding),
+        };
+
+        Some(text)
+    }
+
+    /// The text of the value without the padding, nulls are empty
+    pub(crate) fn text(&self, value: &Value) -> Result<String, String> {
+        let dtype = &self.field.dtype;
+        let value = match (value, dtype) {
+            (Value::Null, _) => return Ok(String::new()),
+            (value, DataType::Any) => value.clone(),
+            (value, dtype) => try_cast(value.clone(), dtype)
+                .map_err(|e| format!("{} can't be cast to {}: {}", value, dtype, e))?,
+        };
+
+        let text = match &value {
+            Value::Bool(b) => if *b { "Y" } else { "N" }.to_string(),
+            Value::Number(n) if self.implied_decimals > 0 => {
+                let mut decimal = match n.clone().into_decimal().map(|n| n.inner().clone()) {
+                    Ok(Num::Decimal(decimal)) => decimal,
+                    _ => return Err(format!("{} can't be a decimal", n)),
+                };
+                decimal.rescale(self.implied_decimals);
+                decimal.to_string().replace('.', "")
+            }
+            Value::Date(d) => match &self.format {
+                Some(format) => d.format(format).to_string(),
+                None => d.to_string(),
+            },
+            Value::Time(t) => match &self.format {
+                Some(format) => t.format(format).to_string(),
+                None => t.to_string(),
+            },
+            Value::Timestamp(d) => match &self.format {
+                Some(format) => d.format(format).to_string(),
+                None => d.format("%Y-%m-%dT%H:%M:%S%.f").to_string(),
+            },
+            Value::TimestampTz(d) => match &self.format {
+                Some(format) => d.format(format).to_string(),
+                None => d.to_rfc3339(),
+            },
+            Value::Array(_) | Value::Map(_) | Value::Binary(_) => {
+                return Err(format!("{} can't be written as fixed width text", dtype))
+            }
+            value => value.to_string(),
+        };
+
+        Ok(text)
+    }
+}
+
+fn parse_temporal(text: &str, format: &str, dtype: &DataType) -> Option<Value> {
+    match dtype {
+        DataType::Date => NaiveDate::parse_from_str(text, format)
+            .ok()
+            .map(Value::Date),
+        DataType::Time => NaiveTime::parse_from_str(text, format)
+            .ok()
+            .map(Value::Time),
+        DataType::Timestamp => NaiveDateTime::parse_from_str(text, format)
+            .ok()
+            .map(Value::Timestamp),
+        DataType::TimestampTz => chrono::DateTime::parse_from_str(text, format)
+            .ok()
+            .map(Value::TimestampTz),
+        _ => None,
+    }
+}
+
+/// The fields of fixed width records
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Layout {
+    fields: Vec<FixedField>,
+}
+
+impl Layout {
+    pub fn new() -> Layout {
+        Layout::default()
+    }
+
+    pub fn with_field(mut self, field: FixedField) -> Layout {
+        self.fields.push(field);
+        self
+    }
+
+    pub fn fields(&self) -> &[FixedField] {
+        &self.fields
+    }
+
+    /// The number of characters in a record, up to the end of the last field
+    pub fn width(&self) -> usize {
+        self.fields.iter().map(FixedField::end).max().unwrap_or(0)
+    }
+
+    /// The schema of the records' fields, it errors when their names or aliases collide
+    pub fn schema(&self) -> Result<Schema, crate::schema::Error> {
+        Schema::with_fields(self.fields.iter().map(|f| f.field.clone()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(field: &FixedField, value: &Value) -> Option<String> {
+        field.text(value).ok().and_then(|text| field.pad(&text))
+    }
+
+    fn decimal(num: i64, scale: u32) -> Value {
+        Value::Number(Number::from(Num::Decimal(Decimal::new(num, scale))))
+    }
+
+    #[test]
+    fn it_parses_and_formats_fields() {
+        let amount = FixedField::new(Field::with_type("amount", DataType::decimal(9, 2)), 0, 8)
+            .with_pad('0')
+            .with_implied_decimals(2);
+        assert_eq!(amount.parse("00012345").unwrap(), decimal(12345, 2));
+        assert_eq!(amount.parse("-0001250").unwrap(), decimal(-1250, 2));
+        assert_eq!(format(&amount, &decimal(-1250, 2)).unwrap(), "-0001250");
+        assert_eq!(format(&amount, &Value::from(1.5)).unwrap(), "00000150");
+        assert!(format(&amount, &Value::from(1234567.0)).is_none());
+
+        let name = FixedField::new(Field::with_type("name", DataType::String), 0, 6);
+        assert_eq!(name.parse("ab    ").unwrap(), Value::from("ab"));
+        assert_eq!(name.parse("      ").unwrap(), Value::Null);
+        assert_eq!(format(&name, &Value::from("ab")).unwrap(), "ab    ");
+        assert_eq!(format(&name, &Value::Null).unwrap(), "      ");
+
+        let date =
+            FixedField::new(Field::with_type("date", DataType::Date), 0, 8).with_format("%Y%m%d");
+        let day = NaiveDate::from_ymd_opt(2019, 9, 5).unwrap();
+        assert_eq!(date.parse("20190905").unwrap(), Value::Date(day));
+        assert_eq!(format(&date, &Value::Date(day)).unwrap(), "20190905");
+        assert!(date.parse("2019-09-").is_err());
+    }
+}
@@ -0,0 +1,695 @@
+use crate::{
+    ops::{cast, infer::integer_type},
+    schema::Constraint,
+    value::number::Num,
+    DataType, Field, Schema, Value,
+};
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use serde_json::{json, Map as JsonMap, Value as JsonValue};
+use snafu::{ResultExt, Snafu};
+
+/// The JSON Schema draft the exported schemas declare
+pub const JSON_SCHEMA_DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// The pattern of decimal strings, decimals are strings since JSON numbers are doubles
+pub const DECIMAL_PATTERN: &str = "^-?[0-9]+(\\.[0-9]+)?$";
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("The JSON Schema is invalid: {}", message))]
+    InvalidJsonSchema { message: String },
+
+    #[snafu(display("Property {} has the unsupported type {}", property, dtype))]
+    UnsupportedType { property: String, dtype: String },
+
+    #[snafu(display("The default of property {} is invalid: {}", property, message))]
+    InvalidDefault { property: String, message: String },
+
+    #[snafu(display("A constraint of property {} is invalid: {}", property, message))]
+    InvalidConstraint { property: String, message: String },
+
+    #[snafu(display("The JSON Schema's properties are invalid: {}", source))]
+    SchemaError { source: crate::schema::Error },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Schema {
+    /// Describe the schema as a JSON Schema object with a property for each field. Every field is
+    /// required, nullable fields also accept null.
+    pub fn to_json_schema(&self) -> JsonValue {
+        let mut schema = JsonMap::new();
+        schema.insert("$schema".to_string(), json!(JSON_SCHEMA_DRAFT));
+
+        if let Some(name) = &self.name {
+            schema.insert("title".to_string(), json!(name));
+        }
+
+        if let Some(doc) = &self.doc {
+            schema.insert("description".to_string(), json!(doc));
+        }
+
+        schema.insert("type".to_string(), json!("object"));
+
+        let properties = self
+            .fields()
+            .iter()
+            .map(|field| (field.name.clone(), field_to_json_schema(field)))
+            .collect::<JsonMap<String, JsonValue>>();
+
+        schema.insert("properties".to_string(), JsonValue::Object(properties));
+        schema.insert("required".to_string(), json!(self.field_names()));
+
+        JsonValue::Object(schema)
+    }
+
+    /// Create a schema from a JSON Schema object, with a field for each of its properties in
+    /// order. Integer properties become the narrowest type that holds their minimum and maximum.
+    pub fn from_json_schema(schema: &JsonValue) -> Result<Schema> {
+        let properties = schema
+            .get("properties")
+            .and_then(JsonValue::as_object)
+            .ok_or_else(|| Error::InvalidJsonSchema {
+                message: "expected an object with properties".to_string(),
+            })?;
+
+        let fields = properties
+            .iter()
+            .map(|(name, property)| field_from_json_schema(name, property))
+            .collect::<Result<Vec<Field>>>()?;
+
+        let mut s = Schema::with_fields(fields).context(SchemaError)?;
+        s.name = schema
+            .get("title")
+            .and_then(JsonValue::as_str)
+            .map(String::from);
+        s.doc = schema
+            .get("description")
+            .and_then(JsonValue::as_str)
+            .map(String::from);
+
+        Ok(s)
+    }
+}
+
+fn field_to_json_schema(field: &Field) -> JsonValue {
+    let mut property = type_to_json_schema(&field.dtype, field.nullable);
+
+    if let Some(doc) = &field.doc {
+        property.insert("description".to_string(), json!(doc));
+    }
+
+    if let Some(default) = &field.default {
+        property.insert("default".to_string(), value_to_json(default));
+    }
+
+    let constraints = field
+        .constraints
+        .iter()
+        .map(|constraint| constraint_to_json_schema(constraint, &field.dtype))
+        .collect::<Vec<(&str, JsonValue)>>();
+
+    // constraints using a keyword the type already uses, like the bounds of integers, can't be
+    // on the property itself, so then every constraint goes in an allOf to keep their order
+    let collides = constraints.iter().enumerate().any(|(i, (key, _))| {
+        property.contains_key(*key) || constraints[..i].iter().any(|(k, _)| k == key)
+    });
+
+    if collides {
+        let all_of = constraints
+            .into_iter()
+            .map(|(key, value)| json!({ key: value }))
+            .collect::<Vec<JsonValue>>();
+        property.insert("allOf".to_string(), JsonValue::Array(all_of));
+    } else {
+        for (key, value) in constraints {
+            property.insert(key.to_string(), value);
+        }
+    }
+
+    JsonValue::Object(property)
+}
+
+/// The keyword and value of the constraint. JSON Schema has no keywords for unique values or the
+/// length of binary data, so those are extension keywords.
+fn constraint_to_json_schema(
+    constraint: &Constraint,
+    dtype: &DataType,
+) -> (&'static str, JsonValue) {
+    // minimum and maximum only apply to numbers, other values use the format keywords
+    let numeric = dtype.is_numeric() && !matches!(dtype, DataType::Decimal(_));
+
+    match constraint {
+        Constraint::Min(min) if numeric => ("minimum", value_to_json(min)),
+        Constraint::Min(min) => ("formatMinimum", value_to_json(min)),
+        Constraint::Max(max) if numeric => ("maximum", value_to_json(max)),
+        Constraint::Max(max) => ("formatMaximum", value_to_json(max)),
+        Constraint::Pattern(pattern) => ("pattern", json!(pattern)),
+        Constraint::AllowedValues(values) => (
+            "enum",
+            JsonValue::Array(values.iter().map(value_to_json).collect()),
+        ),
+        Constraint::MaxLength(length) => match dtype {
+            DataType::Array(_) => ("maxItems", json!(length)),
+            DataType::Binary => ("x-maxBytes", json!(length)),
+            _ => ("maxLength", json!(length)),
+        },
+        Constraint::Unique => ("x-unique", json!(true)),
+    }
+}
+
+/// The keywords describing the type, the elements of arrays and values of maps and structs are
+/// nullable
+fn type_to_json_schema(dtype: &DataType, nullable: bool) -> JsonMap<String, JsonValue> {
+    let mut property = JsonMap::new();
+
+    let (kind, extra) = match dtype {
+        DataType::Bool => (Some("boolean"), json!({})),
+        DataType::String => (Some("string"), json!({})),
+        DataType::Array(element) => (
+            Some("array"),
+            json!({ "items": type_to_json_schema(element, true) }),
+        ),
+        DataType::Map(element) if element.is_any() => (Some("object"), json!({})),
+        DataType::Map(element) => (
+            Some("object"),
+            json!({ "additionalProperties": type_to_json_schema(element, true) }),
+        ),
+        DataType::Struct(fields) => {
+            let properties = fields
+                .iter()
+                .map(|(name, dtype)| (name.clone(), json!(type_to_json_schema(dtype, true))))
+                .collect::<JsonMap<String, JsonValue>>();
+            (
+                Some("object"),
+                json!({ "properties": properties, "additionalProperties": false }),
+            )
+        }
+        DataType::Date => (Some("string"), json!({ "format": "date" })),
+        DataType::Time => (Some("string"), json!({ "format": "time" })),
+        // json schema date-times have an offset, so timestamps without one get their own format
+        DataType::Timestamp => (Some("string"), json!({ "format": "local-date-time" })),
+        DataType::TimestampTz => (Some("string"), json!({ "format": "date-time" })),
+        DataType::Binary => (Some("string"), json!({ "contentEncoding": "base64" })),
+        DataType::Uint8 => (Some("integer"), bounds(u8::MIN, u8::MAX)),
+        DataType::Uint16 => (Some("integer"), bounds(u16::MIN, u16::MAX)),
+        DataType::Uint32 => (Some("integer"), bounds(u32::MIN, u32::MAX)),
+        DataType::Uint64 => (Some("integer"), bounds(u64::MIN, u64::MAX)),
+        DataType::Int8 => (Some("integer"), bounds(i8::MIN, i8::MAX)),
+        DataType::Int16 => (Some("integer"), bounds(i16::MIN, i16::MAX)),
+        DataType::Int32 => (Some("integer"), bounds(i32::MIN, i32::MAX)),
+        DataType::Int64 => (Some("integer"), bounds(i64::MIN, i64::MAX)),
+        DataType::Float => (Some("number"), json!({ "format": "float" })),
+        DataType::Double => (Some("number"), json!({ "format": "double" })),
+        DataType::Decimal(None) => (
+            Some("string"),
+            json!({ "format": "decimal", "pattern": DECIMAL_PATTERN }),
+        ),
+        DataType::Decimal(Some((precision, scale))) => (
+            Some("string"),
+            json!({
+                "format": "decimal",
+                "precision": precision,
+                "scale": scale,
+                "pattern": DECIMAL_PATTERN,
+            }),
+        ),
+        DataType::Null => (Some("null"), json!({})),
+        DataType::Any => (None, json!({})),
+    };
+
+    match kind {
+        Some(kind) if nullable && kind != "null" => {
+            property.insert("type".to_string(), json!([kind, "null"]));
+        }
+        Some(kind) => {
+            property.insert("type".to_string(), json!(kind));
+        }
+        None => {}
+    }
+
+    if let JsonValue::Object(extra) = extra {
+        property.extend(extra);
+    }
+
+    property
+}
+
+fn bounds<N: Into<JsonValue>>(min: N, max: N) -> JsonValue {
+    json!({ "minimum": min.into(), "maximum": max.into() })
+}
+
+fn field_from_json_schema(name: &str, property: &JsonValue) -> Result<Field> {
+    let (dtype, nullable) = type_from_json_schema(name, property)?;
+
+    let default = match property.get("default") {
+        Some(default) => {
+            Some(
+                json_to_value(default, &dtype).map_err(|message| Error::InvalidDefault {
+                    property: name.to_string(),
+                    message,
+                })?,
+            )
+        }
+        None => None,
+    };
+
+    let constraints = constraints_from_json_schema(name, property, &dtype)?;
+
+    let mut field = Field::with_type(name, dtype).with_nullable(nullable);
+    field.default = default;
+    field.constraints = constraints;
+    field.doc = property
+        .get("description")
+        .and_then(JsonValue::as_str)
+        .map(String::from);
+
+    Ok(field)
+}
+
+fn type_from_json_schema(name: &str, property: &JsonValue) -> Result<(DataType, bool)> {
+    let types = match property.get("type") {
+        None => vec![],
+        Some(JsonValue::String(t)) => vec![t.as_str()],
+        Some(JsonValue::Array(types)) => types.iter().filter_map(JsonValue::as_str).collect(),
+        Some(other) => {
+            return Err(Error::InvalidJsonSchema {
+                message: format!(
+                    "the type of {} is {}, expected a string or array",
+                    name, other
+                ),
+            })
+        }
+    };
+
+    let nullable = types.is_empty() || types.contains(&"null");
+    let types = types
+        .into_iter()
+        .filter(|t| *t != "null")
+        .collect::<Vec<&str>>();
+
+    let format = property.get("format").and_then(JsonValue::as_str);
+    let encoding = property.get("contentEncoding").and_then(JsonValue::as_str);
+
+    let dtype = match types.as_slice() {
+        [] if nullable && property.get("type").is_some() => DataType::Null,
+        [] => DataType::Any,
+        ["boolean"] => DataType::Bool,
+        ["string"] => match (format, encoding) {
+            (Some("date"), _) => DataType::Date,
+            (Some("time"), _) => DataType::Time,
+            (Some("local-date-time"), _) => DataType::Timestamp,
+            (Some("date-time"), _) => DataType::TimestampTz,
+            (Some("decimal"), _) => decimal_from_json_schema(property),
+            (_, Some("base64")) => DataType::Binary,
+            _ => DataType::String,
+        },
+        ["integer"] => {
+            let min = property.get("minimum").and_then(json_to_i128);
+            let max = property.get("maximum").and_then(json_to_i128);
+            match (min, max) {
+                (Some(min), Some(max)) => match integer_type(min, max) {
+                    // integers too large for an int64 or uint64
+                    DataType::Decimal(_) => DataType::Int64,
+                    dtype => dtype,
+                },
+                _ => DataType::Int64,
+            }
+        }
+        ["number"] => match format {
+            Some("float") => DataType::Float,
+            Some("decimal") => decimal_from_json_schema(property),
+            _ => DataType::Double,
+        },
+        ["array"] => match property.get("items") {
+            Some(items) => DataType::array(type_from_json_schema(name, items)?.0),
+            None => DataType::array(DataType::Any),
+        },
+        ["object"] => match (
+            property.get("properties").and_then(JsonValue::as_object),
+            property.get("additionalProperties"),
+        ) {
+            (Some(properties), _) => DataType::Struct(
+                properties
+                    .iter()
+                    .map(|(key, p)| Ok((key.clone(), type_from_json_schema(key, p)?.0)))
+                    .collect::<Result<Vec<(String, DataType)>>>()?,
+            ),
+            (None, Some(values)) if values.is_object() => {
+                DataType::map(type_from_json_schema(name, values)?.0)
+            }
+            _ => DataType::map(DataType::Any),
+        },
+        [other] => {
+            return Err(Error::UnsupportedType {
+                property: name.to_string(),
+                dtype: other.to_string(),
+            })
+        }
+        // properties that accept more than one type are weakly typed
+        _ => DataType::Any,
+    };
+
+    Ok((dtype, nullable))
+}
+
+/// Read the constraints from the property's keywords and the keywords of its allOf. The bounds of
+/// integers are only constraints when they're narrower than the type.
+fn constraints_from_json_schema(
+    name: &str,
+    property: &JsonValue,
+    dtype: &DataType,
+) -> Result<Vec<Constraint>> {
+    let invalid = |message: String| Error::InvalidConstraint {
+        property: name.to_string(),
+        message,
+    };
+    let value = |json: &JsonValue| json_to_value(json, dtype).map_err(invalid);
+
+    let all_of = property
+        .get("allOf")
+        .and_then(JsonValue::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let type_keywords = property
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(key, json)| match (key.as_str(), integer_bounds(dtype)) {
+            ("minimum", Some((min, _))) => json_to_i128(json) != Some(min),
+            ("maximum", Some((_, max))) => json_to_i128(json) != Some(max),
+            ("pattern", _) => !matches!(dtype, DataType::Decimal(_)) || *json != DECIMAL_PATTERN,
+            _ => true,
+        });
+    let keywords = type_keywords.chain(all_of.iter().filter_map(JsonValue::as_object).flatten());
+
+    let mut constraints = vec![];
+    for (key, json) in keywords {
+        let length = || {
+            json.as_u64()
+                .map(|n| n as usize)
+                .ok_or_else(|| invalid(format!("{} must be a non-negative integer", key)))
+        };
+
+        let constraint = match key.as_str() {
+            "minimum" | "formatMinimum" => Constraint::Min(value(json)?),
+            "maximum" | "formatMaximum" => Constraint::Max(value(json)?),
+            "pattern" => Constraint::Pattern(
+                json.as_str()
+                    .ok_or_else(|| invalid("pattern must be a string".to_string()))?
+                    .to_string(),
+            ),
+            "enum" => Constraint::AllowedValues(
+                json.as_array()
+                    .ok_or_else(|| invalid("enum must be an array".to_string()))?
+                    .iter()
+                    .map(value)
+                    .collect::<Result<Vec<Value>>>()?,
+            ),
+            "maxLength" | "maxItems" | "x-maxBytes" => Constraint::MaxLength(length()?),
+            "x-unique" if json == true => Constraint::Unique,
+            _ => continue,
+        };
+        constraints.push(constraint);
+    }
+
+    Ok(constraints)
+}
+
+/// The smallest and largest values of integer types
+fn integer_bounds(dtype: &DataType) -> Option<(i128, i128)> {
+    let bounds = match dtype {
+        DataType::Uint8 => (u8::MIN.into(), u8::MAX.into()),
+        DataType::Uint16 => (u16::MIN.into(), u16::MAX.into()),
+        DataType::Uint32 => (u32::MIN.into(), u32::MAX.into()),
+        DataType::Uint64 => (u64::MIN.into(), u64::MAX.into()),
+        DataType::Int8 => (i8::MIN.into(), i8::MAX.into()),
+        DataType::Int16 => (i16::MIN.into(), i16::MAX.into()),
+        DataType::Int32 => (i32::MIN.into(), i32::MAX.into()),
+        DataType::Int64 => (i64::MIN.into(), i64::MAX.into()),
+        _ => return None,
+    };
+
+    Some(bounds)
+}
+
+fn decimal_from_json_schema(property: &JsonValue) -> DataType {
+    let size = |key| {
+        property
+            .get(key)
+            .and_then(JsonValue::as_u64)
+            .map(|n| n as u32)
+    };
+
+    match (size("precision"), size("scale")) {
+        (Some(precision), Some(scale)) => DataType::decimal(precision, scale),
+        _ => DataType::Decimal(None),
+    }
+}
+
+fn json_to_i128(value: &JsonValue) -> Option<i128> {
+    value
+        .as_i64()
+        .map(i128::from)
+        .or_else(|| value.as_u64().map(i128::from))
+}
+
+pub(crate) fn value_to_json(value: &Value) -> JsonValue {
+    match value {
+        Value::Null => JsonValue::Null,
+        Value::Bool(b) => json!(b),
+        Value::String(s) => json!(s),
+        Value::Number(n) => match n.inner() {
+            Num::Uint8(n) => json!(n),
+            Num::Uint16(n) => json!(n),
+            Num::Uint32(n) => json!(n),
+            Num::Uint64(n) => json!(n),
+            Num::Int8(n) => json!(n),
+            Num::Int16(n) => json!(n),
+            Num::Int32(n) => json!(n),
+            Num::Int64(n) => json!(n),
+            Num::Float(n) => json!(n),
+            Num::Double(n) => json!(n),
+            // serde_json numbers are at most f64, so decimals are written as strings to keep
+            // their precision, matching the type of decimals in JSON Schema
+            Num::Decimal(n) => json!(n.to_string()),
+        },
+        Value::Date(d) => json!(d.format("%Y-%m-%d").to_string()),
+        Value::Time(t) => json!(t.format("%H:%M:%S%.f").to_string()),
+        Value::Timestamp(d) => json!(d.format("%Y-%m-%dT%H:%M:%S%.f").to_string()),
+        Value::TimestampTz(d) => json!(d.to_rfc3339()),
+        Value::Binary(b) => json!(base64::encode(b)),
+        Value::Array(a) => JsonValue::Array(a.iter().map(value_to_json).collect()),
+        Value::Map(m) => JsonValue::Object(
+            m.iter()
+                .map(|(k, v)| (k.clone(), value_to_json(v)))
+                .collect(),
+        ),
+    }
+}
+
+fn json_to_value(json: &JsonValue, dtype: &DataType) -> Result<Value, String> {
+    match (dtype, json) {
+        (_, JsonValue::Null) => Ok(Value::Null),
+        (DataType::Date, JsonValue::String(s)) => NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map(Value::Date)
+            .map_err(|e| e.to_string()),
+        (DataType::Time, JsonValue::String(s)) => NaiveTime::parse_from_str(s, "%H:%M:%S%.f")
+            .map(Value::Time)
+            .map_err(|e| e.to_string()),
+        (DataType::Timestamp, JsonValue::String(s)) => {
+            NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f")
+                .map(Value::Timestamp)
+                .map_err(|e| e.to_string())
+        }
+        (DataType::TimestampTz, JsonValue::String(s)) => DateTime::parse_from_rfc3339(s)
+            .map(Value::TimestampTz)
+            .map_err(|e| e.to_string()),
+        (DataType::Binary, JsonValue::String(s)) => base64::decode(s)
+            .map(Value::Binary)
+            .map_err(|e| e.to_string()),
+        (dtype, json) if dtype.is_numeric() => {
+            let value = match json {
+                JsonValue::String(s) => Value::from(s.as_str()),
+                json => Value::from(json.clone()),
+            };
+            cast::into_number(value, dtype).map_err(|e| e.to_string())
+        }
+        (_, json) => Ok(Value::from(json.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn decimal(num: i64, scale: u32) -> Value {
+        Value::Number(crate::Number(Num::Decimal(Decimal::new(num, scale))))
+    }
+
+    fn schema() -> Schema {
+        let mut id = Field::with_type("id", DataType::Uint32).with_nullable(false);
+        id.doc = Some("The id".to_string());
+
+        let mut schema = Schema::with_fields(vec![
+            id,
+            Field::with_type("name", DataType::String)
+                .with_default(Value::from("unknown"))
+                .with_constraint(Constraint::Pattern("^[a-z]+$".to_string()))
+                .with_constraint(Constraint::MaxLength(20))
+                .with_constraint(Constraint::Unique),
+            Field::with_type("price", DataType::decimal(10, 2))
+                .with_default(decimal(105, 1))
+                .with_constraint(Constraint::Min(decimal(0, 0))),
+            Field::with_type("created", DataType::TimestampTz),
+            Field::with_type("updated", DataType::Timestamp),
+            Field::with_type("day", DataType::Date)
+                .with_default(Value::Date(NaiveDate::from_ymd_opt(2019, 9, 5).unwrap())),
+            Field::with_type("data", DataType::Binary)
+                .with_default(Value::Binary(vec![1, 2, 3]))
+                .with_constraint(Constraint::MaxLength(16)),
+            Field::with_type("tags", DataType::array(DataType::String))
+                .with_constraint(Constraint::MaxLength(3)),
+            Field::with_type("attributes", DataType::map(DataType::Int64)),
+            Field::with_type("score", DataType::Int8)
+                .with_constraint(Constraint::Min(Value::from(0i8)))
+                .with_constraint(Constraint::Max(Value::from(100i8))),
+            Field::with_type("ratio", DataType::Double)
+                .with_nullable(false)
+                .with_constraint(Constraint::AllowedValues(vec![
+                    Value::from(0.5),
+                    Value::from(1.0),
+                ])),
+            Field::new("anything"),
+        ])
+        .unwrap();
+        schema.name = Some("products".to_string());
+        schema.doc = Some("The products".to_string());
+        schema
+    }
+
+    #[test]
+    fn it_exports_json_schema() {
+        let json = schema().to_json_schema();
+
+        assert_eq!(json["title"], "products");
+        assert_eq!(json["description"], "The products");
+        assert_eq!(json["required"][0], "id");
+
+        let id = &json["properties"]["id"];
+        assert_eq!(
+            id,
+            &json!({
+                "type": "integer",
+                "minimum": 0,
+                "maximum": 4294967295u32,
+                "description": "The id",
+            })
+        );
+
+        let properties = &json["properties"];
+        assert_eq!(properties["name"]["type"], json!(["string", "null"]));
+        assert_eq!(properties["name"]["default"], "unknown");
+        assert_eq!(properties["price"]["format"], "decimal");
+        assert_eq!(properties["created"]["format"], "date-time");
+        assert_eq!(properties["updated"]["format"], "local-date-time");
+        assert_eq!(properties["day"]["default"], "2019-09-05");
+        assert_eq!(properties["data"]["contentEncoding"], "base64");
+        assert_eq!(properties["data"]["default"], "AQID");
+        assert_eq!(properties["score"]["minimum"], -128);
+        assert_eq!(properties["anything"], json!({}));
+    }
+
+    #[test]
+    fn it_exports_constraints() {
+        let json = schema().to_json_schema();
+        let properties = &json["properties"];
+
+        assert_eq!(properties["name"]["pattern"], "^[a-z]+$");
+        assert_eq!(properties["name"]["maxLength"], 20);
+        assert_eq!(properties["name"]["x-unique"], true);
+        assert_eq!(properties["ratio"]["enum"], json!([0.5, 1.0]));
+        assert_eq!(properties["tags"]["maxItems"], 3);
+        assert_eq!(properties["data"]["x-maxBytes"], 16);
+
+        // the bounds of integers are their type, so the constraints are in an allOf
+        assert_eq!(properties["score"]["minimum"], -128);
+        assert_eq!(
+            properties["score"]["allOf"],
+            json!([{ "minimum": 0 }, { "maximum": 100 }])
+        );
+
+        // decimals are strings in json, so their defaults and bounds are too
+        assert_eq!(properties["price"]["type"], json!(["string", "null"]));
+        assert_eq!(properties["price"]["pattern"], DECIMAL_PATTERN);
+        assert_eq!(properties["price"]["default"], "10.5");
+        assert_eq!(properties["price"]["formatMinimum"], "0");
+    }
+
+    #[test]
+    fn it_round_trips_json_schema() {
+        let schema = schema();
+        assert_eq!(
+            Schema::from_json_schema(&schema.to_json_schema()).unwrap(),
+            schema
+        );
+    }
+
+    #[test]
+    fn it_imports_json_schema() {
+        let json = json!({
+            "properties": {
+                "small": { "type": "integer", "minimum": 0, "maximum": 100 },
+                "big": { "type": "integer" },
+                "when": {
+                    "type": "string",
+                    "format": "date-time",
+                    "default": "2019-09-05T18:14:04+02:00"
+                },
+                "mixed": { "type": ["string", "integer"] },
+                "code": { "type": "string", "enum": ["a", "b"], "maxLength": 1 },
+            }
+        });
+
+        let schema = Schema::from_json_schema(&json).unwrap();
+        assert_eq!(
+            schema.field_names(),
+            vec!["small", "big", "when", "mixed", "code"]
+        );
+        assert_eq!(schema[0].dtype(), &DataType::Uint8);
+        assert!(!schema[0].is_nullable());
+        assert_eq!(schema[1].dtype(), &DataType::Int64);
+        assert_eq!(schema[3].dtype(), &DataType::Any);
+
+        // integer bounds narrower than the type are constraints
+        assert_eq!(
+            schema[0].constraints(),
+            [Constraint::Max(Value::from(100u8))]
+        );
+        assert!(schema[1].constraints().is_empty());
+        assert_eq!(
+            schema[4].constraints(),
+            [
+                Constraint::AllowedValues(vec![Value::from("a"), Value::from("b")]),
+                Constraint::MaxLength(1)
+            ]
+        );
+
+        assert_eq!(schema[2].dtype(), &DataType::TimestampTz);
+        assert_eq!(
+            schema[2].default().map(|d| d.to_string()),
+            Some("2019-09-05 18:14:04 +02:00".to_string())
+        );
+
+        assert!(matches!(
+            Schema::from_json_schema(&json!({ "type": "object" })),
+            Err(Error::InvalidJsonSchema { .. })
+        ));
+        assert!(matches!(
+            Schema::from_json_schema(&json!({ "properties": { "a": { "type": "tuple" } } })),
+            Err(Error::UnsupportedType { .. })
+        ));
+    }
+}
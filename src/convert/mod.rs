@@ -1 +1,5 @@
-mod json;
+pub mod arrow;
+pub mod avro;
+pub mod fixed_width;
+pub mod json;
+pub mod json_schema;
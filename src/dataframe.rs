@@ -1,37 +1,59 @@
 use crate::{
+    column::Column,
     dim::Dim,
     error::{Error, Result},
     ops::{
         cast,
+        coerce::{self as coerce, CoercionReport},
         convert::{self as convert, Convert},
+        evolve,
+        infer::{self as infer, Inference},
+        keys::{self as keys, KeyIndex},
+        python::{self as python, PyTransform},
     },
     traits::TypeOf,
     views::{SubView, View},
     DataType, Field, Schema, Value,
 };
 
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::iter::{FromIterator, Iterator};
-use std::ops::Index;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataFrame<'a> {
-    pub(crate) data: Cow<'a, [Value]>,
+    /// The values of each of the schema's fields, in the same order as the fields
+    pub(crate) columns: Cow<'a, [Column]>,
     pub(crate) dim: Dim,
     pub(crate) schema: Schema,
+    /// The rows of the schema's keys, rebuilt when needed so it isn't serialized
+    #[serde(skip)]
+    pub(crate) keys: KeyIndex,
+    /// Counts the changes to the columns, so the key index can tell when it's stale
+    #[serde(skip)]
+    pub(crate) generation: u64,
 }
 
 impl<'a> std::default::Default for DataFrame<'a> {
     fn default() -> Self {
         DataFrame {
-            data: Cow::from(vec![]),
+            columns: Cow::from(vec![]),
             dim: Dim::default(),
             schema: Schema::default(),
+            keys: KeyIndex::default(),
+            generation: 0,
         }
     }
 }
 
+/// Dataframes are equal when their schemas and data are, whatever the state of their key index
+impl<'a, 'b> PartialEq<DataFrame<'b>> for DataFrame<'a> {
+    fn eq(&self, other: &DataFrame<'b>) -> bool {
+        self.dim == other.dim && self.schema == other.schema && self.columns == other.columns
+    }
+}
+
 // TODO logically reorder the methods
 
 impl<'a> DataFrame<'a> {
@@ -44,9 +66,9 @@ impl<'a> DataFrame<'a> {
     {
         let dim = Dim::new(columns.len(), data.len());
 
-        // TODO ensure that the length of the flattened data is equal the length of the columns *
-        // length of the data -- panic or return result
-        let data = data.into_iter().flatten().collect::<Vec<Value>>();
+        // TODO ensure that the length of each row is equal to the number of columns -- panic or
+        // return result
+        let data = transpose(&vec![&DataType::Any; columns.len()], data);
 
         let columns = columns
             .iter()
@@ -57,13 +79,29 @@ impl<'a> DataFrame<'a> {
         let mut schema = Schema::default();
 
         columns.iter().for_each(|col| {
-            let _ = schema.add_field(col);
+            schema.push_field_unchecked(Field::new(col.clone()));
         });
 
         DataFrame {
-            data: data.into(),
+            columns: data.into(),
             dim,
             schema,
+            keys: KeyIndex::default(),
+            generation: 0,
+        }
+    }
+
+    /// Create a dataframe from the schema and a column of values for each of its fields, all of
+    /// the same length
+    pub(crate) fn from_columns(schema: Schema, columns: Vec<Column>) -> DataFrame<'a> {
+        let rows = columns.first().map_or(0, Column::len);
+
+        DataFrame {
+            dim: Dim::new(schema.len(), rows),
+            columns: columns.into(),
+            schema,
+            keys: KeyIndex::default(),
+            generation: 0,
         }
     }
 
@@ -74,34 +112,42 @@ impl<'a> DataFrame<'a> {
     pub fn with_data(schema: Schema, data: Vec<Vec<Value>>) -> DataFrame<'a> {
         let dim = Dim::new(schema.len(), data.len());
 
-        let num_columns = schema.len();
-        let data = data
-            .into_iter()
-            .flatten()
-            .enumerate()
-            .map(|(i, mut v)| {
-                let col_idx = i % num_columns;
-                let dtype: &DataType = &schema[col_idx].dtype();
+        let mut columns = transpose(&dtypes(&schema), data);
 
-                if !dtype.is_any() && !v.is_null() && v.type_of() != dtype {
-                    v = cast::safe_cast(v, dtype);
-                }
+        for (column, field) in columns.iter_mut().zip(schema.fields()) {
+            let dtype: &DataType = field.dtype();
 
-                v
-            })
-            .collect::<Vec<Value>>();
+            let _ = column.try_for_each_mut(|v| {
+                if !dtype.matches(v) {
+                    *v = cast::safe_cast(std::mem::replace(v, Value::Null), dtype);
+                }
+                Ok::<(), ()>(())
+            });
+        }
 
         DataFrame {
-            data: Cow::from(data),
+            columns: Cow::from(columns),
             schema,
             dim,
+            keys: KeyIndex::default(),
+            generation: 0,
         }
     }
 
     pub fn with_schema<S: Into<Schema>>(s: S) -> DataFrame<'a> {
+        let schema = s.into();
+
         DataFrame {
-            schema: s.into(),
-            ..Default::default()
+            dim: Dim::new(schema.len(), 0),
+            columns: schema
+                .fields()
+                .iter()
+                .map(|f| Column::with_type(f.dtype()))
+                .collect::<Vec<Column>>()
+                .into(),
+            schema,
+            keys: KeyIndex::default(),
+            generation: 0,
         }
     }
 
@@ -124,14 +170,16 @@ impl<'a> DataFrame<'a> {
     }
 
     pub fn iter(&self) -> View<'_, 'a> {
-        View::new(&self)
+        View::new(self)
     }
 
     pub fn schema(&self) -> &Schema {
         &self.schema
     }
 
-    /// Derive the schema's types from the data. Automatically updates the schema.
+    /// Derive the schema's types from the data. Automatically updates the schema. Only looks at
+    /// the type of each value, columns with values of different types become any while arrays and
+    /// maps unify the types of their elements. Use infer_schema to parse the types out of strings.
     pub fn derive_schema(&mut self) {
         // get the keys of the columns and iterate over each column along with the values trying to
         // determine a more strict type and if the column contains null values
@@ -144,36 +192,46 @@ impl<'a> DataFrame<'a> {
             .collect::<Vec<String>>();
 
         for key in keys {
-            let mut dtype: DataType = DataType::Any;
-            let mut strict_dtype = true;
+            let mut dtype = DataType::Null;
             let mut is_nullable = false;
 
-            self.column_values(&key)
-                .unwrap()
-                .iter()
-                .for_each(|v| match (&dtype, v.type_of()) {
-                    (DataType::Any, vtype) => {
-                        dtype = vtype.clone();
-                    }
-                    (_, DataType::Null) => {
-                        is_nullable = true;
-                    }
-                    (col_type, vtype) => {
-                        if col_type != vtype {
-                            strict_dtype = false;
-                        }
-                    }
-                });
+            for v in self.column_values(&key).unwrap() {
+                match v.type_of() {
+                    DataType::Null => is_nullable = true,
+                    vtype => dtype = dtype.unify(&vtype),
+                }
+            }
 
             if let Some(field) = self.schema.get_field_mut(&key) {
-                field.dtype = dtype;
+                field.dtype = match dtype {
+                    DataType::Null => DataType::Any,
+                    dtype => dtype,
+                };
                 field.nullable = is_nullable;
             }
         }
     }
 
-    /// TODO attempt to cast/convert the data into the DF's schema
-    pub fn coherce_data(&mut self) {}
+    /// Infer the schema's types from the data, parsing strings to find the types they hold. See
+    /// Inference for the options, including rewriting the data into the inferred types.
+    pub fn infer_schema(&mut self, options: &Inference) -> Result<()> {
+        infer::infer_schema(self, options)
+    }
+
+    /// Cast the data into the schema's types, replacing nulls with the fields' defaults. Values
+    /// that can't be coerced, including nulls in fields that aren't nullable, are left as they
+    /// are and counted as failed in the report.
+    pub fn coherce_data(&mut self) -> CoercionReport {
+        coerce::coerce(self)
+    }
+
+    /// Reshape the data into the schema, see ops::evolve::evolve. Use Schema::diff to check the
+    /// changes first, and Schema::merge to get a schema that both the data and the new schema fit.
+    pub fn evolve_to(&mut self, schema: &Schema) -> Result<()> {
+        evolve::evolve(self, schema).map_err(|e| Error::EvolveError {
+            source: Box::new(e),
+        })
+    }
 
     pub fn columns(&self) -> Vec<&String> {
         self.schema.field_names()
@@ -193,21 +251,13 @@ impl<'a> DataFrame<'a> {
             return Err(Error::DuplicateColumnName { column: field.name });
         }
 
-        let col_index = self.schema.add_field(field);
+        let column = Column::with_values(&field.dtype, values);
+        self.schema
+            .push_field(field)
+            .map_err(|source| Error::SchemaError { source })?;
+        self.columns_mut().push(column);
         self.dim.0 += 1;
 
-        for row_num in (0..self.dim.1).rev() {
-            let index = (row_num + 1) * col_index;
-
-            dbg!(&index);
-
-            if index > self.data.len() {
-                self.data.to_mut().push(values[row_num].clone());
-            } else {
-                self.data.to_mut().insert(index, values[row_num].clone());
-            }
-        }
-
         Ok(())
     }
 
@@ -220,26 +270,14 @@ impl<'a> DataFrame<'a> {
             return Err(Error::DuplicateColumnName { column: field.name });
         }
 
-        let col_index = self.schema.add_field(field.clone());
+        self.schema
+            .push_field(field.clone())
+            .map_err(|source| Error::SchemaError { source })?;
         self.dim.0 += 1;
 
-        let field_val = if let Some(default_value) = field.default {
-            default_value
-        } else {
-            Value::Null
-        };
-
-        for row_num in 0..self.dim.1 {
-            let index = self.dim.get_value_index(row_num, col_index);
-
-            // insert NULL into the new column for each row at the index. If the index exceeds the
-            // current length, push the value to the end of the vec.
-            if index > self.data.len() {
-                self.data.to_mut().push(field_val.clone());
-            } else {
-                self.data.to_mut().insert(index, field_val.clone());
-            }
-        }
+        // fill the new column with the default or null for each row
+        let column = Column::repeat(field.default.unwrap_or(Value::Null), self.dim.1);
+        self.columns_mut().push(column);
 
         Ok(())
     }
@@ -255,17 +293,11 @@ impl<'a> DataFrame<'a> {
             });
         }
 
-        let field = field.unwrap();
+        let name = field.unwrap().name.clone();
 
-        // starting from the end of our value buffer delete elements for associated with the index
-        // being removed
-        for row_num in (0..self.dim.1).rev() {
-            let index = self.dim.get_value_index(row_num, column);
-            self.data.to_mut().remove(index);
-        }
+        self.columns_mut().remove(column);
 
         self.dim.0 -= 1;
-        let name = field.name.clone();
         let _ = self.schema.remove(&name);
 
         Ok(())
@@ -280,31 +312,47 @@ impl<'a> DataFrame<'a> {
     where
         F: FnMut(&mut Value) -> std::result::Result<(), Error>,
     {
-        let index = self
+        let index = *self
             .schema
             .find_index(column)
             .ok_or(Error::InvalidColumnName {
                 column: column.to_string(),
             })?;
 
-        // skip data up until the index and then step by the number of columns
-        // "a" | "b" | "c"
-        //  0  |  1  |  2
-        //  3  |  4  |  5
-        //  6  |  7  |  8
-        //  index(b) = 1, skip 1 then step by 3 (row length) -> [1, 4, 7]
+        self.columns_mut()[index].try_for_each_mut(func)
+    }
 
-        self.data
-            .to_mut()
-            .iter_mut()
-            .skip(*index)
-            .step_by(self.schema.len())
-            .map(func)
-            .collect()
+    /// Map over each value of the column in parallel. The new values are computed across the
+    /// thread pool and then written back in row order, stopping at the first error, so the result
+    /// is the same as calling map_column with the same function.
+    pub fn par_map_column<F>(&mut self, column: &str, func: F) -> Result<()>
+    where
+        F: Fn(&Value) -> std::result::Result<Value, Error> + Send + Sync,
+    {
+        let index = *self
+            .schema
+            .find_index(column)
+            .ok_or(Error::InvalidColumnName {
+                column: column.to_string(),
+            })?;
+
+        let column = &self.columns[index];
+        let values = (0..column.len())
+            .into_par_iter()
+            .map(|row| func(&column.get(row).unwrap_or(Value::Null)))
+            .collect::<Vec<std::result::Result<Value, Error>>>();
+
+        let column = &mut self.columns_mut()[index];
+        for (row, new_value) in values.into_iter().enumerate() {
+            column.set(row, new_value?);
+        }
+
+        Ok(())
     }
 
-    /// Return a columns values
-    pub fn column_values(&self, column: &str) -> Result<Vec<&Value>> {
+    /// Iterate over a column's values. Values stored as their type are converted as they are read,
+    /// so the column isn't copied.
+    pub fn column_values(&self, column: &str) -> Result<impl Iterator<Item = Value> + '_> {
         let index = self
             .schema
             .find_index(column)
@@ -312,12 +360,14 @@ impl<'a> DataFrame<'a> {
                 column: column.to_string(),
             })?;
 
-        Ok(self
-            .data
-            .iter()
-            .skip(*index)
-            .step_by(self.schema.len())
-            .collect())
+        Ok(self.columns[*index].iter())
+    }
+
+    /// Get a column's values and validity by the column's name
+    pub fn column(&self, column: &str) -> Option<&Column> {
+        self.schema
+            .find_index(column)
+            .and_then(|index| self.columns.get(*index))
     }
 
     /// try to cast the column and its values into a certain type
@@ -328,6 +378,14 @@ impl<'a> DataFrame<'a> {
         })
     }
 
+    /// cast_column, but with the values cast in parallel
+    pub fn par_cast_column(&mut self, column: &str, to_type: DataType) -> Result<()> {
+        cast::par_cast(self, column, &to_type).map(|_| {
+            let field = self.schema.get_field_mut(column).unwrap();
+            field.dtype = to_type;
+        })
+    }
+
     /// try to convert the column and values into a type using the conversion. Differs from cast as
     /// conversion as options (e.x. parsing a date requires the format of the date).
     pub fn convert_column(&mut self, column: &str, conversion: Convert) -> Result<()> {
@@ -338,19 +396,57 @@ impl<'a> DataFrame<'a> {
         })
     }
 
-    /// Get a row by its id/row number
-    pub fn row(&self, row: usize) -> Option<&[Value]> {
-        let (start, end) = self.dim.get_row_range(row);
+    /// convert_column, but with the values converted in parallel
+    pub fn par_convert_column(&mut self, column: &str, conversion: Convert) -> Result<()> {
+        convert::par_convert(self, column, conversion).map(|dtype| {
+            if let Some(field) = self.schema.get_field_mut(column) {
+                field.dtype = dtype;
+            }
+        })
+    }
+
+    /// Apply a transform written in python to the rows or a column of the dataframe
+    pub fn apply_python(&mut self, transform: &PyTransform) -> Result<()> {
+        python::apply(self, transform)
+    }
+
+    /// Get a row by its id/row number. The data is stored by column, so the row's values are
+    /// gathered from each column and the view owns them. Dataframes can't be indexed by row, as
+    /// there is no row to borrow.
+    pub fn row(&self, row: usize) -> Option<SubView<'_>> {
+        self.row_values(row)
+            .map(|values| SubView::new(&self.schema, Cow::Owned(values)))
+    }
 
-        if self.data.len() < end {
+    /// The values of the row, in the order of the columns
+    pub(crate) fn row_values(&self, row: usize) -> Option<Vec<Value>> {
+        if row >= self.dim.1 {
             return None;
         }
 
-        Some(&self.data.as_ref()[start..end])
+        self.columns.iter().map(|c| c.get(row)).collect()
+    }
+
+    /// Replace the data with the rows, which have to be in the order of the schema's fields
+    pub(crate) fn set_rows(&mut self, rows: Vec<Vec<Value>>) {
+        self.dim.1 = rows.len();
+        *self.columns_mut() = transpose(&dtypes(&self.schema), rows);
+    }
+
+    /// The columns to change, every change marks the key index as stale
+    pub(crate) fn columns_mut(&mut self) -> &mut Vec<Column> {
+        self.generation = self.generation.wrapping_add(1);
+        self.columns.to_mut()
+    }
+
+    /// Find the row with the values of the schema's primary key, in the order of the key's fields.
+    /// The values have to be of the fields' types.
+    pub fn find_by_key(&self, key: &[Value]) -> Option<SubView<'_>> {
+        keys::find(self, key)
     }
 
     /// Pushes new row onto the data, performs a check to ensure the length equals the number of
-    /// columns
+    /// columns and that the row doesn't break the schema's primary or unique keys
     pub fn push_row(&mut self, data: Vec<Value>) -> Result<usize> {
         if data.len() != self.dim.0 {
             return Err(Error::InvalidDataLength {
@@ -359,20 +455,41 @@ impl<'a> DataFrame<'a> {
             });
         }
 
-        self.push_row_unchecked(data);
+        keys::insert(self, vec![data])?;
+
+        Ok(self.dim.1)
+    }
+
+    /// push_row, but with the values coerced into the schema the same way as coherce_data. Errors
+    /// without pushing the row if any value can't be coerced.
+    pub fn push_row_typed(&mut self, data: Vec<Value>) -> Result<usize> {
+        if data.len() != self.dim.0 {
+            return Err(Error::InvalidDataLength {
+                expected: self.dim.0,
+                actual: data.len(),
+            });
+        }
+
+        let row = coerce::coerce_row(data, self.schema.fields(), self.dim.1).map_err(|e| {
+            Error::CoerceError {
+                source: Box::new(e),
+            }
+        })?;
 
-        // TODO should we try to cast the data into the columns type?
+        keys::insert(self, vec![row])?;
 
         Ok(self.dim.1)
     }
 
     pub fn push_row_unchecked(&mut self, data: Vec<Value>) {
-        self.data.to_mut().extend(data);
+        for (column, value) in self.columns_mut().iter_mut().zip(data) {
+            column.push(value);
+        }
         self.dim.1 += 1;
     }
 
     /// Extends the internal data with the vector of rows. Ensures that length of each row equals
-    /// the number of columns.
+    /// the number of columns, and that none of the rows break the schema's primary or unique keys.
     pub fn extend(&mut self, data: Vec<Vec<Value>>) -> Result<usize> {
         let len_check = data.iter().find(|r| r.len() != self.dim.0);
 
@@ -382,17 +499,45 @@ impl<'a> DataFrame<'a> {
                 actual: invalid_row.len(),
             }),
             _ => {
-                self.extend_unchecked(data);
+                keys::insert(self, data)?;
                 Ok(self.dim.1)
             }
         }
     }
 
+    /// extend, but with the values coerced into the schema the same way as coherce_data. Errors
+    /// without adding any rows if a value can't be coerced.
+    pub fn extend_typed(&mut self, data: Vec<Vec<Value>>) -> Result<usize> {
+        if let Some(invalid_row) = data.iter().find(|r| r.len() != self.dim.0) {
+            return Err(Error::InvalidDataLength {
+                expected: self.dim.0,
+                actual: invalid_row.len(),
+            });
+        }
+
+        let rows = data
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| coerce::coerce_row(row, self.schema.fields(), self.dim.1 + i))
+            .collect::<std::result::Result<Vec<Vec<Value>>, coerce::Error>>()
+            .map_err(|e| Error::CoerceError {
+                source: Box::new(e),
+            })?;
+
+        keys::insert(self, rows)?;
+
+        Ok(self.dim.1)
+    }
+
     pub fn extend_unchecked(&mut self, data: Vec<Vec<Value>>) {
         self.dim.1 += data.len();
-        self.data
-            .to_mut()
-            .extend(data.into_iter().flatten().collect::<Vec<Value>>());
+
+        let columns = self.columns_mut();
+        for row in data {
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
     }
 
     /// Print the data frame to std out for debugging
@@ -406,9 +551,12 @@ impl<'a> DataFrame<'a> {
         let mut type_row = Row::empty();
         let mut row = Row::empty();
         for c in self.columns() {
-            row.add_cell(Cell::new(&c));
+            row.add_cell(Cell::new(c));
             type_row.add_cell(Cell::new(
-                self.schema.get_field(&c).map_or("?", |f| &f.dtype.as_str()),
+                &self
+                    .schema
+                    .get_field(c)
+                    .map_or("?".into(), |f| f.dtype.as_str()),
             ));
         }
         table.add_row(row);
@@ -438,7 +586,7 @@ impl<'a> DataFrame<'a> {
     /// Clear the schema, and data, and reset the dimensions
     pub fn clear(&mut self) {
         self.schema.clear();
-        self.data.to_mut().clear();
+        self.columns_mut().clear();
         self.dim.0 = 0;
         self.dim.1 = 0;
     }
@@ -471,15 +619,25 @@ impl<'a> FromIterator<SubView<'a>> for DataFrame<'a> {
     }
 }
 
-/// Get the row at the specific index
-impl<'a> Index<usize> for DataFrame<'a> {
-    type Output = [Value];
+fn dtypes(schema: &Schema) -> Vec<&DataType> {
+    schema.fields().iter().map(Field::dtype).collect()
+}
 
-    fn index(&self, index: usize) -> &[Value] {
-        let (start, end) = self.dim.get_row_range(index);
+/// Split the rows into a column of each of the types for each of the row's values
+fn transpose(dtypes: &[&DataType], rows: Vec<Vec<Value>>) -> Vec<Column> {
+    let mut columns = vec![Vec::with_capacity(rows.len()); dtypes.len()];
 
-        &self.data[start..end]
+    for row in rows {
+        for (column, value) in columns.iter_mut().zip(row) {
+            column.push(value);
+        }
     }
+
+    columns
+        .into_iter()
+        .zip(dtypes)
+        .map(|(values, dtype)| Column::with_values(dtype, values))
+        .collect()
 }
 
 #[cfg(test)]
@@ -517,8 +675,8 @@ mod dataframe_tests {
     fn it_adds_column() {
         let mut df = DataFrame::empty();
 
-        df.add_column("a");
-        df.add_column("b");
+        assert!(df.add_column("a").is_ok());
+        assert!(df.add_column("b").is_ok());
 
         assert_eq!(df.shape(), (2, 0));
     }
@@ -530,18 +688,24 @@ mod dataframe_tests {
 
         assert_eq!(df.shape(), (1, 2));
 
-        df.add_column("b");
+        assert!(df.add_column("b").is_ok());
 
         assert_eq!(df.shape(), (2, 2));
-        assert_eq!(df[0], [1.into(), Value::Null]);
-        assert_eq!(df[1], [2.into(), Value::Null]);
+        assert_eq!(df.row(0).unwrap(), &[1.into(), Value::Null][..]);
+        assert_eq!(df.row(1).unwrap(), &[2.into(), Value::Null][..]);
 
         // shape (2, 2) to (3, 2)
-        df.add_column("c");
+        assert!(df.add_column("c").is_ok());
 
         assert_eq!(df.shape(), (3, 2));
-        assert_eq!(df[0], [1.into(), Value::Null, Value::Null]);
-        assert_eq!(df[1], [2.into(), Value::Null, Value::Null]);
+        assert_eq!(
+            df.row(0).unwrap(),
+            &[1.into(), Value::Null, Value::Null][..]
+        );
+        assert_eq!(
+            df.row(1).unwrap(),
+            &[2.into(), Value::Null, Value::Null][..]
+        );
     }
 
     #[test]
@@ -558,8 +722,8 @@ mod dataframe_tests {
 
         assert_eq!(df.columns(), ["b"]);
 
-        assert_eq!(df[0], [10.into()]);
-        assert_eq!(df[1], [20.into()]);
+        assert_eq!(df.row(0).unwrap(), &[10.into()][..]);
+        assert_eq!(df.row(1).unwrap(), &[20.into()][..]);
     }
 
     #[test]
@@ -572,13 +736,33 @@ mod dataframe_tests {
         assert!(res.is_ok());
 
         assert_eq!(df.shape(), (1, 2));
-        assert_eq!(df[0], [1.into()]);
-        assert_eq!(df[1], [2.into()]);
+        assert_eq!(df.row(0).unwrap(), &[1.into()][..]);
+        assert_eq!(df.row(1).unwrap(), &[2.into()][..]);
 
         // don't push row of incorrect length
         assert!(df.push_row(vec![1.into(), 2.into()]).is_err());
     }
 
+    #[test]
+    fn it_gathers_rows_from_columns() {
+        let mut df = DataFrame::new(&["a", "b"], vec![row![1, "x"], row![2, "y"]]);
+
+        let row = df.row(1).unwrap();
+        assert_eq!(row, &row![2, "y"][..]);
+        assert_eq!(row["b"], Value::from("y"));
+        assert_eq!(df.row(2), None);
+
+        df.map_column("a", |v| {
+            *v = Value::from(v.to_string() + "0");
+            Ok(())
+        })
+        .unwrap();
+        df.push_row(row![3, "z"]).unwrap();
+
+        assert_eq!(df.row(1).unwrap(), &row!["20", "y"][..]);
+        assert_eq!(df.row(2).unwrap(), &row![3, "z"][..]);
+    }
+
     #[test]
     fn it_iterates_column_values() {
         let mut df = DataFrame::new(
@@ -620,6 +804,26 @@ mod dataframe_tests {
         assert!(cast_result.is_ok());
     }
 
+    #[test]
+    fn it_casts_column_in_parallel() {
+        let rows = (0..1000)
+            .map(|i| row![i, i * 2])
+            .collect::<Vec<Vec<Value>>>();
+
+        let mut sequential = DataFrame::new(&["a", "b"], rows.clone());
+        let mut parallel = DataFrame::new(&["a", "b"], rows);
+
+        assert!(sequential.cast_column("b", DataType::Int64).is_ok());
+        assert!(parallel.par_cast_column("b", DataType::Int64).is_ok());
+
+        assert_eq!(sequential, parallel);
+        assert_eq!(parallel.row(999).unwrap(), &[val!(999), val!(1998i64)][..]);
+
+        assert!(parallel.par_cast_column("a", DataType::Uint8).is_err());
+        assert_eq!(parallel.row(255).unwrap(), &[val!(255u8), val!(510i64)][..]);
+        assert_eq!(parallel.row(256).unwrap(), &[val!(256), val!(512i64)][..]);
+    }
+
     #[test]
     fn it_derives_schema_from_data() {
         let mut df = df!(["a", "b"], [row![0, 1], row![2, 3], row![4, 5]]);
@@ -631,6 +835,38 @@ mod dataframe_tests {
 
         assert_eq!(df.schema()[0].dtype(), &DataType::Int32);
         assert_eq!(df.schema()[1].dtype(), &DataType::Int32);
+
+        let mut df = df!(["a", "b"], [row![Value::Null, 1], row![2, "x"]]);
+        df.derive_schema();
+
+        assert_eq!(df.schema()[0].dtype(), &DataType::Int32);
+        assert!(df.schema()[0].nullable);
+        assert_eq!(df.schema()[1].dtype(), &DataType::Any);
+    }
+
+    #[test]
+    fn it_pushes_typed_rows() {
+        let mut df = DataFrame::with_schema(
+            Schema::with_fields(vec![
+                Field::with_type("a", DataType::Int64).with_nullable(false),
+                Field::with_type("b", DataType::String),
+            ])
+            .unwrap(),
+        );
+
+        assert_eq!(df.push_row_typed(row!["1", 2]).unwrap(), 1);
+        assert_eq!(
+            df.extend_typed(vec![row![2, true], row![3, "x"]]).unwrap(),
+            3
+        );
+        assert_eq!(df.row(2).unwrap(), &[val!(3i64), val!("x")][..]);
+
+        assert!(matches!(
+            df.push_row_typed(row![Value::Null, "y"]),
+            Err(Error::CoerceError { .. })
+        ));
+        assert!(df.extend_typed(vec![row![4, "z"], row!["x", "z"]]).is_err());
+        assert_eq!(df.size(), 3);
     }
 
     #[test]
@@ -642,5 +878,13 @@ mod dataframe_tests {
             .is_ok());
 
         df.print(10);
+
+        // the column is stored as the field's type, not the type of its first value
+        df.push_column(
+            Field::with_type("d", DataType::Int64),
+            vec![Value::Null, Value::Null],
+        )
+        .unwrap();
+        assert_eq!(df.column("d").unwrap().dtype(), Some(DataType::Int64));
     }
 }
@@ -0,0 +1,137 @@
+use crate::convert::arrow;
+use crate::DataFrame;
+
+use ::arrow::error::ArrowError;
+use ::arrow::ipc::writer::{FileWriter, StreamWriter};
+use snafu::{ResultExt, Snafu};
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to create the arrow file: {}", source))]
+    CreateError { source: std::io::Error },
+
+    #[snafu(display("Failed to convert the dataframe to arrow: {}", source))]
+    ConvertError { source: arrow::Error },
+
+    #[snafu(display("Failed to write arrow: {}", source))]
+    WriteError { source: ArrowError },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Write the dataframe as Arrow IPC data, in a single record batch
+pub trait ArrowDestination {
+    /// Write the dataframe as an Arrow IPC file, returning the writer
+    fn to_arrow<W: std::io::Write>(&self, writer: W) -> Result<W>;
+
+    /// Write the dataframe as an Arrow IPC stream, returning the writer
+    fn to_arrow_stream<W: std::io::Write>(&self, writer: W) -> Result<W>;
+
+    fn to_arrow_path<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let file = std::fs::File::create(path).context(CreateError)?;
+        self.to_arrow(std::io::BufWriter::new(file))?;
+        Ok(())
+    }
+}
+
+impl<'a> ArrowDestination for DataFrame<'a> {
+    fn to_arrow<W: std::io::Write>(&self, writer: W) -> Result<W> {
+        let batch = self.to_record_batch().context(ConvertError)?;
+
+        let mut writer = FileWriter::try_new(writer, &batch.schema()).context(WriteError)?;
+        writer.write(&batch).context(WriteError)?;
+        writer.finish().context(WriteError)?;
+        writer.into_inner().context(WriteError)
+    }
+
+    fn to_arrow_stream<W: std::io::Write>(&self, writer: W) -> Result<W> {
+        let batch = self.to_record_batch().context(ConvertError)?;
+
+        let mut writer = StreamWriter::try_new(writer, &batch.schema()).context(WriteError)?;
+        writer.write(&batch).context(WriteError)?;
+        writer.finish().context(WriteError)?;
+        writer.into_inner().context(WriteError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::arrow::ArrowSource;
+    use crate::{row, value::map::Map, DataType, Field, Schema, Value};
+
+    fn point(x: i64) -> Value {
+        let mut map = Map::new();
+        map.insert("x", Value::from(x));
+        Value::Map(map)
+    }
+
+    #[test]
+    fn it_round_trips_arrow_files_and_streams() {
+        let time = chrono::NaiveTime::from_hms_opt(18, 14, 4).unwrap();
+        let date = chrono::NaiveDate::from_ymd_opt(2019, 9, 5).unwrap();
+        let timestamp = date.and_time(time);
+
+        let mut df = DataFrame::with_schema(
+            Schema::with_fields(vec![
+                Field::with_type("id", DataType::Int64).with_nullable(false),
+                Field::with_type("name", DataType::String).with_doc("the name"),
+                Field::with_type("price", DataType::decimal(10, 2)),
+                Field::with_type("point", DataType::structure(vec![("x", DataType::Int64)])),
+                Field::with_type("tags", DataType::array(DataType::String)),
+                Field::with_type("date", DataType::Date),
+                Field::with_type("time", DataType::Time),
+                Field::with_type("at", DataType::Timestamp),
+                Field::with_type("at_tz", DataType::TimestampTz),
+                Field::with_type("bytes", DataType::Binary),
+            ])
+            .unwrap()
+            .with_name("items"),
+        );
+        df.extend_unchecked(vec![row![
+            1i64,
+            "a",
+            1.5,
+            point(1),
+            Value::Array(vec!["x".into(), "y".into()]),
+            date,
+            time,
+            timestamp,
+            timestamp.and_utc().fixed_offset(),
+            Value::Binary(vec![0, 1])
+        ]]);
+        let mut nulls = vec![Value::Null; 10];
+        nulls[0] = 2i64.into();
+        df.extend_unchecked(vec![nulls]);
+        df.coherce_data();
+
+        let path = std::env::temp_dir().join(format!("retl-arrow-{}.arrow", std::process::id()));
+        df.to_arrow_path(&path).unwrap();
+        let read = DataFrame::from_arrow_path(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(read.schema().name(), Some("items"));
+        assert_eq!(read.schema()[1].doc(), Some("the name"));
+        for (read, field) in read.schema().fields().iter().zip(df.schema().fields()) {
+            assert_eq!(read.dtype(), field.dtype());
+        }
+        assert_eq!(read.row(0).unwrap().data(), df.row(0).unwrap().data());
+        assert_eq!(read.row(1).unwrap().data(), df.row(1).unwrap().data());
+
+        let bytes = df.to_arrow_stream(vec![]).unwrap();
+        let read = DataFrame::read_arrow_stream(&bytes[..]).unwrap();
+        assert_eq!(read.size(), 2);
+        assert_eq!(read.row(0).unwrap().data(), df.row(0).unwrap().data());
+
+        let mut invalid = vec![Value::Null; 10];
+        invalid[0] = "x".into();
+        df.extend_unchecked(vec![invalid]);
+        assert!(matches!(
+            df.to_arrow(vec![]),
+            Err(Error::ConvertError {
+                source: arrow::Error::ValueError { .. }
+            })
+        ));
+    }
+}
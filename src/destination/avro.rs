@@ -0,0 +1,175 @@
+use crate::convert::avro::{self, to_avro_value};
+use crate::DataFrame;
+
+use apache_avro::{types::Value as AvroValue, Schema as AvroSchema, Writer};
+use snafu::{ResultExt, Snafu};
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to create the avro file: {}", source))]
+    CreateError { source: std::io::Error },
+
+    #[snafu(display("Failed to convert the schema to avro: {}", source))]
+    SchemaError { source: avro::Error },
+
+    #[snafu(display("Failed to write the value of {} on row {}: {}", column, row, message))]
+    ValueError {
+        column: String,
+        row: usize,
+        message: String,
+    },
+
+    #[snafu(display("Failed to write avro: {}", source))]
+    WriteError { source: apache_avro::Error },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+pub trait AvroDestination {
+    /// Write the dataframe as an avro container file, returning the writer
+    fn to_avro<W: std::io::Write>(&self, writer: W) -> Result<W>;
+
+    fn to_avro_path<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let file = std::fs::File::create(path).context(CreateError)?;
+        self.to_avro(file)?;
+        Ok(())
+    }
+}
+
+impl<'a> AvroDestination for DataFrame<'a> {
+    fn to_avro<W: std::io::Write>(&self, writer: W) -> Result<W> {
+        let schema = self.schema().to_avro_schema().context(SchemaError)?;
+
+        let fields = match &schema {
+            AvroSchema::Record(record) => record.fields.clone(),
+            _ => unreachable!("schemas are converted into records"),
+        };
+
+        let mut writer = Writer::new(&schema, writer).context(WriteError)?;
+
+        for (row, values) in self.iter().enumerate() {
+            let record = values
+                .iter()
+                .zip(&fields)
+                .map(|(value, field)| {
+                    to_avro_value(value, &field.schema)
+                        .map(|v| (field.name.clone(), v))
+                        .map_err(|message| Error::ValueError {
+                            column: field.name.clone(),
+                            row,
+                            message,
+                        })
+                })
+                .collect::<Result<Vec<(String, AvroValue)>>>()?;
+
+            writer
+                .append_value(AvroValue::Record(record))
+                .context(WriteError)?;
+        }
+
+        writer.into_inner().context(WriteError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::avro::AvroSource;
+    use crate::value::number::Num;
+    use crate::{row, DataType, Field, Schema, Value};
+
+    fn point(x: i64) -> Value {
+        let mut map = crate::value::map::Map::new();
+        map.insert("x", Value::from(x));
+        Value::Map(map)
+    }
+
+    #[test]
+    fn it_round_trips_avro() {
+        let mut df = DataFrame::with_schema(
+            Schema::with_fields(vec![
+                Field::with_type("id", DataType::Int64).with_nullable(false),
+                Field::with_type("name", DataType::String),
+                Field::with_type("price", DataType::decimal(10, 2)),
+                Field::with_type("point", DataType::structure(vec![("x", DataType::Int64)])),
+                Field::with_type("tags", DataType::array(DataType::Any)),
+                Field::new("any"),
+            ])
+            .unwrap(),
+        );
+        df.extend_unchecked(vec![
+            row![
+                1i64,
+                "a",
+                1.5,
+                point(1),
+                Value::Array(vec![1i64.into()]),
+                true
+            ],
+            row![
+                2i64,
+                Value::Null,
+                Value::Null,
+                Value::Null,
+                Value::Array(vec![]),
+                "x"
+            ],
+        ]);
+        df.coherce_data();
+
+        let bytes = df.to_avro(vec![]).unwrap();
+        let read = DataFrame::read_avro(&bytes[..]).unwrap();
+
+        assert_eq!(read.columns(), df.columns());
+        assert_eq!(read.row(0).unwrap().data(), df.row(0).unwrap().data());
+        assert_eq!(read.row(1).unwrap().data(), df.row(1).unwrap().data());
+
+        df.extend_unchecked(vec![row![
+            "x",
+            "a",
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            1i64
+        ]]);
+        assert!(matches!(
+            df.to_avro(vec![]),
+            Err(Error::ValueError { row: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn it_writes_uint64s_and_exact_decimals() {
+        let decimal = |num: i64, scale: u32| {
+            Value::Number(crate::Number(Num::Decimal(rust_decimal::Decimal::new(
+                num, scale,
+            ))))
+        };
+
+        let mut df = DataFrame::with_schema(
+            Schema::with_fields(vec![
+                Field::with_type("big", DataType::Uint64),
+                Field::with_type("amount", DataType::Decimal(None)),
+            ])
+            .unwrap(),
+        );
+        df.extend_unchecked(vec![row![u64::MAX, decimal(15, 1)]]);
+
+        // uint64s are too large for a long, so they're written as decimals
+        let read = DataFrame::read_avro(&df.to_avro(vec![]).unwrap()[..]).unwrap();
+        assert_eq!(read.schema()[0].dtype(), &DataType::decimal(20, 0));
+        assert_eq!(
+            read.row(0).unwrap().data()[0].to_string(),
+            u64::MAX.to_string()
+        );
+        assert_eq!(read.row(0).unwrap().data()[1].to_string(), "1.5000000000");
+
+        // the amount has more decimal places than decimals are written with
+        df.extend_unchecked(vec![row![1u64, decimal(123_456_789_012, 11)]]);
+        assert!(matches!(
+            df.to_avro(vec![]),
+            Err(Error::ValueError { row: 1, .. })
+        ));
+    }
+}
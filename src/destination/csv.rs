@@ -65,5 +65,6 @@ mod tests {
         let res = df.to_csv();
 
         // TODO verify data written?
+        assert!(res.is_ok());
     }
 }
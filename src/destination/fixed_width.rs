@@ -0,0 +1,188 @@
+use crate::convert::fixed_width::Layout;
+use crate::DataFrame;
+
+use snafu::{ResultExt, Snafu};
+use std::io::Write;
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to create the fixed width file: {}", source))]
+    CreateError { source: std::io::Error },
+
+    #[snafu(display("Failed to write the fixed width records: {}", source))]
+    WriteError { source: std::io::Error },
+
+    #[snafu(display("The dataframe doesn't have the column {}", column))]
+    ColumnNotFound { column: String },
+
+    #[snafu(display(
+        "The value in column {} on row {} can't be written: {}",
+        column,
+        row,
+        message
+    ))]
+    ValueError {
+        column: String,
+        row: usize,
+        message: String,
+    },
+
+    #[snafu(display(
+        "The value {} in column {} on row {} is wider than the field's {} characters",
+        value,
+        column,
+        row,
+        width
+    ))]
+    OverflowError {
+        column: String,
+        row: usize,
+        value: String,
+        width: usize,
+    },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Write the dataframe as fixed width records, one per line. The layout's fields are the
+/// dataframe's columns of the same name, cast into the field's type, and the gaps between fields
+/// are spaces.
+pub trait FixedWidthDestination {
+    fn to_fixed_width<W: Write>(&self, writer: W, layout: &Layout) -> Result<W>;
+
+    fn to_fixed_width_path<P: AsRef<Path>>(&self, path: P, layout: &Layout) -> Result<()> {
+        let file = std::fs::File::create(path).context(CreateError)?;
+        self.to_fixed_width(std::io::BufWriter::new(file), layout)?;
+        Ok(())
+    }
+}
+
+impl<'a> FixedWidthDestination for DataFrame<'a> {
+    fn to_fixed_width<W: Write>(&self, mut writer: W, layout: &Layout) -> Result<W> {
+        let names = self.schema().field_names();
+        let columns = layout
+            .fields()
+            .iter()
+            .map(|field| {
+                let name = &field.field().name;
+                names
+                    .iter()
+                    .position(|column| *column == name)
+                    .ok_or_else(|| Error::ColumnNotFound {
+                        column: name.clone(),
+                    })
+            })
+            .collect::<Result<Vec<usize>>>()?;
+
+        let mut line = vec![];
+        for row in 0..self.size() {
+            let values = self.row_values(row).unwrap_or_default();
+
+            line.clear();
+            line.resize(layout.width(), ' ');
+            for (field, column) in layout.fields().iter().zip(&columns) {
+                let name = &field.field().name;
+                let text = field
+                    .text(&values[*column])
+                    .map_err(|message| Error::ValueError {
+                        column: name.clone(),
+                        row,
+                        message,
+                    })?;
+                let text = field.pad(&text).ok_or_else(|| Error::OverflowError {
+                    column: name.clone(),
+                    row,
+                    value: text.clone(),
+                    width: field.width(),
+                })?;
+
+                for (i, c) in text.chars().enumerate() {
+                    line[field.start() + i] = c;
+                }
+            }
+
+            let mut record = line.iter().collect::<String>();
+            record.push('\n');
+            writer.write_all(record.as_bytes()).context(WriteError)?;
+        }
+
+        writer.flush().context(WriteError)?;
+        Ok(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::fixed_width::{FixedField, Justify};
+    use crate::source::fixed_width::FixedWidthSource;
+    use crate::{row, DataType, Field, Schema, Value};
+
+    #[test]
+    fn it_writes_fixed_width_records() {
+        let date = chrono::NaiveDate::from_ymd_opt(2019, 9, 5).unwrap();
+        let schema = Schema::with_fields(vec![
+            Field::with_type("id", DataType::Int64),
+            Field::with_type("name", DataType::String),
+            Field::with_type("price", DataType::decimal(9, 2)),
+            Field::with_type("date", DataType::Date),
+        ])
+        .unwrap();
+        let mut df = DataFrame::with_schema(schema.clone());
+        df.extend_unchecked(vec![
+            row![1i64, "apple", 1.5, date],
+            row![22i64, Value::Null, -0.25, Value::Null],
+        ]);
+        df.coherce_data();
+
+        // the layout leaves a gap between the name and the price
+        let layout = Layout::new()
+            .with_field(
+                FixedField::new(Field::with_type("id", DataType::Int64), 0, 4).with_pad('0'),
+            )
+            .with_field(
+                FixedField::new(Field::with_type("name", DataType::String), 4, 6)
+                    .with_justify(Justify::Right),
+            )
+            .with_field(
+                FixedField::new(Field::with_type("price", DataType::decimal(9, 2)), 12, 6)
+                    .with_pad('0')
+                    .with_implied_decimals(2),
+            )
+            .with_field(
+                FixedField::new(Field::with_type("date", DataType::Date), 18, 8)
+                    .with_format("%Y%m%d"),
+            );
+
+        let text = df.to_fixed_width(vec![], &layout).unwrap();
+        assert_eq!(
+            String::from_utf8(text.clone()).unwrap(),
+            "0001 apple  00015020190905\n0022        -00025        \n"
+        );
+
+        let read = DataFrame::read_fixed_width(&text[..], &layout).unwrap();
+        assert_eq!(read.schema(), &schema);
+        assert_eq!(read, df);
+
+        let narrow = Layout::new().with_field(FixedField::new(
+            Field::with_type("name", DataType::String),
+            0,
+            3,
+        ));
+        assert!(matches!(
+            df.to_fixed_width(vec![], &narrow),
+            Err(Error::OverflowError { .. })
+        ));
+
+        let missing = Layout::new().with_field(FixedField::new(
+            Field::with_type("missing", DataType::String),
+            0,
+            3,
+        ));
+        assert!(matches!(
+            df.to_fixed_width(vec![], &missing),
+            Err(Error::ColumnNotFound { .. })
+        ));
+    }
+}
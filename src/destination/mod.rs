@@ -1,3 +1,8 @@
+pub mod arrow;
+pub mod avro;
 pub mod csv;
-
-use super::error::Error;
+pub mod fixed_width;
+pub mod parquet;
+pub mod postgres;
+pub mod sql;
+pub mod sqlite;
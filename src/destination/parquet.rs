@@ -0,0 +1,231 @@
+use crate::convert::arrow;
+use crate::DataFrame;
+
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression as ParquetCompression, ZstdLevel};
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use snafu::{ResultExt, Snafu};
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to create the parquet file: {}", source))]
+    CreateError { source: std::io::Error },
+
+    #[snafu(display("Failed to convert the dataframe to arrow: {}", source))]
+    ConvertError { source: arrow::Error },
+
+    #[snafu(display("Failed to write parquet: {}", source))]
+    WriteError { source: ParquetError },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The default number of rows in each row group
+pub const ROW_GROUP_SIZE: usize = 1024 * 1024;
+
+/// The codec the pages are compressed with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compression {
+    Uncompressed,
+    Snappy,
+    Zstd,
+}
+
+impl From<Compression> for ParquetCompression {
+    fn from(compression: Compression) -> ParquetCompression {
+        match compression {
+            Compression::Uncompressed => ParquetCompression::UNCOMPRESSED,
+            Compression::Snappy => ParquetCompression::SNAPPY,
+            Compression::Zstd => ParquetCompression::ZSTD(ZstdLevel::default()),
+        }
+    }
+}
+
+/// Options for writing parquet, by default pages are compressed with snappy
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    compression: Compression,
+    row_group_size: usize,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            compression: Compression::Snappy,
+            row_group_size: ROW_GROUP_SIZE,
+        }
+    }
+}
+
+impl Options {
+    pub fn new() -> Options {
+        Options::default()
+    }
+
+    pub fn with_compression(mut self, compression: Compression) -> Options {
+        self.compression = compression;
+        self
+    }
+
+    /// Set the maximum number of rows in each row group
+    pub fn with_row_group_size(mut self, row_group_size: usize) -> Options {
+        self.row_group_size = row_group_size.max(1);
+        self
+    }
+
+    fn properties(&self) -> WriterProperties {
+        WriterProperties::builder()
+            .set_compression(self.compression.into())
+            .set_max_row_group_row_count(Some(self.row_group_size))
+            .build()
+    }
+}
+
+/// Write the dataframe as a Parquet file. The schema is converted with Schema::to_arrow_schema,
+/// so fields that aren't nullable are required columns.
+pub trait ParquetDestination {
+    fn to_parquet<W: std::io::Write + Send>(&self, writer: W) -> Result<W> {
+        self.to_parquet_with_options(writer, &Options::default())
+    }
+
+    fn to_parquet_with_options<W: std::io::Write + Send>(
+        &self,
+        writer: W,
+        options: &Options,
+    ) -> Result<W>;
+
+    fn to_parquet_path<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.to_parquet_path_with_options(path, &Options::default())
+    }
+
+    fn to_parquet_path_with_options<P: AsRef<Path>>(
+        &self,
+        path: P,
+        options: &Options,
+    ) -> Result<()> {
+        let file = std::fs::File::create(path).context(CreateError)?;
+        self.to_parquet_with_options(file, options)?;
+        Ok(())
+    }
+}
+
+impl<'a> ParquetDestination for DataFrame<'a> {
+    fn to_parquet_with_options<W: std::io::Write + Send>(
+        &self,
+        writer: W,
+        options: &Options,
+    ) -> Result<W> {
+        let batch = self.to_record_batch().context(ConvertError)?;
+
+        let mut writer = ArrowWriter::try_new(writer, batch.schema(), Some(options.properties()))
+            .context(WriteError)?;
+        writer.write(&batch).context(WriteError)?;
+        writer.into_inner().context(WriteError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::parquet::{self as source, ParquetSource};
+    use crate::{row, DataType, Field, Schema, Value};
+
+    fn dataframe<'a>() -> DataFrame<'a> {
+        let date = chrono::NaiveDate::from_ymd_opt(2019, 9, 5).unwrap();
+        let timestamp = date.and_hms_opt(18, 14, 4).unwrap();
+
+        let mut df = DataFrame::with_schema(
+            Schema::with_fields(vec![
+                Field::with_type("id", DataType::Int64).with_nullable(false),
+                Field::with_type("name", DataType::String).with_doc("the name"),
+                Field::with_type("price", DataType::decimal(10, 2)),
+                Field::with_type("tags", DataType::array(DataType::String)),
+                Field::with_type("date", DataType::Date),
+                Field::with_type("at_tz", DataType::TimestampTz),
+            ])
+            .unwrap()
+            .with_name("items"),
+        );
+        df.extend_unchecked(
+            (0..10i64)
+                .map(|i| match i % 2 {
+                    0 => row![
+                        i,
+                        format!("item {}", i),
+                        1.5,
+                        Value::Array(vec!["x".into()]),
+                        date,
+                        timestamp.and_utc().fixed_offset()
+                    ],
+                    _ => row![
+                        i,
+                        Value::Null,
+                        Value::Null,
+                        Value::Null,
+                        Value::Null,
+                        Value::Null
+                    ],
+                })
+                .collect(),
+        );
+        df.coherce_data();
+        df
+    }
+
+    #[test]
+    fn it_round_trips_parquet_files() {
+        let df = dataframe();
+        let path = std::env::temp_dir().join(format!("retl-{}.parquet", std::process::id()));
+
+        let options = Options::new()
+            .with_compression(Compression::Zstd)
+            .with_row_group_size(4);
+        df.to_parquet_path_with_options(&path, &options).unwrap();
+
+        let read = DataFrame::from_parquet_path(&path).unwrap();
+        assert_eq!(read.schema().name(), Some("items"));
+        assert_eq!(read.schema()[1].doc(), Some("the name"));
+        for (read, field) in read.schema().fields().iter().zip(df.schema().fields()) {
+            assert_eq!(read.dtype(), field.dtype());
+            assert_eq!(read.is_nullable(), field.is_nullable());
+        }
+        assert_eq!(read, df);
+
+        let projected = DataFrame::from_parquet_path_with_columns(&path, &["name", "id"]).unwrap();
+        assert_eq!(projected.schema().field_names(), vec!["id", "name"]);
+        assert_eq!(
+            projected
+                .column_values("name")
+                .unwrap()
+                .collect::<Vec<Value>>(),
+            df.column_values("name").unwrap().collect::<Vec<Value>>()
+        );
+
+        assert!(matches!(
+            DataFrame::from_parquet_path_with_columns(&path, &["missing"]),
+            Err(source::Error::ColumnNotFound { .. })
+        ));
+
+        let groups = DataFrame::parquet_row_groups(&path, Some(&["id"])).unwrap();
+        assert_eq!(groups.len(), 3);
+        let sizes = groups.map(|df| df.unwrap().size()).collect::<Vec<usize>>();
+        assert_eq!(sizes, vec![4, 4, 2]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn it_errors_on_nulls_in_required_columns() {
+        let mut df = dataframe();
+        df.extend_unchecked(vec![vec![Value::Null; 6]]);
+
+        assert!(matches!(
+            df.to_parquet(vec![]),
+            Err(Error::ConvertError {
+                source: arrow::Error::BatchError { .. }
+            })
+        ));
+    }
+}
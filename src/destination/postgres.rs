@@ -0,0 +1,384 @@
+use super::sql::Dialect;
+use crate::convert::json_schema::value_to_json;
+use crate::ops::cast::try_cast;
+use crate::value::number::Num;
+use crate::{DataFrame, DataType, Field, Schema, Value};
+
+use chrono::{NaiveDate, NaiveTime};
+use rust_decimal::Decimal;
+use snafu::{ResultExt, Snafu};
+use std::io::Write;
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to create the copy file: {}", source))]
+    CreateError { source: std::io::Error },
+
+    #[snafu(display("Failed to write the copy data: {}", source))]
+    WriteError { source: std::io::Error },
+
+    #[snafu(display(
+        "The value in column {} on row {} can't be written: {}",
+        column,
+        row,
+        message
+    ))]
+    ValueError {
+        column: String,
+        row: usize,
+        message: String,
+    },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The signature the binary format starts with
+pub const BINARY_SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+
+/// The format of the copy data
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// Tab separated lines of text, with nulls as \N
+    Text,
+    /// The binary format, which has to match the types of the table's columns
+    Binary,
+}
+
+impl Format {
+    fn name(&self) -> &str {
+        match self {
+            Format::Text => "text",
+            Format::Binary => "binary",
+        }
+    }
+}
+
+/// The COPY statement that reads the data from stdin into the table's columns, the table's
+/// columns are of the types Dialect::Postgres creates them with
+pub fn copy_sql(table: &str, schema: &Schema, format: Format) -> String {
+    let dialect = Dialect::Postgres;
+    let columns = schema
+        .field_names()
+        .iter()
+        .map(|name| dialect.quote(name))
+        .collect::<Vec<String>>();
+
+    format!(
+        "COPY {} ({}) FROM STDIN WITH (FORMAT {})",
+        dialect.quote(table),
+        columns.join(", "),
+        format.name()
+    )
+}
+
+/// Write the dataframe as PostgreSQL COPY data, to be loaded with copy_sql's statement. Values
+/// are cast into their field's type before they are written.
+pub trait PostgresDestination {
+    fn to_copy<W: Write>(&self, writer: W, format: Format) -> Result<W>;
+
+    fn to_copy_path<P: AsRef<Path>>(&self, path: P, format: Format) -> Result<()> {
+        let file = std::fs::File::create(path).context(CreateError)?;
+        self.to_copy(std::io::BufWriter::new(file), format)?;
+        Ok(())
+    }
+}
+
+impl<'a> PostgresDestination for DataFrame<'a> {
+    fn to_copy<W: Write>(&self, mut writer: W, format: Format) -> Result<W> {
+        let fields = self.schema().fields();
+
+        if format == Format::Binary {
+            writer.write_all(BINARY_SIGNATURE).context(WriteError)?;
+            // the flags and the length of the header extension
+            writer.write_all(&[0; 8]).context(WriteError)?;
+        }
+
+        let mut line = vec![];
+        for row in 0..self.size() {
+            let values = self.row_values(row).unwrap_or_default();
+
+            line.clear();
+            if format == Format::Binary {
+                line.extend_from_slice(&(fields.len() as i16).to_be_bytes());
+            }
+
+            for (i, (value, field)) in values.into_iter().zip(fields).enumerate() {
+                let value = cast(value, field).map_err(|message| Error::ValueError {
+                    column: field.name.clone(),
+                    row,
+                    message,
+                })?;
+
+                match format {
+                    Format::Text => {
+                        if i > 0 {
+                            line.push(b'\t');
+                        }
+                        match value {
+                            Value::Null => line.extend_from_slice(b"\\N"),
+                            value => line.extend_from_slice(escape(&text(&value)).as_bytes()),
+                        }
+                    }
+                    Format::Binary => match value {
+                        Value::Null => line.extend_from_slice(&(-1i32).to_be_bytes()),
+                        value => {
+                            let bytes = binary(&value, &field.dtype);
+                            line.extend_from_slice(&(bytes.len() as i32).to_be_bytes());
+                            line.extend_from_slice(&bytes);
+                        }
+                    },
+                }
+            }
+
+            if format == Format::Text {
+                line.push(b'\n');
+            }
+            writer.write_all(&line).context(WriteError)?;
+        }
+
+        if format == Format::Binary {
+            writer
+                .write_all(&(-1i16).to_be_bytes())
+                .context(WriteError)?;
+        }
+
+        writer.flush().context(WriteError)?;
+        Ok(writer)
+    }
+}
+
+fn cast(value: Value, field: &Field) -> std::result::Result<Value, String> {
+    match (&value, &field.dtype) {
+        (Value::Null, _) | (_, DataType::Any) => Ok(value),
+        (_, dtype) if dtype.matches(&value) => Ok(value),
+        (_, dtype) => try_cast(value.clone(), dtype)
+            .map_err(|e| format!("{} can't be cast to {}: {}", value, dtype, e)),
+    }
+}
+
+/// The value in the text format postgres reads, before it's escaped
+fn text(value: &Value) -> String {
+    match value {
+        Value::Null => "\\N".to_string(),
+        Value::Bool(b) => if *b { "t" } else { "f" }.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Number(n) => match n.inner() {
+            Num::Float(f) if f.is_infinite() => infinity(f.is_sign_negative()),
+            Num::Double(f) if f.is_infinite() => infinity(f.is_sign_negative()),
+            _ => n.to_string(),
+        },
+        Value::Date(d) => d.format("%Y-%m-%d").to_string(),
+        Value::Time(t) => t.format("%H:%M:%S%.f").to_string(),
+        Value::Timestamp(d) => d.format("%Y-%m-%d %H:%M:%S%.f").to_string(),
+        Value::TimestampTz(d) => d.format("%Y-%m-%d %H:%M:%S%.f%:z").to_string(),
+        Value::Binary(b) => format!(
+            "\\x{}",
+            b.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+        ),
+        Value::Array(_) | Value::Map(_) => value_to_json(value).to_string(),
+    }
+}
+
+fn infinity(negative: bool) -> String {
+    match negative {
+        true => "-Infinity".to_string(),
+        false => "Infinity".to_string(),
+    }
+}
+
+/// Escape the backslashes and the characters that separate columns and rows
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\u{8}' => escaped.push_str("\\b"),
+            '\u{c}' => escaped.push_str("\\f"),
+            '\u{b}' => escaped.push_str("\\v"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The value in the binary format of the column type of the field's type. Values have been cast
+/// into the field's type, fields of type any are text.
+fn binary(value: &Value, dtype: &DataType) -> Vec<u8> {
+    let epoch = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+
+    match (value, dtype) {
+        (value, DataType::Any) => text(value).into_bytes(),
+        (Value::Bool(b), _) => vec![*b as u8],
+        (Value::Number(n), _) => match n.inner() {
+            Num::Int8(n) => i16::from(*n).to_be_bytes().to_vec(),
+            Num::Uint8(n) => i16::from(*n).to_be_bytes().to_vec(),
+            Num::Int16(n) => n.to_be_bytes().to_vec(),
+            Num::Uint16(n) => i32::from(*n).to_be_bytes().to_vec(),
+            Num::Int32(n) => n.to_be_bytes().to_vec(),
+            Num::Uint32(n) => i64::from(*n).to_be_bytes().to_vec(),
+            Num::Int64(n) => n.to_be_bytes().to_vec(),
+            Num::Uint64(n) => numeric(&Decimal::from(*n)),
+            Num::Float(n) => n.to_be_bytes().to_vec(),
+            Num::Double(n) => n.to_be_bytes().to_vec(),
+            Num::Decimal(n) => numeric(n),
+        },
+        (Value::Date(d), _) => ((*d - epoch).num_days() as i32).to_be_bytes().to_vec(),
+        (Value::Time(t), _) => {
+            let micros = (*t - NaiveTime::MIN).num_microseconds().unwrap_or_default();
+            micros.to_be_bytes().to_vec()
+        }
+        (Value::Timestamp(d), _) => micros_since(*d - epoch.and_time(NaiveTime::MIN)),
+        (Value::TimestampTz(d), _) => micros_since(d.naive_utc() - epoch.and_time(NaiveTime::MIN)),
+        (Value::Binary(b), _) => b.clone(),
+        // jsonb is the version of its format followed by the json
+        (Value::Array(_), _) | (Value::Map(_), _) => {
+            let mut bytes = vec![1];
+            bytes.extend_from_slice(text(value).as_bytes());
+            bytes
+        }
+        (Value::String(s), _) => s.as_bytes().to_vec(),
+        (Value::Null, _) => vec![],
+    }
+}
+
+fn micros_since(duration: chrono::Duration) -> Vec<u8> {
+    duration
+        .num_microseconds()
+        .unwrap_or_default()
+        .to_be_bytes()
+        .to_vec()
+}
+
+/// The binary format of numerics: the number of base 10000 digits, the weight of the first digit,
+/// the sign, the scale and then the digits
+fn numeric(decimal: &Decimal) -> Vec<u8> {
+    let scale = decimal.scale() as usize;
+    let digits = decimal.abs().to_string();
+    let (integer, fraction) = match digits.find('.') {
+        Some(point) => (&digits[..point], &digits[point + 1..]),
+        None => (digits.as_str(), ""),
+    };
+
+    // pad the integer part on the left and the fraction on the right into groups of 4 digits
+    let integer = integer.trim_start_matches('0');
+    let integer = format!("{}{}", "0".repeat((4 - integer.len() % 4) % 4), integer);
+    let fraction = format!("{}{}", fraction, "0".repeat((4 - fraction.len() % 4) % 4));
+
+    let groups = |s: &str| {
+        s.as_bytes()
+            .chunks(4)
+            .map(|chunk| std::str::from_utf8(chunk).unwrap().parse::<i16>().unwrap())
+            .collect::<Vec<i16>>()
+    };
+    let mut weight = groups(&integer).len() as i16 - 1;
+    let mut digits = groups(&integer);
+    digits.extend(groups(&fraction));
+
+    while digits.first() == Some(&0) {
+        digits.remove(0);
+        weight -= 1;
+    }
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    if digits.is_empty() {
+        weight = 0;
+    }
+
+    let sign: u16 = if decimal.is_sign_negative() && !digits.is_empty() {
+        0x4000
+    } else {
+        0
+    };
+
+    let mut bytes = vec![];
+    bytes.extend_from_slice(&(digits.len() as i16).to_be_bytes());
+    bytes.extend_from_slice(&weight.to_be_bytes());
+    bytes.extend_from_slice(&sign.to_be_bytes());
+    bytes.extend_from_slice(&(scale as i16).to_be_bytes());
+    for digit in digits {
+        bytes.extend_from_slice(&digit.to_be_bytes());
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::row;
+    use std::str::FromStr;
+
+    fn numeric_parts(decimal: &str) -> (i16, i16, u16, i16, Vec<i16>) {
+        let bytes = numeric(&Decimal::from_str(decimal).unwrap());
+        let i16_at = |i: usize| i16::from_be_bytes([bytes[i], bytes[i + 1]]);
+        let digits = (0..i16_at(0) as usize).map(|i| i16_at(8 + i * 2)).collect();
+
+        (i16_at(0), i16_at(2), i16_at(4) as u16, i16_at(6), digits)
+    }
+
+    #[test]
+    fn it_encodes_numerics() {
+        assert_eq!(
+            numeric_parts("12345.678"),
+            (3, 1, 0, 3, vec![1, 2345, 6780])
+        );
+        assert_eq!(numeric_parts("-0.5"), (1, -1, 0x4000, 1, vec![5000]));
+        assert_eq!(numeric_parts("0.00"), (0, 0, 0, 2, vec![]));
+        assert_eq!(numeric_parts("10000"), (1, 1, 0, 0, vec![1]));
+    }
+
+    #[test]
+    fn it_writes_copy_data() {
+        let schema = Schema::with_fields(vec![
+            Field::with_type("id", DataType::Int32),
+            Field::with_type("name", DataType::String),
+            Field::with_type("date", DataType::Date),
+            Field::with_type("bytes", DataType::Binary),
+        ])
+        .unwrap();
+        let mut df = DataFrame::with_schema(schema.clone());
+        df.extend_unchecked(vec![
+            row![
+                1i32,
+                "a\tb",
+                NaiveDate::from_ymd_opt(2000, 1, 2).unwrap(),
+                Value::Binary(vec![0, 255])
+            ],
+            row![2i32, Value::Null, Value::Null, Value::Null],
+        ]);
+
+        assert_eq!(
+            copy_sql("items", &schema, Format::Binary),
+            "COPY \"items\" (\"id\", \"name\", \"date\", \"bytes\") FROM STDIN WITH (FORMAT binary)"
+        );
+
+        let text = df.to_copy(vec![], Format::Text).unwrap();
+        assert_eq!(
+            String::from_utf8(text).unwrap(),
+            "1\ta\\tb\t2000-01-02\t\\\\x00ff\n2\t\\N\t\\N\t\\N\n"
+        );
+
+        let binary = df.to_copy(vec![], Format::Binary).unwrap();
+        let mut expected = BINARY_SIGNATURE.to_vec();
+        expected.extend_from_slice(&[0; 8]);
+        expected.extend_from_slice(&[0, 4, 0, 0, 0, 4, 0, 0, 0, 1]);
+        expected.extend_from_slice(&[0, 0, 0, 3, b'a', b'\t', b'b']);
+        expected.extend_from_slice(&[0, 0, 0, 4, 0, 0, 0, 1]);
+        expected.extend_from_slice(&[0, 0, 0, 2, 0, 255]);
+        expected.extend_from_slice(&[0, 4, 0, 0, 0, 4, 0, 0, 0, 2]);
+        expected.extend_from_slice(&[255; 12]);
+        expected.extend_from_slice(&[255, 255]);
+        assert_eq!(binary, expected);
+
+        let mut invalid = DataFrame::with_schema(schema);
+        invalid.extend_unchecked(vec![row!["x", "a", Value::Null, Value::Null]]);
+        assert!(matches!(
+            invalid.to_copy(vec![], Format::Binary),
+            Err(Error::ValueError { .. })
+        ));
+    }
+}
@@ -0,0 +1,644 @@
+use crate::convert::json_schema::value_to_json;
+use crate::value::number::Num;
+use crate::{schema::Constraint, DataFrame, DataType, Field, Schema, Value};
+
+use snafu::{ResultExt, Snafu};
+use std::io::Write;
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to create the sql file: {}", source))]
+    CreateError { source: std::io::Error },
+
+    #[snafu(display("Failed to write the sql script: {}", source))]
+    WriteError { source: std::io::Error },
+
+    #[snafu(display("Upserting into {} requires the schema to have a primary key", table))]
+    MissingPrimaryKey { table: String },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The default number of rows inserted by each statement
+pub const BATCH_SIZE: usize = 500;
+
+/// The length of mysql's varchar columns of strings without a max length
+pub const VARCHAR_LENGTH: usize = 255;
+
+/// The database the statements are written for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dialect {
+    Sqlite,
+    Postgres,
+    MySql,
+}
+
+/// How the dataframe is written into the table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    /// Create the table, it's an error if the table already exists
+    Create,
+    /// Insert the rows, the table is created when it doesn't exist
+    Append,
+    /// Drop the table if it exists and create it again
+    Replace,
+    /// Insert the rows or update the rows with the same primary key, the table is created when
+    /// it doesn't exist
+    Upsert,
+}
+
+/// A statement and the values of its parameters
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    pub sql: String,
+    pub params: Vec<Value>,
+}
+
+impl Statement {
+    fn new(sql: String) -> Statement {
+        Statement {
+            sql,
+            params: vec![],
+        }
+    }
+}
+
+impl Dialect {
+    /// Quote the identifier, doubling any quotes in it
+    pub fn quote(&self, name: &str) -> String {
+        match self {
+            Dialect::MySql => format!("`{}`", name.replace('`', "``")),
+            _ => format!("\"{}\"", name.replace('"', "\"\"")),
+        }
+    }
+
+    /// The name of the column type the values of the data type are stored as. Arrays, maps and
+    /// structs are JSON. Sqlite columns of type any don't have a type so that they can store
+    /// values of any storage class, the other databases store them as text. Sqlite decimals are
+    /// declared with a name that gives them text affinity, so sqlite doesn't round them to reals.
+    pub fn type_name(&self, dtype: &DataType) -> String {
+        let name = match (self, dtype) {
+            (Dialect::Sqlite, DataType::Any) | (Dialect::Sqlite, DataType::Null) => "",
+            (_, DataType::Any) | (_, DataType::Null) => "TEXT",
+            (_, DataType::Bool) => "BOOLEAN",
+            (_, DataType::String) => "TEXT",
+            (Dialect::Postgres, DataType::Array(_))
+            | (Dialect::Postgres, DataType::Map(_))
+            | (Dialect::Postgres, DataType::Struct(_)) => "JSONB",
+            (_, DataType::Array(_)) | (_, DataType::Map(_)) | (_, DataType::Struct(_)) => "JSON",
+            (_, DataType::Date) => "DATE",
+            (Dialect::MySql, DataType::Time) => "TIME(6)",
+            (_, DataType::Time) => "TIME",
+            (Dialect::MySql, DataType::Timestamp) => "DATETIME(6)",
+            (_, DataType::Timestamp) => "TIMESTAMP",
+            (Dialect::MySql, DataType::TimestampTz) => "TIMESTAMP(6)",
+            (_, DataType::TimestampTz) => "TIMESTAMPTZ",
+            (Dialect::Sqlite, DataType::Binary) => "BLOB",
+            (Dialect::Postgres, DataType::Binary) => "BYTEA",
+            (Dialect::MySql, DataType::Binary) => "LONGBLOB",
+
+            (Dialect::Sqlite, DataType::Uint8) => "UINT8",
+            (Dialect::Sqlite, DataType::Uint16) => "UINT16",
+            (Dialect::Sqlite, DataType::Uint32) => "UINT32",
+            (Dialect::Sqlite, DataType::Uint64) => "UINT64",
+            // postgres doesn't have unsigned integers, so they are stored in the next size up
+            (Dialect::Postgres, DataType::Uint8) => "SMALLINT",
+            (Dialect::Postgres, DataType::Uint16) => "INTEGER",
+            (Dialect::Postgres, DataType::Uint32) => "BIGINT",
+            (Dialect::Postgres, DataType::Uint64) => "NUMERIC(20, 0)",
+            (Dialect::MySql, DataType::Uint8) => "TINYINT UNSIGNED",
+            (Dialect::MySql, DataType::Uint16) => "SMALLINT UNSIGNED",
+            (Dialect::MySql, DataType::Uint32) => "INT UNSIGNED",
+            (Dialect::MySql, DataType::Uint64) => "BIGINT UNSIGNED",
+
+            (Dialect::Postgres, DataType::Int8) => "SMALLINT",
+            (_, DataType::Int8) => "TINYINT",
+            (_, DataType::Int16) => "SMALLINT",
+            (Dialect::Postgres, DataType::Int32) => "INTEGER",
+            // other tools create sqlite tables with INT columns of any size
+            (Dialect::Sqlite, DataType::Int32) => "INT32",
+            (_, DataType::Int32) => "INT",
+            (_, DataType::Int64) => "BIGINT",
+            (Dialect::Postgres, DataType::Float) => "REAL",
+            (_, DataType::Float) => "FLOAT",
+            (Dialect::Postgres, DataType::Double) => "DOUBLE PRECISION",
+            (_, DataType::Double) => "DOUBLE",
+
+            (Dialect::Sqlite, DataType::Decimal(Some((precision, scale)))) => {
+                return format!("DECIMAL_TEXT({}, {})", precision, scale)
+            }
+            (Dialect::Postgres, DataType::Decimal(Some((precision, scale)))) => {
+                return format!("NUMERIC({}, {})", precision, scale)
+            }
+            (Dialect::MySql, DataType::Decimal(Some((precision, scale)))) => {
+                return format!("DECIMAL({}, {})", precision, scale)
+            }
+            (Dialect::Sqlite, DataType::Decimal(None)) => "DECIMAL_TEXT",
+            (Dialect::Postgres, DataType::Decimal(None)) => "NUMERIC",
+            // mysql decimals always have a precision, this is the largest one
+            (Dialect::MySql, DataType::Decimal(None)) => "DECIMAL(65, 30)",
+        };
+
+        name.to_string()
+    }
+
+    /// The value as a SQL literal. Dates and times are ISO 8601 strings, and arrays and maps are
+    /// JSON strings. Sqlite reads numbers that don't fit a 64 bit integer as reals, so decimals
+    /// and large unsigned integers are strings in sqlite.
+    pub fn literal(&self, value: &Value) -> String {
+        let text = |s: &str| match self {
+            // mysql treats backslashes as escapes in strings by default
+            Dialect::MySql => format!("'{}'", s.replace('\\', "\\\\").replace('\'', "''")),
+            _ => format!("'{}'", s.replace('\'', "''")),
+        };
+
+        match value {
+            Value::Null => "NULL".to_string(),
+            Value::Bool(b) => match self {
+                Dialect::Sqlite => (*b as u8).to_string(),
+                _ => b.to_string().to_uppercase(),
+            },
+            Value::String(s) => text(s),
+            Value::Number(n) => match (self, n.inner()) {
+                (_, Num::Float(f)) if !f.is_finite() => text(&n.to_string()),
+                (_, Num::Double(f)) if !f.is_finite() => text(&n.to_string()),
+                (Dialect::Sqlite, Num::Decimal(_)) => text(&n.to_string()),
+                (Dialect::Sqlite, Num::Uint64(u)) if *u > i64::MAX as u64 => text(&n.to_string()),
+                _ => n.to_string(),
+            },
+            Value::Date(d) => text(&d.format("%Y-%m-%d").to_string()),
+            Value::Time(t) => text(&t.format("%H:%M:%S%.f").to_string()),
+            Value::Timestamp(d) => text(&d.format("%Y-%m-%dT%H:%M:%S%.f").to_string()),
+            Value::TimestampTz(d) => text(&d.to_rfc3339()),
+            Value::Binary(b) => {
+                let hex = b.iter().map(|b| format!("{:02X}", b)).collect::<String>();
+                match self {
+                    Dialect::Postgres => format!("'\\x{}'", hex),
+                    _ => format!("X'{}'", hex),
+                }
+            }
+            Value::Array(_) | Value::Map(_) => text(&value_to_json(value).to_string()),
+        }
+    }
+
+    /// The CREATE TABLE statement for the schema. Fields that aren't nullable are NOT NULL, the
+    /// fields' defaults are the columns' defaults, and the schema's keys are the table's
+    /// constraints.
+    pub fn create_table(&self, table: &str, schema: &Schema, if_not_exists: bool) -> String {
+        let keys = schema
+            .primary_key()
+            .into_iter()
+            .chain(schema.unique_keys().iter().map(Vec::as_slice))
+            .flatten()
+            .collect::<Vec<&String>>();
+
+        let mut columns = schema
+            .fields()
+            .iter()
+            .map(|field| self.column(field, keys.contains(&&field.name)))
+            .collect::<Vec<String>>();
+
+        if let Some(key) = schema.primary_key() {
+            columns.push(format!("PRIMARY KEY ({})", self.quote_all(key)));
+        }
+        for key in schema.unique_keys() {
+            columns.push(format!("UNIQUE ({})", self.quote_all(key)));
+        }
+
+        format!(
+            "CREATE TABLE {}{} ({})",
+            if if_not_exists { "IF NOT EXISTS " } else { "" },
+            self.quote(table),
+            columns.join(", ")
+        )
+    }
+
+    pub fn drop_table(&self, table: &str) -> String {
+        format!("DROP TABLE IF EXISTS {}", self.quote(table))
+    }
+
+    /// The INSERT statement for a number of rows of the schema, with a parameter for each value
+    pub fn insert(&self, table: &str, schema: &Schema, rows: usize) -> String {
+        self.insert_with(table, schema, rows, |i| self.placeholder(i + 1))
+    }
+
+    /// The INSERT statement for a number of rows of the schema that updates the columns that
+    /// aren't part of the primary key when a row with the same key exists
+    pub fn upsert(&self, table: &str, schema: &Schema, rows: usize) -> Result<String> {
+        let insert = self.insert(table, schema, rows);
+        self.on_conflict(insert, table, schema)
+    }
+
+    /// The column's definition. Mysql can't index or set defaults of text columns, so the strings
+    /// of keys and of fields with defaults are varchars of the field's max length.
+    fn column(&self, field: &Field, keyed: bool) -> String {
+        let mut sql = self.quote(&field.name);
+
+        let dtype = match (self, &field.dtype) {
+            (Dialect::MySql, DataType::String) | (Dialect::MySql, DataType::Any)
+                if keyed || field.default.is_some() =>
+            {
+                let length = field.constraints.iter().find_map(|c| match c {
+                    Constraint::MaxLength(max) => Some(*max),
+                    _ => None,
+                });
+                format!("VARCHAR({})", length.unwrap_or(VARCHAR_LENGTH))
+            }
+            (_, dtype) => self.type_name(dtype),
+        };
+        if !dtype.is_empty() {
+            sql.push(' ');
+            sql.push_str(&dtype);
+        }
+        if !field.nullable {
+            sql.push_str(" NOT NULL");
+        }
+        if let Some(default) = &field.default {
+            sql.push_str(" DEFAULT ");
+            sql.push_str(&self.literal(default));
+        }
+
+        sql
+    }
+
+    fn quote_all<S: AsRef<str>>(&self, names: &[S]) -> String {
+        names
+            .iter()
+            .map(|name| self.quote(name.as_ref()))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// The parameter for the nth value, counting from 1
+    fn placeholder(&self, n: usize) -> String {
+        match self {
+            Dialect::Postgres => format!("${}", n),
+            Dialect::Sqlite => format!("?{}", n),
+            Dialect::MySql => "?".to_string(),
+        }
+    }
+
+    /// The INSERT statement with the rows' values from the function, which is given the index of
+    /// the value across all of the rows
+    fn insert_with<F: FnMut(usize) -> String>(
+        &self,
+        table: &str,
+        schema: &Schema,
+        rows: usize,
+        mut value: F,
+    ) -> String {
+        let columns = schema.len();
+        let rows = (0..rows)
+            .map(|row| {
+                let values = (0..columns)
+                    .map(|column| value(row * columns + column))
+                    .collect::<Vec<String>>();
+                format!("({})", values.join(", "))
+            })
+            .collect::<Vec<String>>();
+
+        format!(
+            "INSERT INTO {} ({}) VALUES {}",
+            self.quote(table),
+            self.quote_all(&schema.field_names()),
+            rows.join(", ")
+        )
+    }
+
+    fn on_conflict(&self, insert: String, table: &str, schema: &Schema) -> Result<String> {
+        let key = schema
+            .primary_key()
+            .ok_or_else(|| Error::MissingPrimaryKey {
+                table: table.to_string(),
+            })?;
+
+        let columns = schema
+            .field_names()
+            .into_iter()
+            .filter(|name| !key.contains(name))
+            .collect::<Vec<&String>>();
+
+        let sql = match self {
+            Dialect::MySql => {
+                let updates = match columns.is_empty() {
+                    // mysql can't do nothing on a duplicate key, so the key is set to itself
+                    true => format!("{0} = {0}", self.quote(&key[0])),
+                    false => columns
+                        .iter()
+                        .map(|name| format!("{0} = VALUES({0})", self.quote(name)))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                };
+                format!("{} ON DUPLICATE KEY UPDATE {}", insert, updates)
+            }
+            _ => {
+                let action = match columns.is_empty() {
+                    true => "NOTHING".to_string(),
+                    false => format!(
+                        "UPDATE SET {}",
+                        columns
+                            .iter()
+                            .map(|name| format!("{0} = excluded.{0}", self.quote(name)))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ),
+                };
+                format!(
+                    "{} ON CONFLICT ({}) DO {}",
+                    insert,
+                    self.quote_all(key),
+                    action
+                )
+            }
+        };
+
+        Ok(sql)
+    }
+}
+
+/// Options for writing the statements, rows are appended in batches of BATCH_SIZE by default
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    dialect: Dialect,
+    mode: Mode,
+    batch_size: usize,
+}
+
+impl Options {
+    pub fn new(dialect: Dialect) -> Options {
+        Options {
+            dialect,
+            mode: Mode::Append,
+            batch_size: BATCH_SIZE,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: Mode) -> Options {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the number of rows inserted by each statement, databases limit the number of
+    /// parameters in a statement so wide tables need smaller batches
+    pub fn with_batch_size(mut self, batch_size: usize) -> Options {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+}
+
+/// Write the dataframe as SQL statements, to be run against a database or reviewed before they
+/// are
+pub trait SqlDestination {
+    /// The statements that create the table for the mode, followed by the inserts of the rows in
+    /// batches
+    fn to_sql_statements(&self, table: &str, options: &Options) -> Result<Vec<Statement>>;
+
+    /// Write the statements as a script, with the values as literals and in a transaction
+    fn to_sql_script<W: Write>(&self, writer: W, table: &str, options: &Options) -> Result<W>;
+
+    fn to_sql_script_path<P: AsRef<Path>>(
+        &self,
+        path: P,
+        table: &str,
+        options: &Options,
+    ) -> Result<()> {
+        let file = std::fs::File::create(path).context(CreateError)?;
+        self.to_sql_script(std::io::BufWriter::new(file), table, options)?;
+        Ok(())
+    }
+}
+
+impl<'a> SqlDestination for DataFrame<'a> {
+    fn to_sql_statements(&self, table: &str, options: &Options) -> Result<Vec<Statement>> {
+        let dialect = options.dialect;
+        let mut statements = ddl(self.schema(), table, options)
+            .into_iter()
+            .map(Statement::new)
+            .collect::<Vec<Statement>>();
+
+        for rows in batches(self, options.batch_size) {
+            let sql = match options.mode {
+                Mode::Upsert => dialect.upsert(table, self.schema(), rows.len())?,
+                _ => dialect.insert(table, self.schema(), rows.len()),
+            };
+
+            statements.push(Statement {
+                sql,
+                params: rows.into_iter().flatten().collect(),
+            });
+        }
+
+        Ok(statements)
+    }
+
+    fn to_sql_script<W: Write>(&self, mut writer: W, table: &str, options: &Options) -> Result<W> {
+        let dialect = options.dialect;
+
+        writeln!(writer, "BEGIN;").context(WriteError)?;
+        for sql in ddl(self.schema(), table, options) {
+            writeln!(writer, "{};", sql).context(WriteError)?;
+        }
+
+        for rows in batches(self, options.batch_size) {
+            let values = rows.iter().flatten().collect::<Vec<&Value>>();
+            let insert = dialect.insert_with(table, self.schema(), rows.len(), |i| {
+                dialect.literal(values[i])
+            });
+            let sql = match options.mode {
+                Mode::Upsert => dialect.on_conflict(insert, table, self.schema())?,
+                _ => insert,
+            };
+
+            writeln!(writer, "{};", sql).context(WriteError)?;
+        }
+        writeln!(writer, "COMMIT;").context(WriteError)?;
+
+        writer.flush().context(WriteError)?;
+        Ok(writer)
+    }
+}
+
+/// The statements that create the table for the mode
+fn ddl(schema: &Schema, table: &str, options: &Options) -> Vec<String> {
+    let dialect = options.dialect;
+
+    match options.mode {
+        Mode::Create => vec![dialect.create_table(table, schema, false)],
+        Mode::Append | Mode::Upsert => vec![dialect.create_table(table, schema, true)],
+        Mode::Replace => vec![
+            dialect.drop_table(table),
+            dialect.create_table(table, schema, false),
+        ],
+    }
+}
+
+/// The rows of the dataframe in batches of the size
+fn batches<'a>(df: &'a DataFrame, batch_size: usize) -> impl Iterator<Item = Vec<Vec<Value>>> + 'a {
+    (0..df.size()).step_by(batch_size).map(move |start| {
+        (start..df.size().min(start + batch_size))
+            .filter_map(|row| df.row_values(row))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::row;
+    use crate::source::sqlite::SqliteSource;
+
+    fn schema() -> Schema {
+        Schema::with_fields(vec![
+            Field::with_type("id", DataType::Int64).with_nullable(false),
+            Field::with_type("name", DataType::String).with_default("it's".into()),
+            Field::with_type("price", DataType::decimal(10, 2)),
+            Field::with_type("tags", DataType::array(DataType::String)),
+            Field::with_type("extra", DataType::Any),
+        ])
+        .unwrap()
+        .with_primary_key(&["id"])
+        .with_unique_key(&["name"])
+    }
+
+    #[test]
+    fn it_creates_tables_for_dialects() {
+        let schema = schema();
+
+        assert_eq!(
+            Dialect::Sqlite.create_table("items", &schema, false),
+            "CREATE TABLE \"items\" (\"id\" BIGINT NOT NULL, \"name\" TEXT DEFAULT 'it''s', \
+             \"price\" DECIMAL_TEXT(10, 2), \"tags\" JSON, \"extra\", PRIMARY KEY (\"id\"), \
+             UNIQUE (\"name\"))"
+        );
+        assert_eq!(
+            Dialect::Postgres.create_table("items", &schema, true),
+            "CREATE TABLE IF NOT EXISTS \"items\" (\"id\" BIGINT NOT NULL, \"name\" TEXT DEFAULT \
+             'it''s', \"price\" NUMERIC(10, 2), \"tags\" JSONB, \"extra\" TEXT, PRIMARY KEY \
+             (\"id\"), UNIQUE (\"name\"))"
+        );
+        assert_eq!(
+            Dialect::MySql.create_table("items", &schema, false),
+            "CREATE TABLE `items` (`id` BIGINT NOT NULL, `name` VARCHAR(255) DEFAULT 'it''s', \
+             `price` DECIMAL(10, 2), `tags` JSON, `extra` TEXT, PRIMARY KEY (`id`), UNIQUE \
+             (`name`))"
+        );
+
+        let schema = Schema::with_fields(vec![
+            Field::with_type("code", DataType::String).with_constraint(Constraint::MaxLength(8)),
+            Field::with_type("note", DataType::String),
+        ])
+        .unwrap()
+        .with_primary_key(&["code"]);
+        assert_eq!(
+            Dialect::MySql.create_table("codes", &schema, false),
+            "CREATE TABLE `codes` (`code` VARCHAR(8), `note` TEXT, PRIMARY KEY (`code`))"
+        );
+
+        assert_eq!(Dialect::Postgres.type_name(&DataType::Uint32), "BIGINT");
+        assert_eq!(Dialect::MySql.type_name(&DataType::Uint32), "INT UNSIGNED");
+        assert_eq!(
+            Dialect::Postgres.literal(&Value::Binary(vec![0, 255])),
+            "'\\x00FF'"
+        );
+        assert_eq!(Dialect::MySql.literal(&Value::from("a\\b")), "'a\\\\b'");
+        assert_eq!(Dialect::Sqlite.literal(&Value::from(true)), "1");
+        assert_eq!(Dialect::Postgres.literal(&Value::from(true)), "TRUE");
+    }
+
+    #[test]
+    fn it_creates_batched_statements() {
+        let schema = Schema::with_fields(vec![
+            Field::with_type("id", DataType::Int64),
+            Field::with_type("name", DataType::String),
+        ])
+        .unwrap()
+        .with_primary_key(&["id"]);
+        let mut df = DataFrame::with_schema(schema);
+        df.extend_unchecked(vec![row![1i64, "a"], row![2i64, "b"], row![3i64, "c"]]);
+
+        let options = Options::new(Dialect::Postgres)
+            .with_mode(Mode::Upsert)
+            .with_batch_size(2);
+        let statements = df.to_sql_statements("items", &options).unwrap();
+        assert_eq!(statements.len(), 3);
+        assert!(statements[0].params.is_empty());
+        assert_eq!(
+            statements[1].sql,
+            "INSERT INTO \"items\" (\"id\", \"name\") VALUES ($1, $2), ($3, $4) ON CONFLICT \
+             (\"id\") DO UPDATE SET \"name\" = excluded.\"name\""
+        );
+        assert_eq!(
+            statements[1].params,
+            vec![1i64.into(), "a".into(), 2i64.into(), "b".into()]
+        );
+        assert_eq!(statements[2].params.len(), 2);
+
+        let options = Options::new(Dialect::MySql).with_mode(Mode::Upsert);
+        let statements = df.to_sql_statements("items", &options).unwrap();
+        assert_eq!(
+            statements[1].sql,
+            "INSERT INTO `items` (`id`, `name`) VALUES (?, ?), (?, ?), (?, ?) ON DUPLICATE KEY \
+             UPDATE `name` = VALUES(`name`)"
+        );
+
+        let unkeyed = DataFrame::new(&["id"], vec![row![1i64]]);
+        assert!(matches!(
+            unkeyed.to_sql_statements("items", &options),
+            Err(Error::MissingPrimaryKey { .. })
+        ));
+    }
+
+    #[test]
+    fn it_writes_scripts() {
+        let mut df = DataFrame::with_schema(schema());
+        df.extend_unchecked(vec![
+            row![1i64, "it's", 1.5, Value::Array(vec!["x".into()]), 1i64],
+            row![2i64, "b", Value::Null, Value::Null, "y"],
+        ]);
+        df.coherce_data();
+
+        let options = Options::new(Dialect::Sqlite)
+            .with_mode(Mode::Replace)
+            .with_batch_size(1);
+        let script = df.to_sql_script(vec![], "items", &options).unwrap();
+        let script = String::from_utf8(script).unwrap();
+        assert_eq!(script.lines().count(), 6);
+        assert!(script.contains("VALUES (1, 'it''s', '1.50', '[\"x\"]', 1);"));
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(&script).unwrap();
+        let read = DataFrame::read_sqlite(&conn, "SELECT * FROM items ORDER BY id").unwrap();
+        assert_eq!(read.row(0).unwrap().data(), df.row(0).unwrap().data());
+        assert_eq!(read.row(1).unwrap().data(), df.row(1).unwrap().data());
+
+        // sqlite would read decimals and large unsigned integers as reals and round them
+        assert_eq!(
+            Dialect::Sqlite.literal(&Value::from(u64::MAX)),
+            "'18446744073709551615'"
+        );
+        let mut df = DataFrame::with_schema(
+            Schema::with_fields(vec![Field::with_type("price", DataType::decimal(20, 2))]).unwrap(),
+        );
+        df.extend_unchecked(vec![row!["12345678901234567.89"]]);
+        df.coherce_data();
+
+        let options = Options::new(Dialect::Sqlite).with_mode(Mode::Create);
+        let script = df.to_sql_script(vec![], "prices", &options).unwrap();
+        let script = String::from_utf8(script).unwrap();
+        assert!(script.contains("VALUES ('12345678901234567.89');"));
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(&script).unwrap();
+        let read = DataFrame::read_sqlite(&conn, "SELECT * FROM prices").unwrap();
+        assert_eq!(read.row(0).unwrap().data(), df.row(0).unwrap().data());
+    }
+}
@@ -0,0 +1,189 @@
+use super::sql::{self, Dialect};
+use crate::convert::json_schema::value_to_json;
+use crate::value::number::Num;
+use crate::{DataFrame, Value};
+
+use rusqlite::types::Value as SqlValue;
+use rusqlite::Connection;
+use snafu::{ResultExt, Snafu};
+use std::convert::TryFrom;
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to open the sqlite database: {}", source))]
+    OpenError { source: rusqlite::Error },
+
+    #[snafu(display("Failed to write to sqlite: {}", source))]
+    WriteError { source: rusqlite::Error },
+
+    #[snafu(display(
+        "The value in column {} on row {} can't be written: {}",
+        column,
+        row,
+        message
+    ))]
+    ValueError {
+        column: String,
+        row: usize,
+        message: String,
+    },
+
+    #[snafu(display("Failed to create the statements: {}", source))]
+    StatementError { source: sql::Error },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+pub use super::sql::Mode;
+
+/// Write the dataframe into a table of a SQLite database, in a single transaction
+pub trait SqliteDestination {
+    fn to_sqlite(&self, conn: &mut Connection, table: &str, mode: Mode) -> Result<()>;
+
+    fn to_sqlite_path<P: AsRef<Path>>(&self, path: P, table: &str, mode: Mode) -> Result<()> {
+        let mut conn = Connection::open(path).context(OpenError)?;
+        self.to_sqlite(&mut conn, table, mode)
+    }
+}
+
+impl<'a> SqliteDestination for DataFrame<'a> {
+    fn to_sqlite(&self, conn: &mut Connection, table: &str, mode: Mode) -> Result<()> {
+        let dialect = Dialect::Sqlite;
+        let insert = match mode {
+            Mode::Upsert => dialect
+                .upsert(table, self.schema(), 1)
+                .context(StatementError)?,
+            _ => dialect.insert(table, self.schema(), 1),
+        };
+
+        let tx = conn.transaction().context(WriteError)?;
+
+        if mode == Mode::Replace {
+            tx.execute(&dialect.drop_table(table), [])
+                .context(WriteError)?;
+        }
+
+        let create = dialect.create_table(table, self.schema(), mode != Mode::Create);
+        tx.execute(&create, []).context(WriteError)?;
+
+        {
+            let mut stmt = tx.prepare(&insert).context(WriteError)?;
+            for (row, values) in self.iter().enumerate() {
+                let params = values
+                    .iter()
+                    .zip(self.schema().fields())
+                    .map(|(value, field)| {
+                        to_sql(value).map_err(|message| Error::ValueError {
+                            column: field.name.clone(),
+                            row,
+                            message,
+                        })
+                    })
+                    .collect::<Result<Vec<SqlValue>>>()?;
+
+                stmt.execute(rusqlite::params_from_iter(params))
+                    .context(WriteError)?;
+            }
+        }
+
+        tx.commit().context(WriteError)
+    }
+}
+
+/// Convert the value into the storage class it's written as. Dates and times are ISO 8601
+/// strings, decimals are strings so that they keep their precision until the column's affinity
+/// converts them, and arrays and maps are JSON.
+pub(crate) fn to_sql(value: &Value) -> std::result::Result<SqlValue, String> {
+    let value = match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(b) => SqlValue::Integer(*b as i64),
+        Value::String(s) => SqlValue::Text(s.clone()),
+        Value::Number(n) => match n.inner() {
+            Num::Uint8(n) => SqlValue::Integer(i64::from(*n)),
+            Num::Uint16(n) => SqlValue::Integer(i64::from(*n)),
+            Num::Uint32(n) => SqlValue::Integer(i64::from(*n)),
+            Num::Uint64(n) => match i64::try_from(*n) {
+                Ok(n) => SqlValue::Integer(n),
+                Err(_) => return Err(format!("{} is larger than a sqlite integer", n)),
+            },
+            Num::Int8(n) => SqlValue::Integer(i64::from(*n)),
+            Num::Int16(n) => SqlValue::Integer(i64::from(*n)),
+            Num::Int32(n) => SqlValue::Integer(i64::from(*n)),
+            Num::Int64(n) => SqlValue::Integer(*n),
+            Num::Float(n) => SqlValue::Real(f64::from(*n)),
+            Num::Double(n) => SqlValue::Real(*n),
+            Num::Decimal(n) => SqlValue::Text(n.to_string()),
+        },
+        Value::Date(d) => SqlValue::Text(d.format("%Y-%m-%d").to_string()),
+        Value::Time(t) => SqlValue::Text(t.format("%H:%M:%S%.f").to_string()),
+        Value::Timestamp(d) => SqlValue::Text(d.format("%Y-%m-%dT%H:%M:%S%.f").to_string()),
+        Value::TimestampTz(d) => SqlValue::Text(d.to_rfc3339()),
+        Value::Binary(b) => SqlValue::Blob(b.clone()),
+        Value::Array(_) | Value::Map(_) => SqlValue::Text(value_to_json(value).to_string()),
+    };
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{row, DataType, Field, Schema};
+
+    #[test]
+    fn it_writes_with_modes() {
+        let schema = Schema::with_fields(vec![
+            Field::with_type("id", DataType::Int64).with_nullable(false),
+            Field::with_type("name", DataType::String),
+        ])
+        .unwrap()
+        .with_primary_key(&["id"]);
+        let mut df = DataFrame::with_schema(schema);
+        df.extend_unchecked(vec![row![1i64, "a"], row![2i64, "b"]]);
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        let count = |conn: &Connection| -> i64 {
+            conn.query_row("SELECT count(*) FROM items", [], |row| row.get(0))
+                .unwrap()
+        };
+
+        df.to_sqlite(&mut conn, "items", Mode::Create).unwrap();
+        assert_eq!(count(&conn), 2);
+        assert!(matches!(
+            df.to_sqlite(&mut conn, "items", Mode::Create),
+            Err(Error::WriteError { .. })
+        ));
+
+        // the primary key is violated, and the transaction is rolled back
+        assert!(df.to_sqlite(&mut conn, "items", Mode::Append).is_err());
+        assert_eq!(count(&conn), 2);
+
+        df.to_sqlite(&mut conn, "items", Mode::Replace).unwrap();
+        assert_eq!(count(&conn), 2);
+
+        let mut update = DataFrame::with_schema(df.schema().clone());
+        update.extend_unchecked(vec![row![2i64, "c"], row![3i64, "d"]]);
+        update.to_sqlite(&mut conn, "items", Mode::Upsert).unwrap();
+        let names = conn
+            .prepare("SELECT name FROM items ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<Vec<String>>>()
+            .unwrap();
+        assert_eq!(names, vec!["a", "c", "d"]);
+
+        let mut nulls = DataFrame::with_schema(df.schema().clone());
+        nulls.extend_unchecked(vec![row![Value::Null, "e"]]);
+        assert!(nulls.to_sqlite(&mut conn, "items", Mode::Append).is_err());
+
+        let unkeyed = DataFrame::new(&["id"], vec![row![1i64]]);
+        assert!(matches!(
+            unkeyed.to_sqlite(&mut conn, "other", Mode::Upsert),
+            Err(Error::StatementError {
+                source: sql::Error::MissingPrimaryKey { .. }
+            })
+        ));
+    }
+}
@@ -1,14 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize, PartialEq, PartialOrd)]
 pub struct Dim(pub(crate) usize, pub(crate) usize);
 
-impl Default for Dim {
-    fn default() -> Self {
-        Dim(0, 0)
-    }
-}
-
 impl Dim {
     pub(crate) fn new(x: usize, y: usize) -> Self {
         Dim(x, y)
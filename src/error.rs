@@ -28,11 +28,38 @@ pub enum Error {
     #[snafu(display("A column with the name {} already exists", column))]
     DuplicateColumnName { column: String },
 
+    #[snafu(display("The column's field is invalid: {}", source))]
+    SchemaError { source: crate::schema::Error },
+
+    #[snafu(display("The key {} can't be null", key))]
+    NullKey { key: String },
+
+    #[snafu(display("Another row already has the value {} for the key {}", value, key))]
+    DuplicateKey { key: String, value: String },
+
     #[snafu(display("Failed to perform cast operation"))]
     CastError { source: crate::ops::cast::Error },
 
     #[snafu(display("Failed to perform conversion operation"))]
     ConvertError { source: crate::ops::convert::Error },
+
+    #[snafu(display("Failed to coerce the data into the schema: {}", source))]
+    CoerceError {
+        source: Box<crate::ops::coerce::Error>,
+    },
+
+    #[snafu(display("Failed to evolve the dataframe into the schema: {}", source))]
+    EvolveError {
+        source: Box<crate::ops::evolve::Error>,
+    },
+
+    #[snafu(display("Failed to infer the schema: {}", source))]
+    InferError { source: crate::ops::infer::Error },
+
+    #[snafu(display("Failed to apply python transform: {}", source))]
+    PythonError {
+        source: Box<crate::ops::python::Error>,
+    },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
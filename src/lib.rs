@@ -1,6 +1,9 @@
+#[macro_use]
+extern crate cpython;
+
 pub mod macros;
-pub use macros::*;
 
+pub mod column;
 pub mod convert;
 pub mod dataframe;
 pub mod destination;
@@ -8,15 +11,20 @@ pub mod dim;
 pub mod error;
 
 pub mod ops;
+pub mod persist;
+pub mod pipeline;
+pub mod python;
 pub mod schema;
 pub mod source;
 pub mod traits;
+pub mod validation;
 pub mod value;
 pub mod views;
 
+pub use column::Column;
 pub use dataframe::DataFrame;
 pub use schema::{DataType, Field, Schema};
 pub use traits::Get;
 pub use value::Value;
 
-pub(crate) use value::number::{Num, Number};
+pub(crate) use value::number::Number;
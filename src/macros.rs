@@ -21,6 +21,7 @@ macro_rules! schema {
                 $($crate::field!($x)),*
             ]
         )
+        .expect("the fields' names and aliases collide")
     )
 }
 
@@ -34,9 +35,9 @@ macro_rules! field {
 #[macro_export]
 macro_rules! df {
     ([$($f:expr),* $(,)?], [$($x:expr),*]) => {
-        $crate::DataFrame::with_data(crate::schema::Schema::with_fields(vec![
+        $crate::DataFrame::with_data($crate::schema::Schema::with_fields(vec![
              $($crate::field!($f)),*
-        ]), vec![
+        ]).expect("the fields' names and aliases collide"), vec![
             $($x),*
         ])
     };
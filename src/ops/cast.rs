@@ -1,4 +1,5 @@
-use crate::{schema::DataType, traits::TypeOf, DataFrame, Number, Value};
+use crate::{schema::DataType, traits::TypeOf, value::map::Map, DataFrame, Number, Value};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
 use snafu::{IntoError, Snafu};
 
 #[derive(Debug, Snafu)]
@@ -15,12 +16,23 @@ pub enum Error {
 
     #[snafu(display("Called convert into_number with non numeric destination type"))]
     InvalidNumericCast,
+
+    #[snafu(display("Cannot parse {} as a {}", value, dest_type.as_str()))]
+    FailedTemporalCast {
+        value: String,
+        dest_type: DataType,
+    },
+
+    #[snafu(display("The struct doesn't have the field {}", field))]
+    UnknownStructField {
+        field: String,
+    },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 macro_rules! castable {
-    ($to:ident, $from:ident, [$($x:path; [$($y:path),*]);+]) => {{
+    ($to:ident, $from:ident, [$($x:pat => [$($y:pat),*]);+]) => {{
         match ($to, $from) {
             $(
                 $(
@@ -33,10 +45,9 @@ macro_rules! castable {
 }
 
 macro_rules! pcast {
-    ($x:path, $y:path) => {($x, $y)};
-    ($x:path; [$(y:path),*]) => { $(pcast!(x, y))* };
-    ($x:path; $y:tt) => { pcast!($x, $y) };
-    ($([$x:path; $y:tt])*) => { $(pcast!($x; $y))*}
+    ($x:pat, $y:pat) => {
+        ($x, $y)
+    };
 }
 
 pub fn can_cast(from: &DataType, to: &DataType) -> bool {
@@ -44,20 +55,26 @@ pub fn can_cast(from: &DataType, to: &DataType) -> bool {
         to,
         from,
         [
-            DataType::Int64; [DataType::Bool, DataType::Uint8, DataType::Uint16, DataType::Uint32, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Float, DataType::Decimal];
-            DataType::Int32; [DataType::Bool, DataType::Uint8, DataType::Uint16, DataType::Int8, DataType::Int16];
-            DataType::Int16; [DataType::Bool, DataType::Uint8, DataType::Int8];
-            DataType::Int8; [DataType::Bool];
+            DataType::Int64 => [DataType::Bool, DataType::Uint8, DataType::Uint16, DataType::Uint32, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Float, DataType::Decimal(_)];
+            DataType::Int32 => [DataType::Bool, DataType::Uint8, DataType::Uint16, DataType::Int8, DataType::Int16];
+            DataType::Int16 => [DataType::Bool, DataType::Uint8, DataType::Int8];
+            DataType::Int8 => [DataType::Bool];
+
+            DataType::Uint64 => [DataType::Bool, DataType::Uint8, DataType::Uint16, DataType::Uint32];
+            DataType::Uint32 => [DataType::Bool, DataType::Uint8, DataType::Uint16];
+            DataType::Uint16 => [DataType::Bool, DataType::Uint8];
+            DataType::Uint8 => [DataType::Bool];
 
-            DataType::Uint64; [DataType::Bool, DataType::Uint8, DataType::Uint16, DataType::Uint32];
-            DataType::Uint32; [DataType::Bool, DataType::Uint8, DataType::Uint16];
-            DataType::Uint16; [DataType::Bool, DataType::Uint8];
-            DataType::Uint8; [DataType::Bool];
+            DataType::Float => [DataType::Uint8, DataType::Uint16, DataType::Int8, DataType::Int16];
+            DataType::Double => [DataType::Uint8, DataType::Uint16, DataType::Uint32, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Float];
+            DataType::Decimal(_) => [DataType::Bool, DataType::Uint8, DataType::Uint16, DataType::Uint32, DataType::Uint64, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Int64, DataType::Decimal(_)];
 
-            DataType::Float; [DataType::Uint8, DataType::Uint16, DataType::Int8, DataType::Uint16];
-            DataType::Double; [DataType::Uint8, DataType::Uint16, DataType::Uint32, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Float];
+            DataType::Date => [DataType::Timestamp, DataType::TimestampTz];
+            DataType::Time => [DataType::Timestamp, DataType::TimestampTz];
+            DataType::Timestamp => [DataType::Date, DataType::TimestampTz];
+            DataType::TimestampTz => [DataType::Date, DataType::Timestamp];
 
-            DataType::String; [DataType::Bool, DataType::Uint8, DataType::Uint16, DataType::Uint32, DataType::Uint64, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Int64]
+            DataType::String => [DataType::Bool, DataType::Uint8, DataType::Uint16, DataType::Uint32, DataType::Uint64, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Int64, DataType::Date, DataType::Time, DataType::Timestamp, DataType::TimestampTz]
 
         ]
     )
@@ -68,41 +85,79 @@ pub fn can_try_cast(from: &DataType, to: &DataType) -> bool {
         to,
         from,
         [
-            DataType::Uint64; [DataType::String, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Int64];
-            DataType::Uint32; [DataType::String, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Int64, DataType::Uint64];
-            DataType::Uint16; [DataType::String, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Int64, DataType::Uint32, DataType::Uint64];
-            DataType::Uint8; [DataType::String, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Int64, DataType::Uint16, DataType::Uint32, DataType::Uint64];
-
-            DataType::Int64; [DataType::String, DataType::Uint64];
-            DataType::Int32; [DataType::String, DataType::Uint64, DataType::Uint32, DataType::Int64];
-            DataType::Int16; [DataType::String, DataType::Uint64, DataType::Uint32, DataType::Uint16, DataType::Int64, DataType::Int32];
-            DataType::Int8; [DataType::String, DataType::Uint64, DataType::Uint32, DataType::Uint16, DataType::Uint8, DataType::Int64, DataType::Int32, DataType::Int16]
+            DataType::Uint64 => [DataType::String, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Int64];
+            DataType::Uint32 => [DataType::String, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Int64, DataType::Uint64];
+            DataType::Uint16 => [DataType::String, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Int64, DataType::Uint32, DataType::Uint64];
+            DataType::Uint8 => [DataType::String, DataType::Int8, DataType::Int16, DataType::Int32, DataType::Int64, DataType::Uint16, DataType::Uint32, DataType::Uint64];
+
+            DataType::Int64 => [DataType::String, DataType::Uint64];
+            DataType::Int32 => [DataType::String, DataType::Uint64, DataType::Uint32, DataType::Int64];
+            DataType::Int16 => [DataType::String, DataType::Uint64, DataType::Uint32, DataType::Uint16, DataType::Int64, DataType::Int32];
+            DataType::Int8 => [DataType::String, DataType::Uint64, DataType::Uint32, DataType::Uint16, DataType::Uint8, DataType::Int64, DataType::Int32, DataType::Int16];
+
+            DataType::Decimal(_) => [DataType::String, DataType::Float, DataType::Double];
+
+            DataType::Date => [DataType::String];
+            DataType::Time => [DataType::String];
+            DataType::Timestamp => [DataType::String];
+            DataType::TimestampTz => [DataType::String]
         ]
     )
 }
 
-/// Try to cast the value into some DataType or return error
+/// Try to cast the value into some DataType or return error. The elements of arrays and the
+/// values of maps and structs are cast into their types.
 pub fn try_cast(value: Value, dtype: &DataType) -> Result<Value> {
-    let cast_allowed = can_cast(&value.type_of(), &dtype);
-    let try_cast_allowed = can_try_cast(&value.type_of(), &dtype);
-
-    // if the cast isn't allowed error imnmediately
-    if !cast_allowed && !try_cast_allowed {
-        dbg!("early fail");
-        return Err(Error::IllegalCast {
-            source_type: value.type_of().clone(),
-            dest_type: dtype.clone(),
-        });
+    if dtype.matches(&value) {
+        return Ok(value);
     }
 
-    // numeric casts
-    if dtype.is_numeric() {
-        return into_number(value, dtype);
-    }
+    match (value, dtype) {
+        (Value::Array(values), DataType::Array(element)) => values
+            .into_iter()
+            .map(|v| try_cast(v, element))
+            .collect::<Result<Vec<Value>>>()
+            .map(Value::Array),
+        (Value::Map(map), DataType::Map(element)) => map
+            .into_iter()
+            .map(|(k, v)| try_cast(v, element).map(|v| (k, v)))
+            .collect::<Result<Map>>()
+            .map(Value::Map),
+        (Value::Map(map), DataType::Struct(fields)) => map
+            .into_iter()
+            .map(|(k, v)| match fields.iter().find(|(name, _)| name == &k) {
+                Some((_, field_type)) => try_cast(v, field_type).map(|v| (k, v)),
+                None => Err(Error::UnknownStructField { field: k }),
+            })
+            .collect::<Result<Map>>()
+            .map(Value::Map),
+        (value, _) => {
+            let source_type = value.type_of();
+            let cast_allowed = can_cast(&source_type, dtype);
+            let try_cast_allowed = can_try_cast(&source_type, dtype);
 
-    match dtype {
-        DataType::String => into_string(value),
-        _ => unimplemented!("This type of cast hasn't been implemented yet."),
+            // if the cast isn't allowed error imnmediately
+            if !cast_allowed && !try_cast_allowed {
+                return Err(Error::IllegalCast {
+                    source_type,
+                    dest_type: dtype.clone(),
+                });
+            }
+
+            // numeric casts
+            if dtype.is_numeric() {
+                return into_number(value, dtype);
+            }
+
+            if dtype.is_temporal() {
+                return into_temporal(value, dtype);
+            }
+
+            match dtype {
+                DataType::String => into_string(value),
+                _ => unimplemented!("This type of cast hasn't been implemented yet."),
+            }
+        }
     }
 }
 
@@ -136,7 +191,7 @@ pub fn into_number(value: Value, into_type: &DataType) -> Result<Value> {
             DataType::Int64 => num.into_int64(),
             DataType::Float => num.into_float(),
             DataType::Double => num.into_double(),
-            DataType::Decimal => num.into_decimal(),
+            DataType::Decimal(size) => num.into_decimal().and_then(|n| n.with_size(*size)),
             _ => panic!("into_type should be a number when calling into_number"),
         } {
             Ok(num) => Ok(Value::Number(num)),
@@ -151,12 +206,60 @@ pub fn into_number(value: Value, into_type: &DataType) -> Result<Value> {
             into_type,
         ),
         _ => Err(Error::IllegalCast {
-            source_type: value.type_of().clone(),
+            source_type: value.type_of(),
+            dest_type: into_type.clone(),
+        }),
+    }
+}
+
+/// Cast the value into a date, time or timestamp. Timestamps with a timezone are normalised to
+/// UTC before dropping the timezone, and timestamps without one are assumed to be in UTC. Strings
+/// are parsed from their ISO 8601 forms.
+pub fn into_temporal(value: Value, into_type: &DataType) -> Result<Value> {
+    let value = match (value, into_type) {
+        (Value::String(s), _) => return parse_temporal(s, into_type),
+        (Value::TimestampTz(d), _) => Value::Timestamp(d.naive_utc()),
+        (Value::Date(d), DataType::TimestampTz) => Value::Timestamp(d.and_time(NaiveTime::MIN)),
+        (value, _) => value,
+    };
+
+    match (value, into_type) {
+        (value, _) if into_type.matches(&value) => Ok(value),
+        (Value::Date(d), DataType::Timestamp) => Ok(Value::Timestamp(d.and_time(NaiveTime::MIN))),
+        (Value::Timestamp(d), DataType::Date) => Ok(Value::Date(d.date())),
+        (Value::Timestamp(d), DataType::Time) => Ok(Value::Time(d.time())),
+        (Value::Timestamp(d), DataType::TimestampTz) => {
+            Ok(Value::TimestampTz(d.and_utc().fixed_offset()))
+        }
+        (value, _) => Err(Error::IllegalCast {
+            source_type: value.type_of(),
             dest_type: into_type.clone(),
         }),
     }
 }
 
+fn parse_temporal(s: String, into_type: &DataType) -> Result<Value> {
+    let value = match into_type {
+        DataType::Date => s.parse::<NaiveDate>().ok().map(Value::Date),
+        DataType::Time => s.parse::<NaiveTime>().ok().map(Value::Time),
+        DataType::Timestamp => s
+            .parse::<NaiveDateTime>()
+            .or_else(|_| s.replacen(' ', "T", 1).parse::<NaiveDateTime>())
+            .ok()
+            .map(Value::Timestamp),
+        DataType::TimestampTz => s
+            .parse::<DateTime<FixedOffset>>()
+            .ok()
+            .map(Value::TimestampTz),
+        _ => None,
+    };
+
+    value.ok_or_else(|| Error::FailedTemporalCast {
+        value: s,
+        dest_type: into_type.clone(),
+    })
+}
+
 pub fn into_string(value: Value) -> Result<Value> {
     Ok(value.to_string().into())
 }
@@ -167,7 +270,7 @@ pub fn cast(
     to_type: &DataType,
 ) -> std::result::Result<(), crate::error::Error> {
     df.map_column(column, move |value| {
-        try_cast(value.clone(), &to_type)
+        try_cast(value.clone(), to_type)
             .map(|casted| {
                 *value = casted;
             })
@@ -175,10 +278,105 @@ pub fn cast(
     })
 }
 
+/// Same as cast, with the values cast on the thread pool
+pub fn par_cast(
+    df: &mut DataFrame,
+    column: &str,
+    to_type: &DataType,
+) -> std::result::Result<(), crate::error::Error> {
+    df.par_map_column(column, |value| {
+        try_cast(value.clone(), to_type).map_err(|e| crate::error::Error::CastError { source: e })
+    })
+}
+
 #[cfg(test)]
 mod test_casting {
-    // use super::*;
+    use super::*;
 
     #[test]
-    fn it_can_cast() {}
+    fn it_can_cast() {
+        let values = Value::Array(vec![Value::from("1"), Value::Null, Value::from(2u8)]);
+        assert_eq!(
+            try_cast(values, &DataType::array(DataType::Int64)).unwrap(),
+            Value::Array(vec![Value::from(1i64), Value::Null, Value::from(2i64)])
+        );
+
+        let mut map = Map::new();
+        map.insert("a", Value::from(5));
+        let cast = try_cast(
+            Value::Map(map.clone()),
+            &DataType::structure(vec![("a", DataType::decimal(4, 2))]),
+        )
+        .unwrap();
+        match cast {
+            Value::Map(cast) => assert_eq!(cast["a"].to_string(), "5.00"),
+            _ => panic!("expected a map"),
+        }
+
+        assert!(matches!(
+            try_cast(Value::Map(map.clone()), &DataType::decimal(4, 2)),
+            Err(Error::IllegalCast { .. })
+        ));
+        assert!(matches!(
+            try_cast(
+                Value::Map(map),
+                &DataType::structure(vec![("b", DataType::Int8)])
+            ),
+            Err(Error::UnknownStructField { .. })
+        ));
+        assert!(matches!(
+            try_cast(Value::from(1000), &DataType::decimal(4, 2)),
+            Err(Error::FailedNumericCast { .. })
+        ));
+    }
+
+    #[test]
+    fn it_casts_temporal_types() {
+        let tz = try_cast(
+            Value::from("2019-09-05 18:14:04 -04:00"),
+            &DataType::TimestampTz,
+        )
+        .unwrap();
+        assert_eq!(tz.to_string(), "2019-09-05 18:14:04 -04:00");
+
+        let utc = try_cast(tz, &DataType::Timestamp).unwrap();
+        assert_eq!(utc.to_string(), "2019-09-05 22:14:04");
+        assert_eq!(
+            try_cast(utc.clone(), &DataType::TimestampTz)
+                .unwrap()
+                .to_string(),
+            "2019-09-05 22:14:04 +00:00"
+        );
+        assert_eq!(
+            try_cast(utc, &DataType::Date).unwrap().to_string(),
+            "2019-09-05"
+        );
+
+        let date = try_cast(Value::from("2019-09-05"), &DataType::Date).unwrap();
+        assert_eq!(
+            try_cast(date.clone(), &DataType::Timestamp)
+                .unwrap()
+                .to_string(),
+            "2019-09-05 00:00:00"
+        );
+        assert_eq!(
+            try_cast(date, &DataType::String).unwrap(),
+            Value::from("2019-09-05")
+        );
+        assert_eq!(
+            try_cast(Value::from("2019-09-05 10:00:00"), &DataType::Timestamp)
+                .unwrap()
+                .to_string(),
+            "2019-09-05 10:00:00"
+        );
+
+        assert!(matches!(
+            try_cast(Value::from("noon"), &DataType::Time),
+            Err(Error::FailedTemporalCast { .. })
+        ));
+        assert!(matches!(
+            try_cast(Value::from(1), &DataType::Date),
+            Err(Error::IllegalCast { .. })
+        ));
+    }
 }
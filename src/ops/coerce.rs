@@ -0,0 +1,231 @@
+use crate::{ops::cast, DataFrame, DataType, Field, Value};
+
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Column {} is not nullable, but row {} is null", column, row))]
+    NullValue { column: String, row: usize },
+
+    #[snafu(display(
+        "Failed to cast the value {} on row {} of column {} into {}: {}",
+        value,
+        row,
+        column,
+        dtype,
+        source
+    ))]
+    FailedCast {
+        column: String,
+        row: usize,
+        value: String,
+        dtype: DataType,
+        source: Box<cast::Error>,
+    },
+}
+
+/// How many of a column's values were changed by coercion
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnReport {
+    pub column: String,
+    /// Values cast into the field's type
+    pub cast: usize,
+    /// Nulls replaced with the field's default
+    pub defaulted: usize,
+    /// Values that couldn't be cast, or nulls in a field that isn't nullable and has no default.
+    /// These are left as they were.
+    pub failed: usize,
+}
+
+/// The result of coercing a dataframe's data into its schema, with a report for each column
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoercionReport {
+    pub columns: Vec<ColumnReport>,
+}
+
+impl CoercionReport {
+    /// True when every value conforms to the schema
+    pub fn is_ok(&self) -> bool {
+        self.failed() == 0
+    }
+
+    pub fn cast(&self) -> usize {
+        self.columns.iter().map(|c| c.cast).sum()
+    }
+
+    pub fn defaulted(&self) -> usize {
+        self.columns.iter().map(|c| c.defaulted).sum()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.columns.iter().map(|c| c.failed).sum()
+    }
+
+    pub fn column(&self, name: &str) -> Option<&ColumnReport> {
+        self.columns.iter().find(|c| c.column == name)
+    }
+}
+
+/// What coercing a single value did
+#[derive(Debug)]
+pub enum Coerced {
+    /// The value already conforms to the field
+    Unchanged,
+    Cast(Value),
+    Defaulted(Value),
+}
+
+/// Coerce the value into the field's type. Nulls are replaced with the field's default if it has
+/// one, and are an error if the field isn't nullable. Fields of type any accept every value.
+pub fn coerce_value(value: &Value, field: &Field, row: usize) -> Result<Coerced, Error> {
+    if value.is_null() {
+        return match &field.default {
+            Some(default) => Ok(Coerced::Defaulted(default.clone())),
+            None if field.nullable => Ok(Coerced::Unchanged),
+            None => Err(Error::NullValue {
+                column: field.name.clone(),
+                row,
+            }),
+        };
+    }
+
+    if field.dtype.matches(value) {
+        return Ok(Coerced::Unchanged);
+    }
+
+    cast::try_cast(value.clone(), &field.dtype)
+        .map(Coerced::Cast)
+        .map_err(|e| Error::FailedCast {
+            column: field.name.clone(),
+            row,
+            value: value.to_string(),
+            dtype: field.dtype.clone(),
+            source: Box::new(e),
+        })
+}
+
+/// Coerce a row into the fields, failing on the first value that doesn't conform
+pub fn coerce_row(row: Vec<Value>, fields: &[Field], index: usize) -> Result<Vec<Value>, Error> {
+    row.into_iter()
+        .zip(fields)
+        .map(|(value, field)| {
+            coerce_value(&value, field, index).map(|coerced| match coerced {
+                Coerced::Unchanged => value,
+                Coerced::Cast(value) | Coerced::Defaulted(value) => value,
+            })
+        })
+        .collect()
+}
+
+/// Coerce every value of the dataframe into its field, values that fail are left as they were and
+/// counted in the report
+pub fn coerce(df: &mut DataFrame<'_>) -> CoercionReport {
+    let fields = df.schema.fields().to_vec();
+
+    let mut columns = fields
+        .iter()
+        .map(|f| ColumnReport {
+            column: f.name.clone(),
+            ..ColumnReport::default()
+        })
+        .collect::<Vec<ColumnReport>>();
+
+    if fields.is_empty() {
+        return CoercionReport { columns };
+    }
+
+    let data = df.columns_mut().iter_mut().zip(&fields);
+
+    for ((values, field), report) in data.zip(columns.iter_mut()) {
+        let mut row = 0;
+        let _ = values.try_for_each_mut(|value| {
+            match coerce_value(value, field, row) {
+                Ok(Coerced::Unchanged) => {}
+                Ok(Coerced::Cast(cast)) => {
+                    *value = cast;
+                    report.cast += 1;
+                }
+                Ok(Coerced::Defaulted(default)) => {
+                    *value = default;
+                    report.defaulted += 1;
+                }
+                Err(_) => report.failed += 1,
+            }
+            row += 1;
+            Ok::<(), ()>(())
+        });
+    }
+
+    CoercionReport { columns }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{row, schema::Schema};
+
+    fn schema() -> Schema {
+        Schema::with_fields(vec![
+            Field::with_type("a", DataType::Int64).with_nullable(false),
+            Field::with_type("b", DataType::Uint8).with_default(Value::from(0u8)),
+            Field::with_type("c", DataType::String),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn it_coerces_data_into_the_schema() {
+        let mut df = DataFrame::with_schema(schema());
+        df.extend_unchecked(vec![
+            row![1i64, 2, true],
+            row![2, Value::Null, "x"],
+            row![Value::Null, "300", 3],
+        ]);
+
+        let report = coerce(&mut df);
+
+        assert_eq!(
+            report.column("a"),
+            Some(&ColumnReport {
+                column: "a".to_string(),
+                cast: 1,
+                defaulted: 0,
+                failed: 1,
+            })
+        );
+        assert_eq!(
+            report.column("b"),
+            Some(&ColumnReport {
+                column: "b".to_string(),
+                cast: 1,
+                defaulted: 1,
+                failed: 1,
+            })
+        );
+        assert_eq!(report.column("c").map(|c| c.cast), Some(2));
+        assert!(!report.is_ok());
+
+        assert_eq!(df.row(0).unwrap(), &row![1i64, 2u8, "true"][..]);
+        assert_eq!(df.row(1).unwrap(), &row![2i64, 0u8, "x"][..]);
+        assert_eq!(df.row(2).unwrap(), &row![Value::Null, "300", "3"][..]);
+    }
+
+    #[test]
+    fn it_coerces_rows() {
+        let schema = schema();
+        let fields = schema.fields();
+
+        assert_eq!(
+            coerce_row(row!["5", Value::Null, "x"], fields, 0).unwrap(),
+            row![5i64, 0u8, "x"]
+        );
+        assert!(matches!(
+            coerce_row(row![Value::Null, 1, "x"], fields, 3),
+            Err(Error::NullValue { row: 3, .. })
+        ));
+        assert!(matches!(
+            coerce_row(row![1, "y", "x"], fields, 0),
+            Err(Error::FailedCast { .. })
+        ));
+    }
+}
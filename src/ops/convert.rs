@@ -1,7 +1,7 @@
-use crate::{DataFrame, DataType, Value};
+use crate::{traits::TypeOf, DataFrame, DataType, Value};
 
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
 use snafu::Snafu;
-use std::error::Error as ErrorTrait;
 
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -26,46 +26,209 @@ pub enum Error {
     },
 }
 
+/// The offsets of the timezone abbreviations understood by %Z, in seconds east of UTC
+pub const TIMEZONE_ABBREVIATIONS: &[(&str, i32)] = &[
+    ("UTC", 0),
+    ("GMT", 0),
+    ("Z", 0),
+    ("EST", -5 * 3600),
+    ("EDT", -4 * 3600),
+    ("CST", -6 * 3600),
+    ("CDT", -5 * 3600),
+    ("MST", -7 * 3600),
+    ("MDT", -6 * 3600),
+    ("PST", -8 * 3600),
+    ("PDT", -7 * 3600),
+    ("AKST", -9 * 3600),
+    ("AKDT", -8 * 3600),
+    ("HST", -10 * 3600),
+    ("BST", 3600),
+    ("CET", 3600),
+    ("CEST", 2 * 3600),
+    ("EET", 2 * 3600),
+    ("EEST", 3 * 3600),
+    ("JST", 9 * 3600),
+    ("AEST", 10 * 3600),
+    ("AEDT", 11 * 3600),
+];
+
 pub enum Convert<'a> {
+    /// Parse strings into timestamps. Formats with an offset (`%z`) or a timezone abbreviation
+    /// (`%Z`) make timezone aware timestamps, the others make naive timestamps.
     ParseDateTime(&'a str),
+    /// Parse strings into calendar dates
+    ParseDate(&'a str),
+    /// Parse strings into times of day
+    ParseTime(&'a str),
+    /// Convert timezone aware timestamps to the offset, normalising them to a single timezone
+    ToTimezone(FixedOffset),
+    /// Make naive timestamps timezone aware by treating them as local to the offset
+    AssumeTimezone(FixedOffset),
 }
 
-pub fn convert<'b, 'a: 'b>(
-    df: &mut DataFrame<'a>,
-    column: &str,
-    conversion: Convert<'b>,
-) -> Result<DataType, crate::error::Error> {
-    match conversion {
-        Convert::ParseDateTime(fmt) => try_parse_datetime(df, column, fmt),
+impl<'a> Convert<'a> {
+    /// The type of the converted values
+    pub fn dtype(&self) -> DataType {
+        match self {
+            Convert::ParseDateTime(fmt) if has_timezone(fmt) => DataType::TimestampTz,
+            Convert::ParseDateTime(_) => DataType::Timestamp,
+            Convert::ParseDate(_) => DataType::Date,
+            Convert::ParseTime(_) => DataType::Time,
+            Convert::ToTimezone(_) | Convert::AssumeTimezone(_) => DataType::TimestampTz,
+        }
+    }
+
+    fn apply(&self, value: &Value) -> Result<Value, Error> {
+        let dtype = self.dtype();
+
+        match (self, value) {
+            (_, Value::Null) => Ok(Value::Null),
+            (Convert::ParseDateTime(fmt), value)
+            | (Convert::ParseDate(fmt), value)
+            | (Convert::ParseTime(fmt), value) => parse_temporal(&value.to_string(), fmt, &dtype)
+                .map_err(|message| Error::ParseDateError {
+                    value: value.to_string(),
+                    format: fmt.to_string(),
+                    message,
+                }),
+            (Convert::ToTimezone(offset), Value::TimestampTz(d)) => {
+                Ok(Value::TimestampTz(d.with_timezone(offset)))
+            }
+            (Convert::AssumeTimezone(offset), Value::Timestamp(d)) => offset
+                .from_local_datetime(d)
+                .single()
+                .map(Value::TimestampTz)
+                .ok_or_else(|| Error::IllegalConversion {
+                    value_type: value.type_of(),
+                    dest_type: dtype,
+                }),
+            (_, value) => Err(Error::IllegalConversion {
+                value_type: value.type_of(),
+                dest_type: dtype,
+            }),
+        }
     }
 }
 
-fn try_parse_datetime(
-    df: &mut DataFrame,
+pub fn convert<'b, 'a: 'b>(
+    df: &mut DataFrame<'a>,
     column: &str,
-    fmt: &str,
+    conversion: Convert<'b>,
 ) -> Result<DataType, crate::error::Error> {
-    use chrono::NaiveDateTime;
-
-    let parse = |value: &mut Value| -> Result<Value, Error> {
-        let str_val = value.to_string();
-        NaiveDateTime::parse_from_str(&str_val, &fmt)
-            .map_err(|err| Error::ParseDateError {
-                value: value.to_string(),
-                format: fmt.to_string().clone(),
-                message: err.description().to_string(),
-            })
-            .and_then(|date| Ok(Value::Date(date)))
-    };
-
     df.map_column(column, |value| {
-        parse(value)
+        conversion
+            .apply(value)
             .map(|converted| {
                 *value = converted;
             })
             .map_err(|e| crate::error::Error::ConvertError { source: e })
     })
-    .map(|_| DataType::Date)
+    .map(|_| conversion.dtype())
+}
+
+/// Same as convert, with the values converted on the thread pool
+pub fn par_convert<'b, 'a: 'b>(
+    df: &mut DataFrame<'a>,
+    column: &str,
+    conversion: Convert<'b>,
+) -> Result<DataType, crate::error::Error> {
+    df.par_map_column(column, |value| {
+        conversion
+            .apply(value)
+            .map_err(|e| crate::error::Error::ConvertError { source: e })
+    })
+    .map(|_| conversion.dtype())
+}
+
+/// Whether the format parses an offset or a timezone abbreviation
+fn has_timezone(fmt: &str) -> bool {
+    fmt.contains("%z") || fmt.contains("%:z") || fmt.contains("%#z") || fmt.contains("%Z")
+}
+
+/// The type of the values parsed with the format: timezone aware timestamps for formats with an
+/// offset or timezone, naive timestamps for formats with a date and time, and dates or times for
+/// the formats with only one of them
+pub(crate) fn format_type(fmt: &str) -> DataType {
+    const DATE: &[&str] = &[
+        "%Y", "%y", "%C", "%G", "%m", "%d", "%e", "%b", "%B", "%h", "%j", "%D", "%F", "%x", "%c",
+        "%s",
+    ];
+    const TIME: &[&str] = &[
+        "%H", "%k", "%I", "%l", "%M", "%S", "%T", "%R", "%r", "%X", "%c", "%s",
+    ];
+
+    let has_date = DATE.iter().any(|s| fmt.contains(s));
+    let has_time = TIME.iter().any(|s| fmt.contains(s));
+
+    if has_timezone(fmt) {
+        DataType::TimestampTz
+    } else if has_date && has_time {
+        DataType::Timestamp
+    } else if has_time {
+        DataType::Time
+    } else {
+        DataType::Date
+    }
+}
+
+/// Parse the string into a value of the temporal type. `%Z` matches the abbreviations in
+/// TIMEZONE_ABBREVIATIONS. Timestamps can be parsed from formats without a time, which are
+/// midnight.
+pub(crate) fn parse_temporal(s: &str, fmt: &str, dtype: &DataType) -> Result<Value, String> {
+    match dtype {
+        DataType::Date => NaiveDate::parse_from_str(s, fmt)
+            .map(Value::Date)
+            .map_err(|e| e.to_string()),
+        DataType::Time => NaiveTime::parse_from_str(s, fmt)
+            .map(Value::Time)
+            .map_err(|e| e.to_string()),
+        DataType::Timestamp => NaiveDateTime::parse_from_str(s, fmt)
+            .or_else(|e| {
+                NaiveDate::parse_from_str(s, fmt)
+                    .map(|d| d.and_time(NaiveTime::MIN))
+                    .map_err(|_| e)
+            })
+            .map(Value::Timestamp)
+            .map_err(|e| e.to_string()),
+        DataType::TimestampTz if fmt.contains("%Z") => {
+            let (s, offset) = replace_abbreviation(s)?;
+            parse_temporal(&s, &fmt.replacen("%Z", "%z", 1), dtype).map(|v| match v {
+                // the abbreviation replaced the offset it was parsed with
+                Value::TimestampTz(d) => Value::TimestampTz(d.with_timezone(&offset)),
+                v => v,
+            })
+        }
+        DataType::TimestampTz => DateTime::parse_from_str(s, fmt)
+            .map(Value::TimestampTz)
+            .map_err(|e| e.to_string()),
+        dtype => Err(format!("{} is not a date or time type", dtype)),
+    }
+}
+
+/// Replace the first timezone abbreviation in the string with its offset
+fn replace_abbreviation(s: &str) -> Result<(String, FixedOffset), String> {
+    for word in s.split(|c: char| !c.is_ascii_alphabetic()) {
+        if let Some((_, seconds)) = TIMEZONE_ABBREVIATIONS
+            .iter()
+            .find(|(abbreviation, _)| *abbreviation == word)
+        {
+            let offset = FixedOffset::east_opt(*seconds).ok_or("the offset is out of range")?;
+            let sign = if *seconds < 0 { '-' } else { '+' };
+            let replacement = format!(
+                "{}{:02}{:02}",
+                sign,
+                seconds.abs() / 3600,
+                seconds.abs() % 3600 / 60
+            );
+
+            let start = word.as_ptr() as usize - s.as_ptr() as usize;
+            let mut replaced = s.to_string();
+            replaced.replace_range(start..start + word.len(), &replacement);
+            return Ok((replaced, offset));
+        }
+    }
+
+    Err(format!("{} doesn't have a known timezone abbreviation", s))
 }
 
 #[cfg(test)]
@@ -78,7 +241,100 @@ mod test_convert {
         let conversion_result = df.convert_column("a", Convert::ParseDateTime("%Y-%m-%d %H:%M:%S"));
 
         assert!(conversion_result.is_ok());
+        assert_eq!(df.schema()[0].dtype(), &DataType::Timestamp);
+    }
+
+    #[test]
+    fn it_converts_timezones() {
+        let rows = vec![
+            vec!["Wed May 21 00:00:00 EDT 2008".into()],
+            vec!["Thu May 22 09:30:00 UTC 2008".into()],
+        ];
+
+        let mut df = DataFrame::new(&["a"], rows);
+        df.convert_column("a", Convert::ParseDateTime("%a %B %e %H:%M:%S %Z %Y"))
+            .unwrap();
+        assert_eq!(df.schema()[0].dtype(), &DataType::TimestampTz);
+        assert_eq!(
+            df.row(0).unwrap()[0].to_string(),
+            "2008-05-21 00:00:00 -04:00"
+        );
+
+        let utc = FixedOffset::east_opt(0).unwrap();
+        df.convert_column("a", Convert::ToTimezone(utc)).unwrap();
+        assert_eq!(
+            df.row(0).unwrap()[0].to_string(),
+            "2008-05-21 04:00:00 +00:00"
+        );
+        assert_eq!(
+            df.row(1).unwrap()[0].to_string(),
+            "2008-05-22 09:30:00 +00:00"
+        );
+
+        let mut df = DataFrame::new(&["a"], vec![vec!["2019-09-05 18:14:04 +0200".into()]]);
+        df.convert_column("a", Convert::ParseDateTime("%Y-%m-%d %H:%M:%S %z"))
+            .unwrap();
+        assert_eq!(
+            df.row(0).unwrap()[0].to_string(),
+            "2019-09-05 18:14:04 +02:00"
+        );
+
+        let mut df = DataFrame::new(&["a"], vec![vec!["2019-09-05 18:14:04".into()]]);
+        df.convert_column("a", Convert::ParseDateTime("%Y-%m-%d %H:%M:%S"))
+            .unwrap();
+        let eastern = FixedOffset::west_opt(5 * 3600).unwrap();
+        df.convert_column("a", Convert::AssumeTimezone(eastern))
+            .unwrap();
+        assert_eq!(
+            df.row(0).unwrap()[0].to_string(),
+            "2019-09-05 18:14:04 -05:00"
+        );
+        assert!(df
+            .convert_column("a", Convert::AssumeTimezone(eastern))
+            .is_err());
+    }
+
+    #[test]
+    fn it_parses_dates_and_times() {
+        let mut df = DataFrame::new(
+            &["a", "b"],
+            vec![vec!["09/05/2019".into(), "6:14 PM".into()]],
+        );
+        df.convert_column("a", Convert::ParseDate("%m/%d/%Y"))
+            .unwrap();
+        df.convert_column("b", Convert::ParseTime("%I:%M %p"))
+            .unwrap();
+
+        assert_eq!(df.row(0).unwrap()[0].to_string(), "2019-09-05");
+        assert_eq!(df.row(0).unwrap()[1].to_string(), "18:14:00");
+        assert_eq!(df.schema()[1].dtype(), &DataType::Time);
+    }
+
+    #[test]
+    fn it_converts_in_parallel() {
+        let rows = vec![
+            vec!["2019-09-05 18:14:04".into()],
+            vec!["2019-09-06 01:02:03".into()],
+            vec!["not a date".into()],
+            vec!["also not a date".into()],
+        ];
+
+        let mut sequential = DataFrame::new(&["a"], rows.clone());
+        let mut parallel = DataFrame::new(&["a"], rows);
+
+        let fmt = "%Y-%m-%d %H:%M:%S";
+        let sequential_err = sequential
+            .convert_column("a", Convert::ParseDateTime(fmt))
+            .unwrap_err();
+        let parallel_err = parallel
+            .par_convert_column("a", Convert::ParseDateTime(fmt))
+            .unwrap_err();
 
-        dbg!(df);
+        // both stop at the first invalid row, leaving the rows after it untouched
+        assert_eq!(
+            format!("{:?}", sequential_err),
+            format!("{:?}", parallel_err)
+        );
+        assert_eq!(sequential, parallel);
     }
 }
@@ -0,0 +1,458 @@
+use crate::{
+    column::Column,
+    ops::{
+        cast,
+        coerce::{self, Coerced},
+    },
+    DataFrame, DataType, Field, Schema, Value,
+};
+
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display(
+        "The types of field {} can't be merged, neither {} nor {} widens into the other",
+        field,
+        from,
+        to
+    ))]
+    IncompatibleTypes {
+        field: String,
+        from: DataType,
+        to: DataType,
+    },
+
+    #[snafu(display(
+        "Column {} is missing, and the field isn't nullable and doesn't have a default",
+        column
+    ))]
+    MissingColumn { column: String },
+
+    #[snafu(display("Failed to evolve the data: {}", source))]
+    FailedCoerce { source: coerce::Error },
+
+    #[snafu(display("The merged schema is invalid: {}", source))]
+    SchemaError { source: crate::schema::Error },
+}
+
+/// A difference between an old and a new schema
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// The new schema has a field that the old one doesn't
+    Added(Field),
+    /// The old schema has a field that the new one doesn't
+    Removed(Field),
+    /// The field has a new name, matched using the fields' aliases
+    Renamed {
+        from: String,
+        to: String,
+    },
+    TypeChanged {
+        field: String,
+        from: DataType,
+        to: DataType,
+    },
+    NullabilityChanged {
+        field: String,
+        nullable: bool,
+    },
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Change::Added(field) => write!(f, "added field {}", field.name),
+            Change::Removed(field) => write!(f, "removed field {}", field.name),
+            Change::Renamed { from, to } => write!(f, "renamed field {} to {}", from, to),
+            Change::TypeChanged { field, from, to } => {
+                write!(f, "changed the type of {} from {} to {}", field, from, to)
+            }
+            Change::NullabilityChanged { field, nullable } => match nullable {
+                true => write!(f, "made {} nullable", field),
+                false => write!(f, "made {} not nullable", field),
+            },
+        }
+    }
+}
+
+/// Whether data of the old schema can be evolved into the new one without failing or losing
+/// values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compatibility {
+    Compatible,
+    Breaking,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaChange {
+    pub change: Change,
+    pub compatibility: Compatibility,
+}
+
+impl SchemaChange {
+    fn new(change: Change, compatible: bool) -> SchemaChange {
+        SchemaChange {
+            change,
+            compatibility: match compatible {
+                true => Compatibility::Compatible,
+                false => Compatibility::Breaking,
+            },
+        }
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.compatibility == Compatibility::Breaking
+    }
+}
+
+/// The changes between two schemas, in the order of the new schema's fields followed by the
+/// removed fields
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaDiff {
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// True when none of the changes are breaking
+    pub fn is_compatible(&self) -> bool {
+        !self.changes.iter().any(SchemaChange::is_breaking)
+    }
+
+    pub fn breaking(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.changes.iter().filter(|c| c.is_breaking())
+    }
+}
+
+impl std::fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for change in &self.changes {
+            match change.compatibility {
+                Compatibility::Compatible => writeln!(f, "{}", change.change)?,
+                Compatibility::Breaking => writeln!(f, "{} (breaking)", change.change)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// True when values of the type can be cast into the other type without failing or losing
+/// precision. Decimals widen into decimals with at least as many digits on both sides of the
+/// point, and everything widens into any.
+pub fn widens(from: &DataType, to: &DataType) -> bool {
+    match (from, to) {
+        (from, to) if from == to => true,
+        (_, DataType::Any) | (DataType::Null, _) => true,
+        (DataType::Decimal(_), DataType::Decimal(None)) => true,
+        (DataType::Decimal(Some((p1, s1))), DataType::Decimal(Some((p2, s2)))) => {
+            s2 >= s1 && i64::from(*p2) - i64::from(*s2) >= i64::from(*p1) - i64::from(*s1)
+        }
+        (DataType::Decimal(None), DataType::Decimal(Some(_))) => false,
+        (DataType::Array(from), DataType::Array(to)) | (DataType::Map(from), DataType::Map(to)) => {
+            widens(from, to)
+        }
+        (from, to) => cast::can_cast(from, to),
+    }
+}
+
+/// Match each field of the new schema to the field of the old schema it evolved from. Fields are
+/// matched by name, and fields that are only in one of the schemas are matched by their aliases.
+fn match_fields(old: &Schema, new: &Schema) -> Vec<Option<usize>> {
+    let position =
+        |schema: &Schema, name: &str| schema.fields().iter().position(|f| f.name == name);
+
+    let mut matched = new
+        .fields()
+        .iter()
+        .map(|field| position(old, &field.name))
+        .collect::<Vec<Option<usize>>>();
+
+    for (j, field) in new.fields().iter().enumerate() {
+        if matched[j].is_some() {
+            continue;
+        }
+
+        matched[j] = old.fields().iter().enumerate().position(|(i, old_field)| {
+            position(new, &old_field.name).is_none()
+                && !matched.contains(&Some(i))
+                && (field.is_named(&old_field.name) || old_field.is_named(&field.name))
+        });
+    }
+
+    matched
+}
+
+impl Schema {
+    /// The changes that turn this schema into the other one, each classified as compatible or
+    /// breaking. Type changes are compatible when the old type widens into the new one, added
+    /// fields and nullability changes when the field is nullable or has a default, and removed
+    /// fields are always breaking.
+    pub fn diff(&self, other: &Schema) -> SchemaDiff {
+        let matched = match_fields(self, other);
+        let mut changes = vec![];
+
+        for (field, old) in other.fields().iter().zip(&matched) {
+            let fillable = field.nullable || field.default.is_some();
+
+            let old = match old {
+                Some(i) => &self.fields()[*i],
+                None => {
+                    changes.push(SchemaChange::new(Change::Added(field.clone()), fillable));
+                    continue;
+                }
+            };
+
+            if old.name != field.name {
+                changes.push(SchemaChange::new(
+                    Change::Renamed {
+                        from: old.name.clone(),
+                        to: field.name.clone(),
+                    },
+                    true,
+                ));
+            }
+
+            if old.dtype != field.dtype {
+                changes.push(SchemaChange::new(
+                    Change::TypeChanged {
+                        field: field.name.clone(),
+                        from: old.dtype.clone(),
+                        to: field.dtype.clone(),
+                    },
+                    widens(&old.dtype, &field.dtype),
+                ));
+            }
+
+            if old.nullable != field.nullable {
+                changes.push(SchemaChange::new(
+                    Change::NullabilityChanged {
+                        field: field.name.clone(),
+                        nullable: field.nullable,
+                    },
+                    fillable,
+                ));
+            }
+        }
+
+        for (i, field) in self.fields().iter().enumerate() {
+            if !matched.contains(&Some(i)) {
+                changes.push(SchemaChange::new(Change::Removed(field.clone()), false));
+            }
+        }
+
+        SchemaDiff { changes }
+    }
+
+    /// A schema that the data of both schemas fits into. Matching fields take the other schema's
+    /// name and the wider of the types, keeping the old name as an alias, and fields that are only
+    /// in one of the schemas become nullable unless they have a default.
+    pub fn merge(&self, other: &Schema) -> Result<Schema, Error> {
+        let matched = match_fields(self, other);
+        let mut fields = vec![];
+
+        for (field, old) in other.fields().iter().zip(&matched) {
+            let mut field = field.clone();
+
+            let old = match old {
+                Some(i) => &self.fields()[*i],
+                None => {
+                    field.nullable = field.nullable || field.default.is_none();
+                    fields.push(field);
+                    continue;
+                }
+            };
+
+            if widens(&field.dtype, &old.dtype) {
+                field.dtype = old.dtype.clone();
+            } else if !widens(&old.dtype, &field.dtype) {
+                return Err(Error::IncompatibleTypes {
+                    field: field.name,
+                    from: old.dtype.clone(),
+                    to: field.dtype,
+                });
+            }
+
+            field.nullable = field.nullable || old.nullable;
+            field.default = field.default.or_else(|| old.default.clone());
+            field.doc = field.doc.or_else(|| old.doc.clone());
+
+            for alias in std::iter::once(&old.name).chain(&old.aliases) {
+                if !field.is_named(alias) {
+                    field.aliases.push(alias.clone());
+                }
+            }
+
+            fields.push(field);
+        }
+
+        for (i, field) in self.fields().iter().enumerate() {
+            if !matched.contains(&Some(i)) {
+                let mut field = field.clone();
+                field.nullable = field.nullable || field.default.is_none();
+                fields.push(field);
+            }
+        }
+
+        let mut schema = Schema::with_fields(fields).context(SchemaError)?;
+        schema.name = other.name.clone().or_else(|| self.name.clone());
+        schema.doc = other.doc.clone().or_else(|| self.doc.clone());
+
+        Ok(schema)
+    }
+}
+
+/// Reshape the dataframe's data into the schema. Columns are matched to fields the same way as
+/// Schema::diff, values are coerced into their field's type, missing columns are filled with the
+/// field's default or nulls, and columns that aren't in the schema are dropped. The dataframe
+/// is left unchanged on errors.
+pub fn evolve(df: &mut DataFrame<'_>, schema: &Schema) -> Result<(), Error> {
+    let matched = match_fields(&df.schema, schema);
+
+    for (field, old) in schema.fields().iter().zip(&matched) {
+        if old.is_none() && !field.nullable && field.default.is_none() {
+            return Err(Error::MissingColumn {
+                column: field.name.clone(),
+            });
+        }
+    }
+
+    let rows = df.dim.1;
+    let mut columns = Vec::with_capacity(schema.len());
+
+    for (field, old) in schema.fields().iter().zip(&matched) {
+        let mut column = Column::with_capacity(rows);
+
+        for row in 0..rows {
+            let value = match old {
+                Some(i) => df.columns[*i].get(row).unwrap_or(Value::Null),
+                None => Value::Null,
+            };
+
+            let value = match coerce::coerce_value(&value, field, row) {
+                Ok(Coerced::Unchanged) => value,
+                Ok(Coerced::Cast(value)) | Ok(Coerced::Defaulted(value)) => value,
+                Err(source) => return Err(Error::FailedCoerce { source }),
+            };
+
+            column.push(value);
+        }
+
+        columns.push(column);
+    }
+
+    *df = DataFrame::from_columns(schema.clone(), columns);
+    df.dim.1 = rows;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::row;
+
+    fn old_schema() -> Schema {
+        Schema::with_fields(vec![
+            Field::with_type("id", DataType::Int32).with_nullable(false),
+            Field::with_type("cust_id", DataType::Int64),
+            Field::with_type("price", DataType::decimal(6, 2)),
+            Field::with_type("note", DataType::String).with_nullable(false),
+        ])
+        .unwrap()
+    }
+
+    fn new_schema() -> Schema {
+        Schema::with_fields(vec![
+            Field::with_type("id", DataType::Int64).with_nullable(false),
+            Field::with_type("customer_id", DataType::Int64).with_alias("cust_id"),
+            Field::with_type("price", DataType::decimal(4, 2)),
+            Field::with_type("active", DataType::Bool).with_default(Value::Bool(true)),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn it_diffs_schemas() {
+        let diff = old_schema().diff(&new_schema());
+
+        let changes = diff
+            .changes
+            .iter()
+            .map(|c| (c.change.to_string(), c.is_breaking()))
+            .collect::<Vec<(String, bool)>>();
+
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    "changed the type of id from int32 to int64".to_string(),
+                    false
+                ),
+                ("renamed field cust_id to customer_id".to_string(), false),
+                (
+                    "changed the type of price from decimal(6,2) to decimal(4,2)".to_string(),
+                    true
+                ),
+                ("added field active".to_string(), false),
+                ("removed field note".to_string(), true),
+            ]
+        );
+        assert!(!diff.is_compatible());
+        assert_eq!(diff.breaking().count(), 2);
+        assert!(old_schema().diff(&old_schema()).is_empty());
+    }
+
+    #[test]
+    fn it_merges_schemas() {
+        let merged = old_schema().merge(&new_schema()).unwrap();
+
+        assert_eq!(
+            merged.field_names(),
+            vec!["id", "customer_id", "price", "active", "note"]
+        );
+        assert_eq!(merged[0].dtype, DataType::Int64);
+        assert_eq!(merged[1].aliases(), &["cust_id".to_string()]);
+        assert_eq!(merged[2].dtype, DataType::decimal(6, 2));
+        assert_eq!(merged[3].default(), Some(&Value::Bool(true)));
+        assert!(merged[4].nullable);
+        assert!(old_schema().diff(&merged).is_compatible());
+
+        let other = Schema::with_fields(vec![Field::with_type("id", DataType::Date)]).unwrap();
+        assert!(matches!(
+            old_schema().merge(&other),
+            Err(Error::IncompatibleTypes { .. })
+        ));
+    }
+
+    #[test]
+    fn it_evolves_dataframes() {
+        let mut df = DataFrame::with_schema(old_schema());
+        df.extend_unchecked(vec![
+            row![1, 10i64, "1.5", "a"],
+            row![2, 20i64, Value::Null, "b"],
+        ]);
+        df.coherce_data();
+
+        let schema = old_schema().merge(&new_schema()).unwrap();
+        df.evolve_to(&schema).unwrap();
+
+        assert_eq!(df.schema(), &schema);
+        assert_eq!(&df.row(0).unwrap().data()[..2], &row![1i64, 10i64][..]);
+        assert_eq!(df.row(0).unwrap()[2].to_string(), "1.50");
+        assert_eq!(df.row(1).unwrap()[3], Value::Bool(true));
+        assert_eq!(df.row(1).unwrap()[4], Value::from("b"));
+
+        let required = Schema::with_fields(vec![
+            Field::with_type("id", DataType::Int64),
+            Field::with_type("missing", DataType::String).with_nullable(false),
+        ])
+        .unwrap();
+        assert!(df.evolve_to(&required).is_err());
+        assert_eq!(df.schema(), &schema);
+    }
+}
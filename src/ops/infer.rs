@@ -0,0 +1,542 @@
+use crate::{
+    ops::convert::{format_type, parse_temporal},
+    traits::TypeOf,
+    value::number::Num,
+    DataFrame, DataType, Number, Value,
+};
+
+use rust_decimal::Decimal;
+use snafu::Snafu;
+use std::borrow::Borrow;
+use std::str::FromStr;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display(
+        "Column {} was inferred as {}, but the value {} on row {} can't be converted to it",
+        column,
+        dtype,
+        value,
+        row
+    ))]
+    RewriteError {
+        column: String,
+        dtype: DataType,
+        value: String,
+        row: usize,
+    },
+}
+
+/// The date formats tried when no formats are given. The first format that parses every value is
+/// used, and decides if the column holds dates, times or timestamps with or without a timezone.
+pub const DEFAULT_DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S%.f%:z",
+    "%Y-%m-%d %H:%M:%S %z",
+    "%Y-%m-%d",
+    "%m/%d/%Y %H:%M:%S",
+    "%m/%d/%Y",
+    "%H:%M:%S",
+];
+
+/// Options for inferring a dataframe's schema from its data
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inference {
+    sample_size: Option<usize>,
+    date_formats: Vec<String>,
+    rewrite: bool,
+}
+
+impl Default for Inference {
+    fn default() -> Self {
+        Inference {
+            sample_size: Some(1000),
+            date_formats: DEFAULT_DATE_FORMATS.iter().map(|f| f.to_string()).collect(),
+            rewrite: false,
+        }
+    }
+}
+
+impl Inference {
+    pub fn new() -> Inference {
+        Inference::default()
+    }
+
+    /// Only look at the first `rows` rows of each column, or every row when None
+    pub fn with_sample_size(mut self, rows: Option<usize>) -> Inference {
+        self.sample_size = rows;
+        self
+    }
+
+    /// The strftime style formats tried, in order, when detecting dates
+    pub fn with_date_formats<S: AsRef<str>>(mut self, formats: &[S]) -> Inference {
+        self.date_formats = formats.iter().map(|f| f.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Convert the data into the inferred types, rather than only updating the schema
+    pub fn with_rewrite(mut self, rewrite: bool) -> Inference {
+        self.rewrite = rewrite;
+        self
+    }
+}
+
+/// The type of a column as inferred from its values
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inferred {
+    pub dtype: DataType,
+    pub nullable: bool,
+    /// The format used to parse the column's dates, if it holds dates parsed from strings
+    pub date_format: Option<String>,
+}
+
+/// What a single value looks like
+enum Kind {
+    Null,
+    Bool,
+    Int(i128),
+    /// A number with a fractional part, and whether f64 can represent it exactly
+    Real(bool),
+    /// A string, along with which of the date formats parse it
+    Text(Vec<bool>),
+    Other(DataType),
+}
+
+impl Kind {
+    fn of(value: &Value, formats: &[String]) -> Kind {
+        match value {
+            Value::Null => Kind::Null,
+            Value::Bool(_) => Kind::Bool,
+            Value::String(s) => Kind::parse(s, formats),
+            Value::Number(n) => match n.inner() {
+                Num::Uint8(n) => Kind::Int(i128::from(*n)),
+                Num::Uint16(n) => Kind::Int(i128::from(*n)),
+                Num::Uint32(n) => Kind::Int(i128::from(*n)),
+                Num::Uint64(n) => Kind::Int(i128::from(*n)),
+                Num::Int8(n) => Kind::Int(i128::from(*n)),
+                Num::Int16(n) => Kind::Int(i128::from(*n)),
+                Num::Int32(n) => Kind::Int(i128::from(*n)),
+                Num::Int64(n) => Kind::Int(i128::from(*n)),
+                Num::Float(_) | Num::Double(_) => Kind::Real(true),
+                Num::Decimal(_) => Kind::Real(false),
+            },
+            v => Kind::Other(v.type_of()),
+        }
+    }
+
+    fn parse(s: &str, formats: &[String]) -> Kind {
+        let s = s.trim();
+
+        if s.is_empty() {
+            return Kind::Null;
+        }
+
+        if s.eq_ignore_ascii_case("true") || s.eq_ignore_ascii_case("false") {
+            return Kind::Bool;
+        }
+
+        if let Ok(n) = i128::from_str(s) {
+            return Kind::Int(n);
+        }
+
+        if let Ok(d) = Decimal::from_str(s) {
+            return Kind::Real(fits_double(&d));
+        }
+
+        if s.parse::<f64>().map(f64::is_finite).unwrap_or(false) {
+            return Kind::Real(true);
+        }
+
+        Kind::Text(formats.iter().map(|f| parse_date(s, f).is_some()).collect())
+    }
+}
+
+/// Whether the decimal survives being stored as a double
+fn fits_double(d: &Decimal) -> bool {
+    d.to_string()
+        .parse::<f64>()
+        .ok()
+        .and_then(|f| Decimal::from_str(&f.to_string()).ok())
+        .map(|back| back.normalize() == d.normalize())
+        .unwrap_or(false)
+}
+
+fn parse_date(s: &str, format: &str) -> Option<Value> {
+    parse_temporal(s, format, &format_type(format)).ok()
+}
+
+/// The narrowest integer type that holds every value between min and max
+pub(crate) fn integer_type(min: i128, max: i128) -> DataType {
+    let fits = |lo: i128, hi: i128| min >= lo && max <= hi;
+
+    if min >= 0 {
+        if fits(0, i128::from(u8::MAX)) {
+            DataType::Uint8
+        } else if fits(0, i128::from(u16::MAX)) {
+            DataType::Uint16
+        } else if fits(0, i128::from(u32::MAX)) {
+            DataType::Uint32
+        } else if fits(0, i128::from(u64::MAX)) {
+            DataType::Uint64
+        } else {
+            DataType::Decimal(None)
+        }
+    } else if fits(i128::from(i8::MIN), i128::from(i8::MAX)) {
+        DataType::Int8
+    } else if fits(i128::from(i16::MIN), i128::from(i16::MAX)) {
+        DataType::Int16
+    } else if fits(i128::from(i32::MIN), i128::from(i32::MAX)) {
+        DataType::Int32
+    } else if fits(i128::from(i64::MIN), i128::from(i64::MAX)) {
+        DataType::Int64
+    } else {
+        DataType::Decimal(None)
+    }
+}
+
+/// Infer the type of a column from its values. Strings are parsed, so a column of strings that
+/// all look like integers is inferred as the narrowest integer type that holds them. Columns of
+/// strings that don't share a type are strings, other mixed columns are any.
+pub fn infer<I>(values: I, options: &Inference) -> Inferred
+where
+    I: IntoIterator,
+    I::Item: Borrow<Value>,
+{
+    let values = values
+        .into_iter()
+        .take(options.sample_size.unwrap_or(usize::MAX));
+
+    let mut nullable = false;
+    let mut empty_strings = false;
+    let mut all_strings = true;
+    let mut bools = 0;
+    let mut ints = 0;
+    let mut reals = 0;
+    let mut exact = true;
+    let mut range: Option<(i128, i128)> = None;
+    let mut texts = 0;
+    let mut dates = vec![true; options.date_formats.len()];
+    let mut others: Option<DataType> = None;
+    let mut count = 0;
+
+    for value in values {
+        let value = value.borrow();
+        let kind = Kind::of(value, &options.date_formats);
+
+        if let Kind::Null = kind {
+            match value {
+                Value::String(_) => empty_strings = true,
+                _ => nullable = true,
+            }
+            continue;
+        }
+
+        count += 1;
+        all_strings &= matches!(value, Value::String(_));
+
+        match kind {
+            Kind::Null => {}
+            Kind::Bool => bools += 1,
+            Kind::Int(n) => {
+                ints += 1;
+                range = Some(match range {
+                    Some((lo, hi)) => (lo.min(n), hi.max(n)),
+                    None => (n, n),
+                });
+            }
+            Kind::Real(fits) => {
+                reals += 1;
+                exact &= fits;
+            }
+            Kind::Text(parsed) => {
+                texts += 1;
+                dates
+                    .iter_mut()
+                    .zip(parsed)
+                    .for_each(|(date, parsed)| *date &= parsed);
+            }
+            Kind::Other(dtype) => {
+                others = Some(match others {
+                    None => dtype,
+                    Some(other) => other.unify(&dtype),
+                })
+            }
+        }
+    }
+
+    let mut date_format = None;
+
+    let dtype = if count == 0 {
+        DataType::Any
+    } else if bools == count {
+        DataType::Bool
+    } else if ints == count {
+        range
+            .map(|(min, max)| integer_type(min, max))
+            .unwrap_or(DataType::Int64)
+    } else if ints + reals == count {
+        if exact {
+            DataType::Double
+        } else {
+            DataType::Decimal(None)
+        }
+    } else if texts == count && all_strings {
+        match dates.iter().position(|parsed| *parsed) {
+            Some(i) => {
+                date_format = Some(options.date_formats[i].clone());
+                format_type(&options.date_formats[i])
+            }
+            None => DataType::String,
+        }
+    } else if all_strings {
+        DataType::String
+    } else if texts == 0 && bools == 0 && ints == 0 && reals == 0 {
+        others.unwrap_or(DataType::Any)
+    } else {
+        DataType::Any
+    };
+
+    // empty strings only count as null in columns that aren't strings
+    if dtype != DataType::String {
+        nullable |= empty_strings;
+    }
+
+    Inferred {
+        dtype,
+        nullable,
+        date_format,
+    }
+}
+
+/// Convert the value into the inferred type
+pub(crate) fn rewrite(value: &Value, inferred: &Inferred) -> Option<Value> {
+    if let Value::String(s) = value {
+        let s = s.trim();
+
+        if s.is_empty() && inferred.dtype != DataType::String {
+            return Some(Value::Null);
+        }
+
+        let num = |num: Num| Some(Value::Number(Number(num)));
+
+        return match &inferred.dtype {
+            DataType::Bool => match s.to_ascii_lowercase().as_str() {
+                "true" => Some(Value::Bool(true)),
+                "false" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            DataType::Uint8 => s.parse().ok().and_then(|n| num(Num::Uint8(n))),
+            DataType::Uint16 => s.parse().ok().and_then(|n| num(Num::Uint16(n))),
+            DataType::Uint32 => s.parse().ok().and_then(|n| num(Num::Uint32(n))),
+            DataType::Uint64 => s.parse().ok().and_then(|n| num(Num::Uint64(n))),
+            DataType::Int8 => s.parse().ok().and_then(|n| num(Num::Int8(n))),
+            DataType::Int16 => s.parse().ok().and_then(|n| num(Num::Int16(n))),
+            DataType::Int32 => s.parse().ok().and_then(|n| num(Num::Int32(n))),
+            DataType::Int64 => s.parse().ok().and_then(|n| num(Num::Int64(n))),
+            DataType::Double => s.parse().ok().and_then(|n| num(Num::Double(n))),
+            DataType::Decimal(_) => Decimal::from_str(s)
+                .or_else(|_| Decimal::from_scientific(s))
+                .ok()
+                .and_then(|n| num(Num::Decimal(n))),
+            dtype if dtype.is_temporal() => {
+                inferred.date_format.as_ref().and_then(|f| parse_date(s, f))
+            }
+            _ => Some(value.clone()),
+        };
+    }
+
+    // values that aren't strings are left as they are, apart from numbers in columns inferred to
+    // be a different numeric type
+    match (value, &inferred.dtype) {
+        (Value::Number(_), dtype) if dtype.is_numeric() && !dtype.matches(value) => {
+            crate::ops::cast::into_number(value.clone(), dtype).ok()
+        }
+        _ => Some(value.clone()),
+    }
+}
+
+/// Infer the types of the dataframe's columns and update its schema. When rewriting, every
+/// column is converted before any data is replaced, so an error leaves the dataframe unchanged.
+pub fn infer_schema(
+    df: &mut DataFrame<'_>,
+    options: &Inference,
+) -> Result<(), crate::error::Error> {
+    let columns = df.columns().into_iter().cloned().collect::<Vec<String>>();
+
+    let mut inferred_columns = vec![];
+
+    for column in columns {
+        let inferred = infer(df.column_values(&column)?, options);
+
+        let rewritten = if options.rewrite {
+            let rewritten = df
+                .column_values(&column)?
+                .enumerate()
+                .map(|(row, value)| {
+                    rewrite(&value, &inferred).ok_or_else(|| Error::RewriteError {
+                        column: column.clone(),
+                        dtype: inferred.dtype.clone(),
+                        value: value.to_string(),
+                        row,
+                    })
+                })
+                .collect::<Result<Vec<Value>, Error>>()
+                .map_err(|e| crate::error::Error::InferError { source: e })?;
+            Some(rewritten)
+        } else {
+            None
+        };
+
+        inferred_columns.push((column, inferred, rewritten));
+    }
+
+    for (column, inferred, rewritten) in inferred_columns {
+        if let Some(rewritten) = rewritten {
+            let mut rewritten = rewritten.into_iter();
+            df.map_column(&column, |value| {
+                if let Some(new_value) = rewritten.next() {
+                    *value = new_value;
+                }
+                Ok(())
+            })?;
+        }
+
+        if let Some(field) = df.schema.get_field_mut(&column) {
+            field.dtype = inferred.dtype;
+            field.nullable = inferred.nullable;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{df, row};
+
+    fn infer_strings(values: &[&str]) -> Inferred {
+        let values = values
+            .iter()
+            .map(|v| Value::from(*v))
+            .collect::<Vec<Value>>();
+        infer(values.iter(), &Inference::default())
+    }
+
+    #[test]
+    fn it_infers_types_from_strings() {
+        assert_eq!(infer_strings(&["true", "FALSE"]).dtype, DataType::Bool);
+        assert_eq!(infer_strings(&["1", "255"]).dtype, DataType::Uint8);
+        assert_eq!(infer_strings(&["1", "256"]).dtype, DataType::Uint16);
+        assert_eq!(infer_strings(&["-1", "127"]).dtype, DataType::Int8);
+        assert_eq!(infer_strings(&["-1", "40000"]).dtype, DataType::Int32);
+        assert_eq!(infer_strings(&["1", "2.5", "1e3"]).dtype, DataType::Double);
+        assert_eq!(
+            infer_strings(&["1.5", "0.1000000000000000055511"]).dtype,
+            DataType::Decimal(None)
+        );
+        assert_eq!(infer_strings(&["1", "x"]).dtype, DataType::String);
+
+        let dates = infer_strings(&["2019-09-05", "2019-09-06 10:00:00"]);
+        assert_eq!(dates.dtype, DataType::String);
+
+        let dates = infer_strings(&["09/05/2019", "12/31/2019"]);
+        assert_eq!(dates.dtype, DataType::Date);
+        assert_eq!(dates.date_format.as_deref(), Some("%m/%d/%Y"));
+
+        let nullable = infer_strings(&["1", "", "2"]);
+        assert_eq!(nullable.dtype, DataType::Uint8);
+        assert!(nullable.nullable);
+
+        // empty strings are values of string columns, but nulls aren't
+        assert!(!infer_strings(&["x", "", "y"]).nullable);
+        let values = [Value::from("x"), Value::Null, Value::from("")];
+        let inferred = infer(values.iter(), &Inference::default());
+        assert_eq!(inferred.dtype, DataType::String);
+        assert!(inferred.nullable);
+    }
+
+    #[test]
+    fn it_falls_back_for_mixed_values() {
+        let values = [Value::from(true), Value::from(vec![1, 2])];
+        assert_eq!(
+            infer(values.iter(), &Inference::default()).dtype,
+            DataType::Any
+        );
+
+        let values = [
+            Value::Null,
+            Value::from(vec![1]),
+            Value::from(Vec::<i32>::new()),
+        ];
+        let inferred = infer(values.iter(), &Inference::default());
+        assert_eq!(inferred.dtype, DataType::array(DataType::Int32));
+        assert!(inferred.nullable);
+
+        let values = [Value::from("1"), Value::from("x")];
+        let inferred = infer(
+            values.iter(),
+            &Inference::default().with_sample_size(Some(1)),
+        );
+        assert_eq!(inferred.dtype, DataType::Uint8);
+    }
+
+    #[test]
+    fn it_rewrites_data() {
+        let mut df = df!(
+            ["a", "b", "c", "d"],
+            [
+                row!["1", "true", "2019-09-05", "x"],
+                row!["", "false", "2019-09-06", "y"]
+            ]
+        );
+
+        assert!(infer_schema(&mut df, &Inference::default().with_rewrite(true)).is_ok());
+
+        let dtypes = (0..4)
+            .map(|i| df.schema()[i].dtype().clone())
+            .collect::<Vec<DataType>>();
+        assert_eq!(
+            dtypes,
+            vec![
+                DataType::Uint8,
+                DataType::Bool,
+                DataType::Date,
+                DataType::String
+            ]
+        );
+        assert!(df.schema()[0].nullable);
+
+        let date = chrono::NaiveDate::from_ymd_opt(2019, 9, 5).unwrap();
+        assert_eq!(
+            df.row(0).unwrap(),
+            &[
+                Value::from(1u8),
+                Value::Bool(true),
+                Value::Date(date),
+                Value::from("x")
+            ][..]
+        );
+        assert_eq!(df.row(1).unwrap()[0], Value::Null);
+    }
+
+    #[test]
+    fn it_reports_values_that_cant_be_rewritten() {
+        let mut df = df!(["a"], [row!["1"], row!["2"], row!["x"]]);
+        let options = Inference::default()
+            .with_sample_size(Some(2))
+            .with_rewrite(true);
+
+        assert!(matches!(
+            infer_schema(&mut df, &options),
+            Err(crate::error::Error::InferError {
+                source: Error::RewriteError { row: 2, .. }
+            })
+        ));
+
+        // nothing is changed when a value can't be converted
+        assert_eq!(df.row(0).unwrap(), &row!["1"][..]);
+        assert_eq!(df.schema()[0].dtype(), &DataType::Any);
+    }
+}
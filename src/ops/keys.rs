@@ -0,0 +1,411 @@
+use crate::{
+    error::{Error, Result},
+    ops::cast,
+    value::number::Num,
+    views::SubView,
+    DataFrame, DataType, Schema, Value,
+};
+
+use ordered_float::OrderedFloat;
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+/// Maps the values of each of the schema's keys to the row they are in. The index is kept up to
+/// date as rows are pushed, and rebuilt when the dataframe's columns have changed in other ways.
+#[derive(Debug, Clone, Default)]
+pub struct KeyIndex {
+    /// The index of each key, the primary key first
+    maps: Vec<HashMap<Vec<KeyValue>, usize>>,
+    /// The generation of the dataframe's columns the index is of, None before it's built
+    generation: Option<u64>,
+}
+
+impl KeyIndex {
+    fn is_current(&self, df: &DataFrame) -> bool {
+        self.generation == Some(df.generation)
+    }
+}
+
+/// A value of a key. Floats are compared by value with NaNs equal to each other, so that they can
+/// be hashed, and the same comparison is used when the index is stale and the rows are scanned.
+#[derive(Debug, Clone)]
+pub(crate) struct KeyValue(pub(crate) Value);
+
+impl PartialEq for KeyValue {
+    fn eq(&self, other: &KeyValue) -> bool {
+        key_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for KeyValue {}
+
+impl Hash for KeyValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_value(&self.0, state);
+    }
+}
+
+fn key_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => match (a.inner(), b.inner()) {
+            (Num::Float(a), Num::Float(b)) => OrderedFloat(*a) == OrderedFloat(*b),
+            (Num::Double(a), Num::Double(b)) => OrderedFloat(*a) == OrderedFloat(*b),
+            (a, b) => a == b,
+        },
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| key_eq(a, b))
+        }
+        (Value::Map(a), Value::Map(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b.iter())
+                    .all(|((ka, va), (kb, vb))| ka == kb && key_eq(va, vb))
+        }
+        (a, b) => a == b,
+    }
+}
+
+fn hash_value<H: Hasher>(value: &Value, state: &mut H) {
+    std::mem::discriminant(value).hash(state);
+
+    match value {
+        Value::Null => {}
+        Value::Bool(b) => b.hash(state),
+        Value::String(s) => s.hash(state),
+        Value::Number(n) => {
+            std::mem::discriminant(n.inner()).hash(state);
+            match n.inner() {
+                Num::Uint8(n) => n.hash(state),
+                Num::Uint16(n) => n.hash(state),
+                Num::Uint32(n) => n.hash(state),
+                Num::Uint64(n) => n.hash(state),
+                Num::Int8(n) => n.hash(state),
+                Num::Int16(n) => n.hash(state),
+                Num::Int32(n) => n.hash(state),
+                Num::Int64(n) => n.hash(state),
+                Num::Float(n) => OrderedFloat(*n).hash(state),
+                Num::Double(n) => OrderedFloat(*n).hash(state),
+                Num::Decimal(n) => n.hash(state),
+            }
+        }
+        Value::Date(d) => d.hash(state),
+        Value::Time(t) => t.hash(state),
+        Value::Timestamp(d) => d.hash(state),
+        Value::TimestampTz(d) => d.hash(state),
+        Value::Binary(b) => b.hash(state),
+        Value::Array(a) => {
+            a.len().hash(state);
+            a.iter().for_each(|v| hash_value(v, state));
+        }
+        Value::Map(m) => {
+            m.len().hash(state);
+            for (k, v) in m.iter() {
+                k.hash(state);
+                hash_value(v, state);
+            }
+        }
+    }
+}
+
+/// A key's columns, their types and whether it's the primary key
+struct Key {
+    names: Vec<String>,
+    columns: Vec<usize>,
+    dtypes: Vec<DataType>,
+    primary: bool,
+}
+
+impl Key {
+    /// The key's values in the row, None when any of them is null. The values are cast to their
+    /// field's type the way the columns store them, values that can't be cast are kept as they
+    /// are and aren't the same key as values of another type.
+    fn of(&self, row: &[Value]) -> Option<Vec<KeyValue>> {
+        self.values(self.columns.iter().map(|i| &row[*i]))
+    }
+
+    /// The key of the values of the key's columns, see of
+    fn values<V, I>(&self, values: I) -> Option<Vec<KeyValue>>
+    where
+        V: Borrow<Value>,
+        I: IntoIterator<Item = V>,
+    {
+        values
+            .into_iter()
+            .zip(&self.dtypes)
+            .map(|(value, dtype)| match value.borrow() {
+                Value::Null => None,
+                value => Some(KeyValue(
+                    cast::try_cast(value.clone(), dtype).unwrap_or_else(|_| value.clone()),
+                )),
+            })
+            .collect()
+    }
+}
+
+fn keys(schema: &Schema) -> Result<Vec<Key>> {
+    let primary = schema.primary_key().map(|key| (key, true));
+    let unique = schema
+        .unique_keys()
+        .iter()
+        .map(|key| (key.as_slice(), false));
+
+    primary
+        .into_iter()
+        .chain(unique)
+        .map(|(names, primary)| {
+            let columns = names
+                .iter()
+                .map(|name| {
+                    schema
+                        .find_index(name)
+                        .copied()
+                        .ok_or_else(|| Error::InvalidColumnName {
+                            column: name.clone(),
+                        })
+                })
+                .collect::<Result<Vec<usize>>>()?;
+
+            let dtypes = columns
+                .iter()
+                .map(|i| schema.fields()[*i].dtype().clone())
+                .collect();
+
+            Ok(Key {
+                names: names.to_vec(),
+                columns,
+                dtypes,
+                primary,
+            })
+        })
+        .collect()
+}
+
+/// Rebuild the index from the dataframe's rows, the first of rows with the same key is indexed
+fn rebuild(df: &mut DataFrame) -> Result<()> {
+    let keys = keys(&df.schema)?;
+    let mut maps = vec![HashMap::new(); keys.len()];
+
+    for (i, row) in (0..df.dim.1).filter_map(|i| df.row_values(i)).enumerate() {
+        for (key, map) in keys.iter().zip(maps.iter_mut()) {
+            if let Some(value) = key.of(&row) {
+                map.entry(value).or_insert(i);
+            }
+        }
+    }
+
+    df.keys.maps = maps;
+    df.keys.generation = Some(df.generation);
+
+    Ok(())
+}
+
+/// Check the rows against the schema's keys, then push them onto the dataframe and add them to
+/// the index. Errors without changing the dataframe if a row has a null in the primary key, or
+/// has the same key as another row.
+pub fn insert(df: &mut DataFrame, rows: Vec<Vec<Value>>) -> Result<()> {
+    if df.schema.primary_key().is_none() && df.schema.unique_keys().is_empty() {
+        df.extend_unchecked(rows);
+        return Ok(());
+    }
+
+    if !df.keys.is_current(df) {
+        rebuild(df)?;
+    }
+
+    let keys = keys(&df.schema)?;
+    let mut values = vec![vec![]; keys.len()];
+
+    for (key, (map, values)) in keys.iter().zip(df.keys.maps.iter().zip(values.iter_mut())) {
+        let mut seen = HashSet::new();
+
+        for row in &rows {
+            let value = match key.of(row) {
+                Some(value) => value,
+                None if key.primary => {
+                    return Err(Error::NullKey {
+                        key: key.names.join(", "),
+                    })
+                }
+                None => {
+                    values.push(None);
+                    continue;
+                }
+            };
+
+            if map.contains_key(&value) || !seen.insert(value.clone()) {
+                return Err(Error::DuplicateKey {
+                    key: key.names.join(", "),
+                    value: display(&key.columns, row),
+                });
+            }
+
+            values.push(Some(value));
+        }
+    }
+
+    let first = df.dim.1;
+    df.extend_unchecked(rows);
+
+    for (map, values) in df.keys.maps.iter_mut().zip(values) {
+        for (i, value) in values.into_iter().enumerate() {
+            if let Some(value) = value {
+                map.insert(value, first + i);
+            }
+        }
+    }
+    df.keys.generation = Some(df.generation);
+
+    Ok(())
+}
+
+/// Find the row with the primary key's values, using the index when it is up to date
+pub fn find<'b>(df: &'b DataFrame, values: &[Value]) -> Option<SubView<'b>> {
+    let key = keys(&df.schema).ok()?.into_iter().find(|key| key.primary)?;
+
+    if values.len() != key.columns.len() {
+        return None;
+    }
+
+    // primary keys can't be null, so neither can the values of a row's key
+    let value = key.values(values)?;
+
+    let matches = |row: usize| {
+        key.values(
+            key.columns
+                .iter()
+                .map(|i| df.columns[*i].get(row).unwrap_or(Value::Null)),
+        )
+        .is_some_and(|row| row == value)
+    };
+
+    if df.keys.is_current(df) {
+        return df.keys.maps[0]
+            .get(&value)
+            .filter(|row| matches(**row))
+            .and_then(|row| df.row(*row));
+    }
+
+    (0..df.dim.1)
+        .find(|row| matches(*row))
+        .and_then(|row| df.row(row))
+}
+
+fn display(columns: &[usize], row: &[Value]) -> String {
+    columns
+        .iter()
+        .map(|i| row[*i].to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{row, DataFrame, DataType, Field, Schema, Value};
+
+    fn schema() -> Schema {
+        Schema::with_fields(vec![
+            Field::with_type("id", DataType::Int64),
+            Field::with_type("email", DataType::String),
+            Field::with_type("name", DataType::String),
+        ])
+        .unwrap()
+        .with_primary_key(&["id"])
+        .with_unique_key(&["email"])
+    }
+
+    #[test]
+    fn it_enforces_keys() {
+        let mut df = DataFrame::with_schema(schema());
+        assert!(df.push_row(row![1i64, "a@x.com", "a"]).is_ok());
+        assert!(df.push_row(row![2i64, Value::Null, "b"]).is_ok());
+        assert!(df.push_row(row![3i64, Value::Null, "c"]).is_ok());
+
+        assert!(df.push_row(row![1i64, "z@x.com", "z"]).is_err());
+        assert!(df.push_row(row![4i64, "a@x.com", "z"]).is_err());
+        assert!(df.push_row(row![Value::Null, "z@x.com", "z"]).is_err());
+
+        // the batch is rejected as a whole, including duplicates within it
+        assert!(df
+            .extend(vec![row![4i64, "d@x.com", "d"], row![4i64, "e@x.com", "e"]])
+            .is_err());
+        assert_eq!(df.size(), 3);
+
+        assert!(df
+            .extend(vec![row![4i64, "d@x.com", "d"], row![5i64, "e@x.com", "e"]])
+            .is_ok());
+        assert_eq!(df.size(), 5);
+    }
+
+    #[test]
+    fn it_finds_rows_by_key() {
+        let mut df = DataFrame::with_schema(schema());
+        df.extend(vec![row![1i64, "a@x.com", "a"], row![2i64, "b@x.com", "b"]])
+            .unwrap();
+
+        assert_eq!(df.find_by_key(&[Value::from(2i64)]).unwrap()[2], "b".into());
+        assert!(df.find_by_key(&[Value::from(3i64)]).is_none());
+
+        // rows pushed without checks make the index stale, and the lookup falls back to a scan
+        df.push_row_unchecked(row![3i64, "c@x.com", "c"]);
+        assert_eq!(df.find_by_key(&[Value::from(3i64)]).unwrap()[2], "c".into());
+
+        df.map_column("name", |v| {
+            *v = Value::from("x");
+            Ok(())
+        })
+        .unwrap();
+        assert!(df.push_row(row![4i64, "d@x.com", "d"]).is_ok());
+        assert_eq!(df.find_by_key(&[Value::from(1i64)]).unwrap()[2], "x".into());
+
+        // changing the keys' values without changing the number of rows makes the index stale
+        df.map_column("id", |v| {
+            *v = Value::from(v.to_string().parse::<i64>().unwrap() * 10);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(
+            df.find_by_key(&[Value::from(20i64)]).unwrap()[1],
+            "b@x.com".into()
+        );
+        assert!(df.push_row(row![1i64, "f@x.com", "f"]).is_ok());
+        assert!(df.push_row(row![30i64, "g@x.com", "g"]).is_err());
+    }
+
+    #[test]
+    fn it_compares_float_keys_by_value() {
+        let schema = Schema::with_fields(vec![Field::with_type("x", DataType::Double)])
+            .unwrap()
+            .with_primary_key(&["x"]);
+        let mut df = DataFrame::with_schema(schema);
+
+        assert!(df.push_row(row![0.0]).is_ok());
+        assert!(df.push_row(row![-0.0]).is_err());
+        assert!(df.push_row(row![f64::NAN]).is_ok());
+        assert!(df.push_row(row![f64::NAN]).is_err());
+
+        // the same rows are found whether the index is current or stale
+        for _ in 0..2 {
+            assert!(df.find_by_key(&[Value::from(-0.0)]).is_some());
+            assert!(df.find_by_key(&[Value::from(f64::NAN)]).is_some());
+            df.push_row_unchecked(row![1.0]);
+        }
+    }
+
+    #[test]
+    fn it_casts_keys_to_the_fields_types() {
+        let mut df = DataFrame::with_schema(schema());
+        assert!(df.push_row(row![1i64, "a@x.com", "a"]).is_ok());
+        assert!(df.push_row(row![1i32, "b@x.com", "b"]).is_err());
+        assert!(df
+            .extend(vec![row![2i32, "c@x.com", "c"], row![2i64, "d@x.com", "d"]])
+            .is_err());
+        assert!(df.push_row(row![2i32, "c@x.com", "c"]).is_ok());
+
+        for _ in 0..2 {
+            assert_eq!(df.find_by_key(&[Value::from(1i32)]).unwrap()[2], "a".into());
+            assert_eq!(df.find_by_key(&[Value::from(2i64)]).unwrap()[2], "c".into());
+            df.push_row_unchecked(row![3i64, "e@x.com", "e"]);
+        }
+    }
+}
@@ -1,5 +1,10 @@
 pub mod cast;
+pub mod coerce;
 pub mod convert;
+pub mod evolve;
+pub mod infer;
+pub mod keys;
+pub mod python;
 pub mod select;
 // pub mod filter;
 // pub mod reverse;
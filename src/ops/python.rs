@@ -0,0 +1,538 @@
+use crate::ops::coerce::{self, Coerced};
+use crate::{python::value, DataFrame, Field, Value};
+
+use cpython::{CompareOp, ObjectProtocol, PyDict, PyErr, PyObject, PyString, Python};
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to load the python function {}: {}", function, message))]
+    LoadError { function: String, message: String },
+
+    #[snafu(display("The python function {} failed on row {}: {}", function, row, message))]
+    RowError {
+        function: String,
+        row: usize,
+        message: String,
+    },
+
+    #[snafu(display(
+        "The python function {} failed on column {}: {}",
+        function,
+        column,
+        message
+    ))]
+    ColumnError {
+        function: String,
+        column: String,
+        message: String,
+    },
+}
+
+/// A python function, either defined by its source code or imported from a module
+#[derive(Debug, Clone, PartialEq)]
+pub enum Function {
+    /// Source code that defines a function with the name
+    Source { code: String, name: String },
+    /// A function with the name in an importable module, e.x. `("transforms.clean", "strip")`
+    Module { module: String, name: String },
+}
+
+impl Function {
+    pub fn from_source(code: &str, name: &str) -> Function {
+        Function::Source {
+            code: code.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    pub fn from_module(module: &str, name: &str) -> Function {
+        Function::Module {
+            module: module.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Function::Source { name, .. } | Function::Module { name, .. } => name,
+        }
+    }
+
+    /// A version of the function's definition that changes when the function does. Source code
+    /// is its own version, a module's version is its `__version__` and the contents of its file.
+    /// None when the module can't be imported or has neither.
+    pub fn version(&self) -> Option<String> {
+        match self {
+            Function::Source { code, .. } => Some(code.clone()),
+            Function::Module { module, .. } => {
+                let gil = Python::acquire_gil();
+                let py = gil.python();
+                let module = py.import(module).ok()?;
+
+                let attr = |name: &str| {
+                    module
+                        .get(py, name)
+                        .ok()
+                        .and_then(|obj| obj.extract::<String>(py).ok())
+                };
+                let version = attr("__version__");
+                let source = attr("__file__").and_then(|path| std::fs::read_to_string(path).ok());
+
+                match (version, source) {
+                    (None, None) => None,
+                    (version, source) => Some(format!(
+                        "{}\n{}",
+                        version.unwrap_or_default(),
+                        source.unwrap_or_default()
+                    )),
+                }
+            }
+        }
+    }
+
+    fn load(&self, py: Python) -> Result<PyObject, Error> {
+        let function = match self {
+            Function::Source { code, name } => {
+                let globals = PyDict::new(py);
+                py.run(code, Some(&globals), None)
+                    .and_then(|_| {
+                        globals.get_item(py, name).ok_or_else(|| {
+                            PyErr::new::<cpython::exc::NameError, _>(
+                                py,
+                                format!("the source does not define {}", name),
+                            )
+                        })
+                    })
+                    .map_err(|e| self.load_error(py, e))?
+            }
+            Function::Module { module, name } => py
+                .import(module)
+                .and_then(|m| m.get(py, name))
+                .map_err(|e| self.load_error(py, e))?,
+        };
+
+        if !function.is_callable(py) {
+            return Err(Error::LoadError {
+                function: self.name().to_string(),
+                message: "the object is not callable".to_string(),
+            });
+        }
+
+        Ok(function)
+    }
+
+    fn load_error(&self, py: Python, err: PyErr) -> Error {
+        Error::LoadError {
+            function: self.name().to_string(),
+            message: describe(py, err),
+        }
+    }
+}
+
+/// A transform implemented by a python function
+#[derive(Debug, Clone, PartialEq)]
+pub enum PyTransform {
+    /// Call the function with each row as a dict keyed by column name. The function returns a
+    /// dict with the new values of the columns it changes, or None to keep the changes it made to
+    /// the dict it was passed.
+    Rows(Function),
+    /// Call the function once with the column's values as a list, it returns the new values
+    Column(String, Function),
+}
+
+impl PyTransform {
+    pub fn function(&self) -> &Function {
+        match self {
+            PyTransform::Rows(function) | PyTransform::Column(_, function) => function,
+        }
+    }
+}
+
+pub fn apply(df: &mut DataFrame<'_>, transform: &PyTransform) -> Result<(), crate::error::Error> {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let function = transform.function();
+    let callable = function
+        .load(py)
+        .map_err(|e| crate::error::Error::PythonError {
+            source: Box::new(e),
+        })?;
+
+    match transform {
+        PyTransform::Rows(_) => apply_rows(py, df, function, &callable),
+        PyTransform::Column(column, _) => apply_column(py, df, column, function, &callable),
+    }
+    .map_err(|e| crate::error::Error::PythonError {
+        source: Box::new(e),
+    })
+}
+
+fn apply_rows(
+    py: Python,
+    df: &mut DataFrame<'_>,
+    function: &Function,
+    callable: &PyObject,
+) -> Result<(), Error> {
+    let columns = df
+        .columns()
+        .into_iter()
+        .map(|c| PyString::new(py, c))
+        .collect::<Vec<PyString>>();
+
+    let fields = df.schema().fields().to_vec();
+    let num_columns = columns.len();
+
+    if num_columns == 0 {
+        return Ok(());
+    }
+
+    // the rows are written back once every row succeeded, so a failure leaves the dataframe as it
+    // was
+    let mut data = (0..df.size())
+        .filter_map(|row| df.row_values(row))
+        .collect::<Vec<Vec<Value>>>();
+
+    for (row, values) in data.iter_mut().enumerate() {
+        let row_error = |message: String| Error::RowError {
+            function: function.name().to_string(),
+            row,
+            message,
+        };
+
+        let dict = PyDict::new(py);
+        for (column, value) in columns.iter().zip(values.iter()) {
+            let obj = value::to_py(py, value).map_err(|e| row_error(describe(py, e)))?;
+            dict.set_item(py, column, obj)
+                .map_err(|e| row_error(describe(py, e)))?;
+        }
+
+        let result = callable
+            .call(py, (&dict,), None)
+            .map_err(|e| row_error(describe(py, e)))?;
+
+        let result = if result == py.None() {
+            dict
+        } else {
+            result
+                .cast_into::<PyDict>(py)
+                .map_err(|_| row_error("the function must return a dict or None".to_string()))?
+        };
+
+        for (key, obj) in result.items(py) {
+            let key = key
+                .extract::<String>(py)
+                .map_err(|e| row_error(describe(py, e)))?;
+
+            let index = columns
+                .iter()
+                .position(|c| c.to_string_lossy(py) == key)
+                .ok_or_else(|| row_error(format!("{} is not a column", key)))?;
+
+            // a value the function left alone keeps its original type instead of round tripping
+            // through python
+            let original =
+                value::to_py(py, &values[index]).map_err(|e| row_error(describe(py, e)))?;
+            if unchanged(py, &obj, &original) {
+                continue;
+            }
+
+            let value = value::from_py(py, &obj).map_err(|e| row_error(describe(py, e)))?;
+            values[index] =
+                coerce_result(value, &fields[index], row).map_err(|e| row_error(e.to_string()))?;
+        }
+    }
+
+    df.set_rows(data);
+
+    Ok(())
+}
+
+fn unchanged(py: Python, obj: &PyObject, original: &PyObject) -> bool {
+    obj.get_type(py) == original.get_type(py)
+        && obj
+            .rich_compare(py, original, CompareOp::Eq)
+            .and_then(|eq| eq.is_true(py))
+            .unwrap_or(false)
+}
+
+fn apply_column(
+    py: Python,
+    df: &mut DataFrame<'_>,
+    column: &str,
+    function: &Function,
+    callable: &PyObject,
+) -> Result<(), Error> {
+    let column_error = |message: String| Error::ColumnError {
+        function: function.name().to_string(),
+        column: column.to_string(),
+        message,
+    };
+
+    let field = df
+        .schema()
+        .get_field(column)
+        .cloned()
+        .ok_or_else(|| column_error("the column doesn't exist".to_string()))?;
+
+    let values = df
+        .column_values(column)
+        .map_err(|e| column_error(e.to_string()))?
+        .map(|v| value::to_py(py, &v))
+        .collect::<Result<Vec<PyObject>, PyErr>>()
+        .map_err(|e| column_error(describe(py, e)))?;
+
+    let expected = values.len();
+    let list = cpython::PyList::new(py, &values);
+
+    let result = callable
+        .call(py, (list,), None)
+        .and_then(|result| {
+            result
+                .iter(py)?
+                .map(|obj| obj.and_then(|obj| value::from_py(py, &obj)))
+                .collect::<Result<Vec<Value>, PyErr>>()
+        })
+        .map_err(|e| column_error(describe(py, e)))?;
+
+    if result.len() != expected {
+        return Err(column_error(format!(
+            "the function returned {} values for {} rows",
+            result.len(),
+            expected
+        )));
+    }
+
+    let mut result = result
+        .into_iter()
+        .enumerate()
+        .map(|(row, value)| coerce_result(value, &field, row))
+        .collect::<Result<Vec<Value>, coerce::Error>>()
+        .map_err(|e| column_error(e.to_string()))?
+        .into_iter();
+
+    df.map_column(column, |value| {
+        if let Some(new_value) = result.next() {
+            *value = new_value;
+        }
+        Ok(())
+    })
+    .map_err(|e| column_error(e.to_string()))
+}
+
+/// Coerce a value returned by the function into the field's type, so the schema still describes
+/// the column
+fn coerce_result(value: Value, field: &Field, row: usize) -> Result<Value, coerce::Error> {
+    Ok(match coerce::coerce_value(&value, field, row)? {
+        Coerced::Unchanged => value,
+        Coerced::Cast(value) | Coerced::Defaulted(value) => value,
+    })
+}
+
+/// Format the exception as `<type>: <message>`
+fn describe(py: Python, mut err: PyErr) -> String {
+    let instance = err.instance(py);
+    let name = instance.get_type(py).name(py).into_owned();
+
+    match instance.str(py) {
+        Ok(message) if message.to_string_lossy(py).is_empty() => name,
+        Ok(message) => format!("{}: {}", name, message.to_string_lossy(py)),
+        Err(_) => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::TypeOf;
+    use crate::{df, row, DataType, Schema};
+
+    fn apply_err(df: &mut DataFrame, transform: &PyTransform) -> Error {
+        match apply(df, transform) {
+            Err(crate::error::Error::PythonError { source }) => *source,
+            other => panic!("expected a python error, got {:?}", other),
+        }
+    }
+
+    fn frame() -> DataFrame<'static> {
+        df!(
+            ["name", "score"],
+            [row!["a", 1], row!["b", 2], row!["c", 3]]
+        )
+    }
+
+    #[test]
+    fn it_applies_rows() {
+        let mut df = frame();
+
+        let code = r#"
+def transform(row):
+    return {"name": row["name"].upper(), "score": row["score"] * 10}
+
+def in_place(row):
+    row["score"] += 1
+"#;
+
+        assert!(apply(
+            &mut df,
+            &PyTransform::Rows(Function::from_source(code, "transform"))
+        )
+        .is_ok());
+        assert!(apply(
+            &mut df,
+            &PyTransform::Rows(Function::from_source(code, "in_place"))
+        )
+        .is_ok());
+
+        assert_eq!(df.row(0).unwrap(), &row!["A", 11i64][..]);
+        assert_eq!(df.row(2).unwrap(), &row!["C", 31i64][..]);
+    }
+
+    #[test]
+    fn it_applies_column() {
+        let mut df = frame();
+
+        let transform = PyTransform::Column(
+            "score".to_string(),
+            Function::from_source("def f(xs):\n    return [x * x for x in xs]", "f"),
+        );
+        assert!(apply(&mut df, &transform).is_ok());
+        assert_eq!(
+            df.column_values("score").unwrap().collect::<Vec<Value>>(),
+            vec![Value::from(1i64), Value::from(4i64), Value::from(9i64)]
+        );
+
+        let transform = PyTransform::Column(
+            "score".to_string(),
+            Function::from_module("builtins", "reversed"),
+        );
+        assert!(apply(&mut df, &transform).is_ok());
+        assert_eq!(df.row(0).unwrap(), &row!["a", 9i64][..]);
+
+        let transform = PyTransform::Column(
+            "score".to_string(),
+            Function::from_source("def f(xs):\n    return xs[1:]", "f"),
+        );
+        assert!(matches!(
+            apply_err(&mut df, &transform),
+            Error::ColumnError { .. }
+        ));
+    }
+
+    #[test]
+    fn it_keeps_the_types_of_untouched_values() {
+        let mut df = DataFrame::with_data(
+            Schema::with_fields(vec![
+                Field::with_type("name", DataType::String),
+                Field::with_type("value", DataType::Any),
+            ])
+            .unwrap(),
+            vec![
+                vec![Value::from("a"), Value::from(1i32)],
+                vec![Value::from("b"), Value::from(1.5f32)],
+            ],
+        );
+
+        let code = r#"
+def partial(row):
+    return {"name": row["name"].upper()}
+
+def in_place(row):
+    row["name"] = row["name"].lower()
+"#;
+
+        for function in &["partial", "in_place"] {
+            assert!(apply(
+                &mut df,
+                &PyTransform::Rows(Function::from_source(code, function))
+            )
+            .is_ok());
+        }
+
+        assert_eq!(df.row(0).unwrap()[1].type_of(), DataType::Int32);
+        assert_eq!(df.row(1).unwrap()[1].type_of(), DataType::Float);
+        assert_eq!(df.row(1).unwrap(), &row!["b", 1.5f32][..]);
+    }
+
+    #[test]
+    fn it_coerces_results_into_the_fields() {
+        let mut df = DataFrame::with_data(
+            Schema::with_fields(vec![
+                Field::with_type("name", DataType::String),
+                Field::with_type("score", DataType::Int64),
+            ])
+            .unwrap(),
+            vec![row!["a", 1i64], row!["b", 2i64]],
+        );
+
+        let code = "def f(row):\n    return {\"score\": str(row[\"score\"] + 1)}";
+        assert!(apply(
+            &mut df,
+            &PyTransform::Rows(Function::from_source(code, "f"))
+        )
+        .is_ok());
+        assert_eq!(df.row(0).unwrap(), &row!["a", 2i64][..]);
+
+        let transform = PyTransform::Column(
+            "score".to_string(),
+            Function::from_source("def f(xs):\n    return [str(x) for x in xs]", "f"),
+        );
+        assert!(apply(&mut df, &transform).is_ok());
+        assert_eq!(df.row(1).unwrap(), &row!["b", 3i64][..]);
+
+        let transform = PyTransform::Column(
+            "score".to_string(),
+            Function::from_source("def f(xs):\n    return [\"x\" for x in xs]", "f"),
+        );
+        assert!(matches!(
+            apply_err(&mut df, &transform),
+            Error::ColumnError { .. }
+        ));
+        assert_eq!(df.row(1).unwrap(), &row!["b", 3i64][..]);
+
+        let code = "def f(row):\n    return {\"name\": 1.5}";
+        assert!(matches!(
+            apply_err(
+                &mut df,
+                &PyTransform::Rows(Function::from_source(code, "f"))
+            ),
+            Error::RowError { row: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn it_reports_the_failing_row() {
+        let mut df = frame();
+
+        let code = r#"
+def transform(row):
+    if row["name"] == "b":
+        raise ValueError("bad row")
+    return {"score": 0}
+"#;
+
+        match apply_err(
+            &mut df,
+            &PyTransform::Rows(Function::from_source(code, "transform")),
+        ) {
+            Error::RowError { row, message, .. } => {
+                assert_eq!(row, 1);
+                assert_eq!(message, "ValueError: bad row");
+            }
+            other => panic!("expected a row error, got {:?}", other),
+        }
+
+        let missing = Function::from_source("x = 1", "transform");
+        assert!(matches!(
+            apply_err(&mut df, &PyTransform::Rows(missing)),
+            Error::LoadError { .. }
+        ));
+
+        let unknown = Function::from_source("def f(row):\n    return {\"z\": 1}", "f");
+        assert!(matches!(
+            apply_err(&mut df, &PyTransform::Rows(unknown)),
+            Error::RowError { row: 0, .. }
+        ));
+    }
+}
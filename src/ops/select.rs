@@ -1,16 +1,18 @@
 use crate::{
-    dim::Dim,
+    column::Column,
     schema::{Field, Schema},
-    DataFrame, Value,
+    DataFrame,
 };
 
-use snafu::Snafu;
-use std::borrow::Cow;
+use snafu::{ResultExt, Snafu};
 
 #[derive(Debug, Snafu)]
 pub enum Error {
     #[snafu(display("A column with the name {} does not exist", name))]
     InvalidColumnName { name: String },
+
+    #[snafu(display("The selected columns are invalid: {}", source))]
+    SchemaError { source: crate::schema::Error },
 }
 
 pub enum Select<'a> {
@@ -68,35 +70,23 @@ pub fn select<'a>(df: &DataFrame<'a>, select: &[Select]) -> Result<DataFrame<'a>
     let fields = columns
         .into_iter()
         .map(|(pos, field)| {
-            schema.push_field(field);
-            pos
+            schema.push_field(field).context(SchemaError)?;
+            Ok(pos)
         })
-        .collect::<Vec<&usize>>();
-
-    let next_size = fields.len() * df.dim.1;
-    let mut data: Vec<Value> = Vec::with_capacity(next_size);
-
-    for i in 0..df.dim.1 {
-        let offset = df.dim.0 * i;
-        fields.iter().for_each(|col_index| {
-            data.push(df.data[*col_index + offset].clone());
-        });
-    }
+        .collect::<Result<Vec<&usize>, Error>>()?;
 
-    let dim = Dim::new(schema.len(), df.dim.1);
+    let columns = fields
+        .into_iter()
+        .map(|col_index| df.columns[*col_index].clone())
+        .collect::<Vec<Column>>();
 
-    Ok(DataFrame {
-        schema,
-        dim,
-        data: Cow::from(data),
-    })
+    Ok(DataFrame::from_columns(schema, columns))
 }
 
 #[cfg(test)]
 mod test_select {
     use super::*;
-    #[macro_use]
-    use crate::{Value, val, row};
+    use crate::row;
 
     #[test]
     fn it_selects_columns_by_name() {
@@ -113,10 +103,10 @@ mod test_select {
 
         assert_eq!(out_df.dim.shape(), (2, 4));
 
-        assert_eq!(out_df[0].to_vec(), row![1, 2]);
-        assert_eq!(out_df[1].to_vec(), row![4, 5]);
-        assert_eq!(out_df[2].to_vec(), row![7, 8]);
-        assert_eq!(out_df[3].to_vec(), row![10, 11]);
+        assert_eq!(out_df.row(0).unwrap().data().to_vec(), row![1, 2]);
+        assert_eq!(out_df.row(1).unwrap().data().to_vec(), row![4, 5]);
+        assert_eq!(out_df.row(2).unwrap().data().to_vec(), row![7, 8]);
+        assert_eq!(out_df.row(3).unwrap().data().to_vec(), row![10, 11]);
 
         assert_eq!(
             out_df.schema.field_names(),
@@ -139,10 +129,22 @@ mod test_select {
 
         assert_eq!(out_df.dim.shape(), (2, 4));
 
-        assert_eq!(out_df[0].to_vec(), vec![2.into(), 0.into()]);
-        assert_eq!(out_df[1].to_vec(), vec![5.into(), 3.into()]);
-        assert_eq!(out_df[2].to_vec(), vec![8.into(), 6.into()]);
-        assert_eq!(out_df[3].to_vec(), vec![11.into(), 9.into()]);
+        assert_eq!(
+            out_df.row(0).unwrap().data().to_vec(),
+            vec![2.into(), 0.into()]
+        );
+        assert_eq!(
+            out_df.row(1).unwrap().data().to_vec(),
+            vec![5.into(), 3.into()]
+        );
+        assert_eq!(
+            out_df.row(2).unwrap().data().to_vec(),
+            vec![8.into(), 6.into()]
+        );
+        assert_eq!(
+            out_df.row(3).unwrap().data().to_vec(),
+            vec![11.into(), 9.into()]
+        );
 
         assert_eq!(
             out_df.schema.field_names(),
@@ -165,10 +167,10 @@ mod test_select {
 
         assert_eq!(out_df.dim.shape(), (1, 4));
 
-        assert_eq!(out_df[0].to_vec(), vec![0.into()]);
-        assert_eq!(out_df[1].to_vec(), vec![3.into()]);
-        assert_eq!(out_df[2].to_vec(), vec![6.into()]);
-        assert_eq!(out_df[3].to_vec(), vec![9.into()]);
+        assert_eq!(out_df.row(0).unwrap().data().to_vec(), vec![0.into()]);
+        assert_eq!(out_df.row(1).unwrap().data().to_vec(), vec![3.into()]);
+        assert_eq!(out_df.row(2).unwrap().data().to_vec(), vec![6.into()]);
+        assert_eq!(out_df.row(3).unwrap().data().to_vec(), vec![9.into()]);
 
         assert_eq!(out_df.schema.field_names(), vec![&"z".to_string()]);
     }
@@ -0,0 +1,290 @@
+use crate::{dim::Dim, DataFrame, Schema, Value};
+
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Identifies a file as a saved dataframe
+pub const MAGIC: &[u8; 4] = b"RETL";
+
+/// The version of the file format written by save. Files written with a different version can't
+/// be loaded. Bump it when the layout of the schema or values changes.
+pub const FORMAT_VERSION: u16 = 9;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to read or write {}: {}", path.display(), source))]
+    IoError {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("{} is not a saved dataframe", path.display()))]
+    InvalidMagic { path: PathBuf },
+
+    #[snafu(display(
+        "{} was written with format version {}, but only version {} is supported",
+        path.display(),
+        found,
+        supported
+    ))]
+    UnsupportedVersion {
+        path: PathBuf,
+        found: u16,
+        supported: u16,
+    },
+
+    #[snafu(display(
+        "{} is corrupt, expected checksum {:08x} but the contents have checksum {:08x}",
+        path.display(),
+        expected,
+        actual
+    ))]
+    ChecksumMismatch {
+        path: PathBuf,
+        expected: u32,
+        actual: u32,
+    },
+
+    #[snafu(display("Failed to encode or decode {}: {}", path.display(), source))]
+    EncodingError {
+        path: PathBuf,
+        source: bincode::Error,
+    },
+
+    #[snafu(display(
+        "{} is corrupt, the header describes {} values but the file contains {}",
+        path.display(),
+        expected,
+        actual
+    ))]
+    InvalidDataLength {
+        path: PathBuf,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Written before the data, describes the shape and the types of the data
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    schema: Schema,
+    rows: u64,
+}
+
+// The layout of a saved dataframe, all integers are little endian:
+//
+//   magic (4 bytes) | version (u16) | header length (u64) | header | data length (u64) | data |
+//   crc32 of the header and data (u32)
+
+impl<'a> DataFrame<'a> {
+    /// Save the dataframe to the path in retl's binary format
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+
+        let header = Header {
+            schema: self.schema.clone(),
+            rows: self.dim.1 as u64,
+        };
+
+        let header = bincode::serialize(&header).context(EncodingError { path })?;
+        // the values are written row by row
+        let values = (0..self.dim.1)
+            .flat_map(|row| self.columns.iter().filter_map(move |c| c.get(row)))
+            .collect::<Vec<Value>>();
+        let data = bincode::serialize(&values).context(EncodingError { path })?;
+
+        let mut hasher = crc32fast::Hasher::new();
+        hasher.update(&header);
+        hasher.update(&data);
+        let checksum = hasher.finalize();
+
+        let file = File::create(path).context(IoError { path })?;
+        let mut writer = BufWriter::new(file);
+
+        writer
+            .write_all(MAGIC)
+            .and_then(|_| writer.write_all(&FORMAT_VERSION.to_le_bytes()))
+            .and_then(|_| writer.write_all(&(header.len() as u64).to_le_bytes()))
+            .and_then(|_| writer.write_all(&header))
+            .and_then(|_| writer.write_all(&(data.len() as u64).to_le_bytes()))
+            .and_then(|_| writer.write_all(&data))
+            .and_then(|_| writer.write_all(&checksum.to_le_bytes()))
+            .and_then(|_| writer.flush())
+            .context(IoError { path })
+    }
+
+    /// Load a dataframe previously written with save
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<DataFrame<'a>> {
+        let path = path.as_ref();
+        let mut reader = open(path)?;
+
+        let header_bytes = read_section(&mut reader, path)?;
+        let data_bytes = read_section(&mut reader, path)?;
+
+        let mut checksum = [0u8; 4];
+        reader.read_exact(&mut checksum).context(IoError { path })?;
+        let expected = u32::from_le_bytes(checksum);
+
+        let mut hasher = crc32fast::Hasher::new();
+        hasher.update(&header_bytes);
+        hasher.update(&data_bytes);
+        let actual = hasher.finalize();
+
+        if expected != actual {
+            return Err(Error::ChecksumMismatch {
+                path: path.to_path_buf(),
+                expected,
+                actual,
+            });
+        }
+
+        let header: Header = bincode::deserialize(&header_bytes).context(EncodingError { path })?;
+        let data: Vec<Value> = bincode::deserialize(&data_bytes).context(EncodingError { path })?;
+
+        let dim = Dim::new(header.schema.len(), header.rows as usize);
+
+        if dim.expected_len() != data.len() {
+            return Err(Error::InvalidDataLength {
+                path: path.to_path_buf(),
+                expected: dim.expected_len(),
+                actual: data.len(),
+            });
+        }
+
+        let mut df = DataFrame::with_schema(header.schema);
+        if dim.0 == 0 {
+            df.dim = dim;
+        } else {
+            let rows = data.chunks(dim.0).map(<[Value]>::to_vec).collect();
+            df.extend_unchecked(rows);
+        }
+
+        Ok(df)
+    }
+
+    /// Read only the schema of a dataframe written with save, without loading its data
+    pub fn load_schema<P: AsRef<Path>>(path: P) -> Result<Schema> {
+        let path = path.as_ref();
+        let mut reader = open(path)?;
+
+        let header_bytes = read_section(&mut reader, path)?;
+        let header: Header = bincode::deserialize(&header_bytes).context(EncodingError { path })?;
+
+        Ok(header.schema)
+    }
+}
+
+/// Open the file and check that it is a saved dataframe with a supported version
+fn open(path: &Path) -> Result<BufReader<File>> {
+    let file = File::open(path).context(IoError { path })?;
+    let mut reader = BufReader::new(file);
+
+    let mut magic = [0u8; 4];
+    let mut version = [0u8; 2];
+
+    if reader.read_exact(&mut magic).is_err() || &magic != MAGIC {
+        return Err(Error::InvalidMagic {
+            path: path.to_path_buf(),
+        });
+    }
+
+    reader.read_exact(&mut version).context(IoError { path })?;
+    let version = u16::from_le_bytes(version);
+
+    if version != FORMAT_VERSION {
+        return Err(Error::UnsupportedVersion {
+            path: path.to_path_buf(),
+            found: version,
+            supported: FORMAT_VERSION,
+        });
+    }
+
+    Ok(reader)
+}
+
+/// Read a section prefixed by its length
+fn read_section<R: Read>(reader: &mut R, path: &Path) -> Result<Vec<u8>> {
+    let mut len = [0u8; 8];
+    reader.read_exact(&mut len).context(IoError { path })?;
+
+    let mut section = vec![];
+    reader
+        .take(u64::from_le_bytes(len))
+        .read_to_end(&mut section)
+        .context(IoError { path })?;
+
+    Ok(section)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{df, row, value::number::Num, DataType, Number};
+    use rust_decimal::Decimal;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("retl-{}-{}.bin", name, std::process::id()))
+    }
+
+    #[test]
+    fn it_saves_and_loads() {
+        let path = temp_path("save");
+        let df = df!(
+            [("a", DataType::Int64), "b"],
+            [row![1, "x"], row![2, true], row![3, 1.5]]
+        );
+        let mut df = df;
+        let date = chrono::NaiveDate::from_ymd_opt(2019, 9, 5)
+            .and_then(|d| d.and_hms_opt(18, 14, 4))
+            .unwrap();
+        let decimal = Value::Number(Number(Num::Decimal(Decimal::new(12345, 2))));
+        assert!(df.push_row(vec![4.into(), Value::Timestamp(date)]).is_ok());
+        assert!(df.push_row(vec![5.into(), decimal]).is_ok());
+
+        assert!(df.save(&path).is_ok());
+
+        let loaded = DataFrame::load(&path).unwrap();
+        assert_eq!(loaded, df);
+        assert_eq!(DataFrame::load_schema(&path).unwrap(), *df.schema());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn it_rejects_other_versions_and_corrupt_files() {
+        let path = temp_path("corrupt");
+        let df = df!(["a"], [row![1], row![2]]);
+        assert!(df.save(&path).is_ok());
+
+        let mut bytes = std::fs::read(&path).unwrap();
+
+        // flip a bit in the data
+        let last = bytes.len() - 5;
+        bytes[last] ^= 1;
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(
+            DataFrame::load(&path),
+            Err(Error::ChecksumMismatch { .. })
+        ));
+
+        bytes[4..6].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(
+            DataFrame::load(&path),
+            Err(Error::UnsupportedVersion { found, .. }) if found == FORMAT_VERSION + 1
+        ));
+
+        std::fs::write(&path, b"a,b,c").unwrap();
+        assert!(matches!(
+            DataFrame::load(&path),
+            Err(Error::InvalidMagic { .. })
+        ));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,17 +1,770 @@
+use crate::{ops::python::PyTransform, persist, DataFrame};
+
+use rayon::prelude::*;
+use snafu::{ResultExt, Snafu};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Task {} failed during run {}: {}", task_id, run_id, source))]
+    TaskFailed {
+        run_id: String,
+        task_id: String,
+        #[snafu(source(from(crate::error::Error, Box::new)))]
+        source: Box<crate::error::Error>,
+    },
+
+    #[snafu(display("Failed to write checkpoint {}: {}", path.display(), source))]
+    WriteCheckpoint {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Failed to save checkpoint {}: {}", path.display(), source))]
+    SaveCheckpoint {
+        path: PathBuf,
+        source: persist::Error,
+    },
+
+    #[snafu(display("Failed to load checkpoint {}: {}", path.display(), source))]
+    LoadCheckpoint {
+        path: PathBuf,
+        source: persist::Error,
+    },
+
+    #[snafu(display(
+        "Task {} uses {} as its input, but no task with that id runs before it",
+        task_id,
+        input
+    ))]
+    InvalidTaskInput { task_id: String, input: String },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The operation performed by a task. Receives the output of the previous task (or an empty
+/// dataframe for the first task) and returns the dataframe handed to the next task.
+pub type TaskFn =
+    Box<dyn Fn(DataFrame<'static>) -> crate::error::Result<DataFrame<'static>> + Send + Sync>;
+
 pub struct Pipeline {
     id: String,
     description: String,
     tasks: Vec<Task>,
+    checkpoint_dir: Option<PathBuf>,
+    parallel: bool,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TaskKind {
     Source,
     Op,
     Destination,
 }
 
+/// Where a task gets the dataframe it operates on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TaskInput {
+    /// The output of the task added before it
+    Previous,
+    /// The output of the task with the id, which has to be added before it
+    Task(String),
+    /// An empty dataframe
+    Empty,
+}
+
 pub struct Task {
     id: String,
     description: String,
     kind: TaskKind,
+    version: Option<String>,
+    checkpoint: bool,
+    input: TaskInput,
+    op: TaskFn,
+}
+
+impl Task {
+    pub fn new<F>(id: &str, description: &str, kind: TaskKind, op: F) -> Task
+    where
+        F: Fn(DataFrame<'static>) -> crate::error::Result<DataFrame<'static>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Task {
+            id: id.to_string(),
+            description: description.to_string(),
+            kind,
+            version: None,
+            checkpoint: true,
+            input: TaskInput::Previous,
+            op: Box::new(op),
+        }
+    }
+
+    /// Create an op task that applies the python transform to its input. The function's version
+    /// is the task's version, so editing the function invalidates the task's checkpoints. Tasks
+    /// with a function that has no version aren't checkpointed.
+    pub fn python(id: &str, description: &str, transform: PyTransform) -> Task {
+        let version = transform.function().version();
+
+        let task = Task::new(id, description, TaskKind::Op, move |mut df| {
+            df.apply_python(&transform)?;
+            Ok(df)
+        });
+
+        match version {
+            Some(version) => task.with_version(version),
+            None => task.with_checkpoint(false),
+        }
+    }
+
+    /// Set where the task reads its input from. Tasks that don't depend on each other, directly
+    /// or through their inputs, can run at the same time in parallel mode.
+    pub fn with_input(mut self, input: TaskInput) -> Task {
+        self.input = input;
+        self
+    }
+
+    /// Set the version of the task's definition. Since the operation itself can't be inspected,
+    /// changing the version is how a task signals that existing checkpoints are stale.
+    pub fn with_version<S: Into<String>>(mut self, version: S) -> Task {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Set whether the task's output is checkpointed, tasks without a checkpoint run every time
+    pub fn with_checkpoint(mut self, checkpoint: bool) -> Task {
+        self.checkpoint = checkpoint;
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn kind(&self) -> TaskKind {
+        self.kind
+    }
+
+    pub fn input(&self) -> &TaskInput {
+        &self.input
+    }
+
+    /// Hash the task's definition together with the fingerprint of its input task, so that
+    /// changing any task invalidates its checkpoint and the checkpoints of every task downstream.
+    fn fingerprint(&self, previous: u32) -> u32 {
+        let kind = match self.kind {
+            TaskKind::Source => "source",
+            TaskKind::Op => "op",
+            TaskKind::Destination => "destination",
+        };
+
+        fingerprint(&[
+            &previous.to_le_bytes(),
+            self.id.as_bytes(),
+            self.description.as_bytes(),
+            kind.as_bytes(),
+            &[self.version.is_some() as u8],
+            self.version.as_deref().unwrap_or_default().as_bytes(),
+        ])
+    }
+}
+
+impl Pipeline {
+    pub fn new(id: &str, description: &str) -> Pipeline {
+        Pipeline {
+            id: id.to_string(),
+            description: description.to_string(),
+            tasks: vec![],
+            checkpoint_dir: None,
+            parallel: false,
+        }
+    }
+
+    /// Checkpoint the output of each task into the directory. Without a checkpoint directory
+    /// every run starts from the first task.
+    pub fn with_checkpoint_dir<P: Into<PathBuf>>(mut self, dir: P) -> Pipeline {
+        self.checkpoint_dir = Some(dir.into());
+        self
+    }
+
+    /// Run tasks that are independent of each other on the rayon thread pool. The output and
+    /// the error returned for a failing run are the same as when running sequentially.
+    pub fn with_parallelism(mut self, parallel: bool) -> Pipeline {
+        self.parallel = parallel;
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn tasks(&self) -> &[Task] {
+        &self.tasks
+    }
+
+    pub fn add_task(&mut self, task: Task) -> usize {
+        self.tasks.push(task);
+        self.tasks.len() - 1
+    }
+
+    /// Run every task under a new run id. If a task fails the error contains the run id, which
+    /// can be passed to resume.
+    pub fn run(&self) -> Result<DataFrame<'static>> {
+        let run_id = chrono::Utc::now().format("%Y%m%d%H%M%S%f").to_string();
+        self.resume(&run_id)
+    }
+
+    /// Run the tasks for the run id, skipping any task with a checkpoint that is still valid for
+    /// the current task definitions. Returns the output of the last task.
+    pub fn resume(&self, run_id: &str) -> Result<DataFrame<'static>> {
+        let inputs = self.resolve_inputs()?;
+        let fingerprints = self.fingerprints(&inputs);
+
+        // the number of tasks still waiting on each task's output; the output of the last task is
+        // waited on by the caller
+        let mut consumers = vec![0; self.tasks.len()];
+        inputs
+            .iter()
+            .flatten()
+            .for_each(|input| consumers[*input] += 1);
+        if let Some(last) = consumers.last_mut() {
+            *last += 1;
+        }
+
+        let mut outputs: Vec<Option<DataFrame<'static>>> = vec![None; self.tasks.len()];
+
+        // take the input from the outputs, only cloning it if another task still needs it
+        let mut take_input = |position: usize, outputs: &mut Vec<Option<DataFrame<'static>>>| {
+            inputs[position].map_or_else(DataFrame::empty, |input| {
+                consumers[input] -= 1;
+
+                if consumers[input] == 0 {
+                    outputs[input].take().unwrap_or_default()
+                } else {
+                    outputs[input].clone().unwrap_or_default()
+                }
+            })
+        };
+
+        if self.parallel {
+            // a sequential run fails on the failing task with the lowest position, so once a task
+            // fails only the tasks before it keep running to find out if one of them fails first
+            let mut failure: Option<(usize, Error)> = None;
+
+            for wave in waves(&inputs) {
+                let batch = wave
+                    .into_iter()
+                    .filter(|position| failure.as_ref().is_none_or(|(f, _)| position < f))
+                    .map(|position| (position, take_input(position, &mut outputs)))
+                    .collect::<Vec<(usize, DataFrame<'static>)>>();
+
+                let results = batch
+                    .into_par_iter()
+                    .map(|(position, df)| {
+                        let output = self.run_task(run_id, position, fingerprints[position], df);
+                        (position, output)
+                    })
+                    .collect::<Vec<(usize, Result<DataFrame<'static>>)>>();
+
+                for (position, output) in results {
+                    match output {
+                        Ok(df) => outputs[position] = Some(df),
+                        Err(e) => {
+                            if failure.as_ref().is_none_or(|(f, _)| position < *f) {
+                                failure = Some((position, e));
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Some((_, e)) = failure {
+                return Err(e);
+            }
+        } else {
+            for position in 0..self.tasks.len() {
+                let df = take_input(position, &mut outputs);
+                outputs[position] =
+                    Some(self.run_task(run_id, position, fingerprints[position], df)?);
+            }
+        }
+
+        Ok(outputs.pop().and_then(|output| output).unwrap_or_default())
+    }
+
+    /// Remove all the checkpoints for the run
+    pub fn remove_checkpoints(&self, run_id: &str) -> Result<()> {
+        if let Some(dir) = self.run_dir(run_id) {
+            if dir.exists() {
+                fs::remove_dir_all(&dir).context(WriteCheckpoint { path: dir })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Load the task's output from its checkpoint or run the task and checkpoint its output
+    fn run_task(
+        &self,
+        run_id: &str,
+        position: usize,
+        fingerprint: u32,
+        df: DataFrame<'static>,
+    ) -> Result<DataFrame<'static>> {
+        let task = &self.tasks[position];
+        let path = self.checkpoint_path(run_id, position, task, fingerprint);
+
+        if let Some(data) = path.as_deref().map(read_checkpoint).transpose()?.flatten() {
+            return Ok(data);
+        }
+
+        let df = (task.op)(df).context(TaskFailed {
+            run_id: run_id.to_string(),
+            task_id: task.id.clone(),
+        })?;
+
+        if let Some(path) = path {
+            write_checkpoint(&path, &checkpoint_prefix(position, task), &df)?;
+        }
+
+        Ok(df)
+    }
+
+    /// Resolve the position of each task's input task
+    fn resolve_inputs(&self) -> Result<Vec<Option<usize>>> {
+        self.tasks
+            .iter()
+            .enumerate()
+            .map(|(position, task)| match &task.input {
+                TaskInput::Previous => Ok(position.checked_sub(1)),
+                TaskInput::Empty => Ok(None),
+                TaskInput::Task(id) => self.tasks[..position]
+                    .iter()
+                    .rposition(|t| &t.id == id)
+                    .map(Some)
+                    .ok_or(Error::InvalidTaskInput {
+                        task_id: task.id.clone(),
+                        input: id.clone(),
+                    }),
+            })
+            .collect()
+    }
+
+    fn fingerprints(&self, inputs: &[Option<usize>]) -> Vec<u32> {
+        let root = fingerprint(&[self.id.as_bytes()]);
+
+        let mut fingerprints: Vec<u32> = Vec::with_capacity(self.tasks.len());
+
+        for (task, input) in self.tasks.iter().zip(inputs) {
+            let previous = input.map_or(root, |input| fingerprints[input]);
+            fingerprints.push(task.fingerprint(previous));
+        }
+
+        fingerprints
+    }
+
+    fn run_dir(&self, run_id: &str) -> Option<PathBuf> {
+        self.checkpoint_dir
+            .as_ref()
+            .map(|dir| dir.join(&self.id).join(run_id))
+    }
+
+    /// The checkpoint's name has the fingerprint of the task definitions that produced it, so a
+    /// changed task never finds a stale checkpoint
+    fn checkpoint_path(
+        &self,
+        run_id: &str,
+        position: usize,
+        task: &Task,
+        fingerprint: u32,
+    ) -> Option<PathBuf> {
+        self.run_dir(run_id).filter(|_| task.checkpoint).map(|dir| {
+            dir.join(format!(
+                "{}{:08x}.ckpt",
+                checkpoint_prefix(position, task),
+                fingerprint
+            ))
+        })
+    }
+}
+
+/// Group the tasks into waves, where every task in a wave only depends on tasks from earlier
+/// waves. Tasks within a wave are ordered by their position in the pipeline.
+fn waves(inputs: &[Option<usize>]) -> Vec<Vec<usize>> {
+    let mut depths: Vec<usize> = Vec::with_capacity(inputs.len());
+    let mut waves: Vec<Vec<usize>> = vec![];
+
+    for (position, input) in inputs.iter().enumerate() {
+        let depth = input.map_or(0, |input| depths[input] + 1);
+        depths.push(depth);
+
+        if waves.len() <= depth {
+            waves.resize(depth + 1, vec![]);
+        }
+        waves[depth].push(position);
+    }
+
+    waves
+}
+
+/// A fingerprint that is the same across builds and platforms: the crc32 of the parts, each
+/// prefixed by its length so that moving bytes between parts changes the fingerprint
+fn fingerprint(parts: &[&[u8]]) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    for part in parts {
+        hasher.update(&(part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+    hasher.finalize()
+}
+
+/// The start of the names of the task's checkpoints, whatever their fingerprint
+fn checkpoint_prefix(position: usize, task: &Task) -> String {
+    format!("{:04}-{}-", position, task.id)
+}
+
+/// Load the checkpoint if it exists. Checkpoints written with another version of the format are
+/// treated as missing so that the task is run again, other failures to load it are errors.
+fn read_checkpoint(path: &Path) -> Result<Option<DataFrame<'static>>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    match DataFrame::load(path) {
+        Ok(df) => Ok(Some(df)),
+        Err(persist::Error::UnsupportedVersion { .. }) => Ok(None),
+        Err(source) => Err(Error::LoadCheckpoint {
+            path: path.to_path_buf(),
+            source,
+        }),
+    }
+}
+
+/// Write the checkpoint to a temporary file first and then move it into place so that a crash
+/// mid-write never leaves a partial checkpoint behind. The task's checkpoints with other
+/// fingerprints are stale and removed.
+fn write_checkpoint(path: &Path, prefix: &str, df: &DataFrame) -> Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(dir).context(WriteCheckpoint { path })?;
+
+    let tmp_path = path.with_extension("tmp");
+    df.save(&tmp_path).context(SaveCheckpoint { path })?;
+    fs::rename(&tmp_path, path).context(WriteCheckpoint { path })?;
+
+    // the prefix is followed by the 8 digits of the fingerprint
+    for entry in fs::read_dir(dir).context(WriteCheckpoint { path })? {
+        let stale = entry.context(WriteCheckpoint { path })?.path();
+        let name = stale
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        if stale != path
+            && name.starts_with(prefix)
+            && name.ends_with(".ckpt")
+            && name.len() == prefix.len() + 13
+        {
+            fs::remove_file(&stale).context(WriteCheckpoint { path: &stale })?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::python::Function;
+    use crate::{error::Error as DataFrameError, row, Value};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn checkpoint_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("retl-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn build(
+        dir: &Path,
+        calls: Arc<AtomicUsize>,
+        fail: Arc<AtomicBool>,
+        version: &str,
+    ) -> Pipeline {
+        let mut pipeline = Pipeline::new("test", "checkpoint test").with_checkpoint_dir(dir);
+
+        let source_calls = calls.clone();
+        pipeline.add_task(Task::new("load", "load", TaskKind::Source, move |_| {
+            source_calls.fetch_add(1, Ordering::SeqCst);
+            let mut df = DataFrame::with_columns(&["a"]);
+            df.extend(vec![row![1], row![2]])?;
+            Ok(df)
+        }));
+
+        pipeline.add_task(
+            Task::new("add", "add column", TaskKind::Op, move |mut df| {
+                if fail.load(Ordering::SeqCst) {
+                    return Err(DataFrameError::InvalidColumnName {
+                        column: "b".to_string(),
+                    });
+                }
+                df.add_column("b")?;
+                Ok(df)
+            })
+            .with_version(version),
+        );
+
+        pipeline
+    }
+
+    #[test]
+    fn it_resumes_from_checkpoint() {
+        let dir = checkpoint_dir("resume");
+        let calls = Arc::new(AtomicUsize::new(0));
+        let fail = Arc::new(AtomicBool::new(true));
+
+        let pipeline = build(&dir, calls.clone(), fail.clone(), "1");
+
+        let run_id = match pipeline.run() {
+            Err(Error::TaskFailed {
+                run_id, task_id, ..
+            }) => {
+                assert_eq!(task_id, "add");
+                run_id
+            }
+            _ => panic!("expected the second task to fail"),
+        };
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        fail.store(false, Ordering::SeqCst);
+        let df = pipeline.resume(&run_id).unwrap();
+
+        // the source task was loaded from its checkpoint
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(df.shape(), (2, 2));
+        assert_eq!(df.row(1).unwrap(), &[2.into(), Value::Null][..]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn it_invalidates_changed_tasks() {
+        let dir = checkpoint_dir("invalidate");
+        let calls = Arc::new(AtomicUsize::new(0));
+        let fail = Arc::new(AtomicBool::new(false));
+
+        let pipeline = build(&dir, calls.clone(), fail.clone(), "1");
+        assert!(pipeline.resume("run").is_ok());
+        assert!(pipeline.resume("run").is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // changing the second task only re-runs the second task; failing proves it isn't skipped
+        fail.store(true, Ordering::SeqCst);
+        let pipeline = build(&dir, calls.clone(), fail.clone(), "2");
+        assert!(pipeline.resume("run").is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // the new version's checkpoint replaces the old one
+        fail.store(false, Ordering::SeqCst);
+        assert!(pipeline.resume("run").is_ok());
+        let checkpoints = fs::read_dir(dir.join("test").join("run"))
+            .unwrap()
+            .filter(|entry| {
+                let name = entry.as_ref().unwrap().file_name();
+                name.to_string_lossy().starts_with("0001-add-")
+            })
+            .count();
+        assert_eq!(checkpoints, 1);
+
+        assert!(pipeline.remove_checkpoints("run").is_ok());
+        assert!(!dir.join("test").join("run").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn it_rejects_corrupt_checkpoints() {
+        let dir = checkpoint_dir("corrupt");
+        let calls = Arc::new(AtomicUsize::new(0));
+        let fail = Arc::new(AtomicBool::new(false));
+
+        let pipeline = build(&dir, calls.clone(), fail, "1");
+        assert!(pipeline.resume("run").is_ok());
+
+        let path = fs::read_dir(dir.join("test").join("run"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .find(|path| path.to_string_lossy().contains("0000-load-"))
+            .unwrap();
+        let mut bytes = fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+        fs::write(&path, bytes).unwrap();
+
+        assert!(matches!(
+            pipeline.resume("run"),
+            Err(Error::LoadCheckpoint {
+                source: persist::Error::ChecksumMismatch { .. },
+                ..
+            })
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // fingerprints don't change between builds
+        assert_eq!(fingerprint(&[b"test"]), 0x81e8_5c00);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    fn independent(parallel: bool) -> Pipeline {
+        let mut pipeline = Pipeline::new("independent", "").with_parallelism(parallel);
+
+        pipeline.add_task(Task::new("load", "", TaskKind::Source, |_| {
+            let mut df = DataFrame::with_columns(&["a"]);
+            df.extend(vec![row![1], row![2]])?;
+            Ok(df)
+        }));
+
+        pipeline.add_task(Task::new("b", "", TaskKind::Op, |mut df| {
+            df.add_column("b")?;
+            Ok(df)
+        }));
+
+        pipeline.add_task(
+            Task::new("c", "", TaskKind::Op, |mut df| {
+                df.add_column("c")?;
+                Ok(df)
+            })
+            .with_input(TaskInput::Task("load".to_string())),
+        );
+
+        pipeline.add_task(
+            Task::new("fail", "", TaskKind::Op, |_| {
+                Err(DataFrameError::InvalidColumnName {
+                    column: "x".to_string(),
+                })
+            })
+            .with_input(TaskInput::Task("b".to_string())),
+        );
+
+        pipeline
+    }
+
+    #[test]
+    fn it_groups_independent_tasks_into_waves() {
+        let inputs = vec![None, Some(0), Some(0), Some(1), None];
+        assert_eq!(waves(&inputs), vec![vec![0, 4], vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn it_runs_independent_tasks_in_parallel() {
+        let mut sequential = independent(false);
+        let mut parallel = independent(true);
+
+        // both modes fail on the same task
+        for pipeline in &[&sequential, &parallel] {
+            match pipeline.run() {
+                Err(Error::TaskFailed { task_id, .. }) => assert_eq!(task_id, "fail"),
+                _ => panic!("expected the last task to fail"),
+            }
+        }
+
+        sequential.tasks.pop();
+        parallel.tasks.pop();
+
+        let expected = sequential.run().unwrap();
+        let actual = parallel.run().unwrap();
+
+        assert_eq!(expected, actual);
+        assert_eq!(actual.columns(), ["a", "c"]);
+    }
+
+    #[test]
+    fn it_rejects_unknown_inputs() {
+        let mut pipeline = Pipeline::new("invalid", "");
+        pipeline.add_task(
+            Task::new("a", "", TaskKind::Op, Ok).with_input(TaskInput::Task("b".to_string())),
+        );
+
+        assert!(match pipeline.run() {
+            Err(Error::InvalidTaskInput { task_id, input }) => task_id == "a" && input == "b",
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn it_runs_python_tasks() {
+        let mut pipeline = Pipeline::new("python", "python test");
+
+        pipeline.add_task(Task::new("load", "load", TaskKind::Source, |_| {
+            let mut df = DataFrame::with_columns(&["a"]);
+            df.extend(vec![row![1], row![2]])?;
+            Ok(df)
+        }));
+
+        let code = "def double(row):\n    return {\"a\": row[\"a\"] * 2}";
+        let task = Task::python(
+            "double",
+            "double a",
+            PyTransform::Rows(Function::from_source(code, "double")),
+        );
+        assert_eq!(task.version.as_deref(), Some(code));
+        pipeline.add_task(task);
+
+        // module functions are versioned by their file, and aren't checkpointed without one
+        let dir = checkpoint_dir("python-module");
+        fs::create_dir_all(&dir).unwrap();
+        let module = dir.join("retl_pipeline_transforms.py");
+        {
+            use cpython::ObjectProtocol;
+
+            let gil = cpython::Python::acquire_gil();
+            let py = gil.python();
+            let sys = py.import("sys").unwrap();
+            let path = sys.get(py, "path").unwrap();
+            path.call_method(py, "insert", (0, dir.to_str().unwrap()), None)
+                .unwrap();
+        }
+
+        let module_task = || {
+            Task::python(
+                "module",
+                "",
+                PyTransform::Rows(Function::from_module("retl_pipeline_transforms", "f")),
+            )
+        };
+        fs::write(&module, "def f(row):\n    return None\n").unwrap();
+        let first = module_task();
+        fs::write(&module, "def f(row):\n    return {}\n").unwrap();
+        let second = module_task();
+        assert!(first.checkpoint && second.checkpoint);
+        assert_ne!(first.version, second.version);
+
+        let builtin = Task::python(
+            "builtin",
+            "",
+            PyTransform::Column("a".to_string(), Function::from_module("builtins", "list")),
+        );
+        assert_eq!(builtin.version, None);
+        assert!(!builtin.checkpoint);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let df = pipeline.run().unwrap();
+        assert_eq!(
+            df.column_values("a").unwrap().collect::<Vec<Value>>(),
+            vec![Value::from(2i64), Value::from(4i64)]
+        );
+    }
 }
@@ -0,0 +1,268 @@
+//! The `retl` python module. Build the crate with the `extension-module` feature and rename the
+//! built library to `retl.so` to import it from python.
+
+// py_class! implements traits inside of the functions it generates, and strips prefixes by hand
+#![allow(non_local_definitions, clippy::manual_strip)]
+
+pub mod value;
+
+use crate::{
+    dataframe::DataFrame as Frame,
+    ops::{
+        convert::Convert,
+        select::{select, Select},
+    },
+    source::csv::CsvSource,
+    DataType,
+};
+
+use cpython::{
+    exc, PyDict, PyErr, PyList, PyModule, PyObject, PyResult, PyString, PyTuple, Python,
+    PythonObject,
+};
+use std::cell::RefCell;
+
+py_exception!(retl, RetlError);
+
+py_module_initializer!(retl, initretl, PyInit_retl, |py, m| init_module(py, m));
+
+/// Add the module's members, called when python imports the module
+pub fn init_module(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add(py, "__doc__", "Extract, transform and load tabular data")?;
+    m.add(py, "RetlError", py.get_type::<RetlError>())?;
+    m.add_class::<DataFrame>(py)?;
+    Ok(())
+}
+
+/// Raise the error as a RetlError
+fn to_py_err<E: std::fmt::Display>(py: Python, err: E) -> PyErr {
+    PyErr::new::<RetlError, _>(py, err.to_string())
+}
+
+/// Parse the type's name, raising a ValueError listing the valid names when it isn't a type
+fn parse_dtype(py: Python, name: &str) -> PyResult<DataType> {
+    name.parse()
+        .map_err(|e: crate::schema::Error| PyErr::new::<exc::ValueError, _>(py, e.to_string()))
+}
+
+py_class!(pub class DataFrame |py| {
+    data df: RefCell<Frame<'static>>;
+
+    @staticmethod
+    def read_csv(path: &str) -> PyResult<DataFrame> {
+        let df = <Frame as CsvSource>::from_path(path).map_err(|e| to_py_err(py, e))?;
+        DataFrame::create_instance(py, RefCell::new(df))
+    }
+
+    def columns(&self) -> PyResult<Vec<String>> {
+        Ok(self.df(py).borrow().columns().into_iter().cloned().collect())
+    }
+
+    def shape(&self) -> PyResult<(usize, usize)> {
+        Ok(self.df(py).borrow().shape())
+    }
+
+    def __len__(&self) -> PyResult<usize> {
+        Ok(self.df(py).borrow().size())
+    }
+
+    /// Select the columns by name, a column can be renamed by passing a tuple of the name and the
+    /// new name
+    def select(&self, columns: PyList) -> PyResult<DataFrame> {
+        let columns = columns
+            .iter(py)
+            .map(|c| match c.cast_as::<PyTuple>(py) {
+                Ok(_) => c.extract::<(String, String)>(py),
+                Err(_) => c.extract::<String>(py).map(|name| (name.clone(), name)),
+            })
+            .collect::<PyResult<Vec<(String, String)>>>()?;
+
+        let selection = columns
+            .iter()
+            .map(|(name, alias)| Select::Alias(name, alias))
+            .collect::<Vec<Select>>();
+
+        let df = select(&self.df(py).borrow(), &selection).map_err(|e| to_py_err(py, e))?;
+        DataFrame::create_instance(py, RefCell::new(df))
+    }
+
+    def cast_column(&self, column: &str, dtype: &str) -> PyResult<PyObject> {
+        let dtype = parse_dtype(py, dtype)?;
+        self.df(py)
+            .borrow_mut()
+            .cast_column(column, dtype)
+            .map_err(|e| to_py_err(py, e))?;
+        Ok(py.None())
+    }
+
+    /// Convert the column, parsing the values using the strftime style format. The conversions are
+    /// "datetime", "date" and "time".
+    def convert_column(&self, column: &str, conversion: &str, format: &str) -> PyResult<PyObject> {
+        let conversion = match conversion {
+            "datetime" => Convert::ParseDateTime(format),
+            "date" => Convert::ParseDate(format),
+            "time" => Convert::ParseTime(format),
+            _ => {
+                return Err(PyErr::new::<exc::ValueError, _>(
+                    py,
+                    format!("{} is not a valid conversion", conversion),
+                ))
+            }
+        };
+
+        self.df(py)
+            .borrow_mut()
+            .convert_column(column, conversion)
+            .map_err(|e| to_py_err(py, e))?;
+        Ok(py.None())
+    }
+
+    def print(&self, num_rows: usize = 10) -> PyResult<PyObject> {
+        self.df(py).borrow().print(num_rows);
+        Ok(py.None())
+    }
+
+    /// The rows as a list of dicts keyed by column name
+    def to_dicts(&self) -> PyResult<PyList> {
+        let df = self.df(py).borrow();
+
+        let columns = df
+            .columns()
+            .into_iter()
+            .map(|c| PyString::new(py, c))
+            .collect::<Vec<PyString>>();
+
+        let rows = df
+            .iter()
+            .map(|row| {
+                let dict = PyDict::new(py);
+                for (column, value) in columns.iter().zip(row.iter()) {
+                    dict.set_item(py, column, value::to_py(py, value)?)?;
+                }
+                Ok(dict.into_object())
+            })
+            .collect::<PyResult<Vec<PyObject>>>()?;
+
+        Ok(PyList::new(py, &rows))
+    }
+});
+
+impl DataFrame {
+    /// Wrap the dataframe so that it can be handed to python
+    pub fn from_frame(py: Python, df: Frame<'static>) -> PyResult<DataFrame> {
+        DataFrame::create_instance(py, RefCell::new(df))
+    }
+
+    /// Take a copy of the wrapped dataframe
+    pub fn to_frame(&self, py: Python) -> Frame<'static> {
+        self.df(py).borrow().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{row, value::number::Num, Number, Value};
+    use cpython::{ObjectProtocol, PyDict, ToPyObject};
+    use rust_decimal::Decimal;
+
+    fn run(py: Python, df: Frame<'static>, code: &str) -> PyResult<()> {
+        let m = PyModule::new(py, "retl")?;
+        init_module(py, &m)?;
+
+        let locals = PyDict::new(py);
+        locals.set_item(py, "retl", &m)?;
+        locals.set_item(py, "df", DataFrame::from_frame(py, df)?)?;
+
+        py.run(code, None, Some(&locals))
+    }
+
+    #[test]
+    fn it_exposes_dataframe() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let mut df = Frame::with_columns(&["a", "b", "c"]);
+        assert!(df
+            .extend(vec![row!["1", "x", true], row!["2", "y", false]])
+            .is_ok());
+
+        let code = r#"
+assert df.columns() == ["a", "b", "c"]
+assert df.shape() == (3, 2)
+assert len(df) == 2
+
+df.cast_column("a", "int64")
+assert df.to_dicts() == [{"a": 1, "b": "x", "c": True}, {"a": 2, "b": "y", "c": False}]
+
+selected = df.select(["c", ("a", "z")])
+assert selected.columns() == ["c", "z"]
+assert selected.to_dicts()[1] == {"c": False, "z": 2}
+
+try:
+    df.cast_column("b", "int64")
+    assert False
+except retl.RetlError:
+    pass
+
+try:
+    df.cast_column("a", "not a type")
+    assert False
+except ValueError:
+    pass
+"#;
+
+        if let Err(e) = run(py, df, code) {
+            e.print(py);
+            panic!("python assertions failed");
+        }
+    }
+
+    #[test]
+    fn it_converts_values() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let date = chrono::NaiveDate::from_ymd_opt(2019, 9, 5)
+            .and_then(|d| d.and_hms_micro_opt(18, 14, 4, 10))
+            .unwrap();
+
+        let values = vec![
+            Value::Null,
+            Value::Bool(true),
+            Value::from(-3i64),
+            Value::from(u64::MAX),
+            Value::from(1.5f64),
+            Value::Number(Number(Num::Decimal(Decimal::new(12345, 2)))),
+            Value::from("x"),
+            Value::Timestamp(date),
+            Value::TimestampTz(date.and_utc().fixed_offset()),
+            Value::Date(date.date()),
+            Value::Time(date.time()),
+            Value::Binary(vec![0, 1]),
+            Value::from(vec![1i64, 2i64]),
+            Value::Map(
+                vec![("k".to_string(), Value::from("v"))]
+                    .into_iter()
+                    .collect(),
+            ),
+        ];
+
+        for v in &values {
+            let obj = value::to_py(py, v).unwrap();
+            assert_eq!(&value::from_py(py, &obj).unwrap(), v);
+        }
+
+        let decimal = value::to_py(py, &values[5]).unwrap();
+        assert_eq!(decimal.str(py).unwrap().to_string(py).unwrap(), "123.45");
+
+        let obj = py.eval("object()", None, None).unwrap();
+        assert!(value::from_py(py, &obj).is_err());
+
+        let tuple = (1i64, "a").to_py_object(py).into_object();
+        assert_eq!(
+            value::from_py(py, &tuple).unwrap(),
+            Value::from(vec![Value::from(1i64), Value::from("a")])
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{
+    value::{map::Map, number::Num},
+    Number, Value,
+};
+
+use chrono::{Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike};
+use cpython::{
+    exc, ObjectProtocol, PyBool, PyBytes, PyDict, PyErr, PyFloat, PyList, PyLong, PyObject,
+    PyResult, PyString, PyTuple, PyType, Python, PythonObject, ToPyObject,
+};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Convert a value into the equivalent python object. Dates, times and timestamps become the
+/// `datetime` module's objects, with an offset timezone for timestamps with a timezone, and
+/// decimals become `decimal.Decimal`.
+pub fn to_py(py: Python, value: &Value) -> PyResult<PyObject> {
+    let obj = match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => b.to_py_object(py).into_object(),
+        Value::String(s) => s.to_py_object(py).into_object(),
+        Value::Number(n) => match n.inner() {
+            Num::Uint8(n) => n.to_py_object(py).into_object(),
+            Num::Uint16(n) => n.to_py_object(py).into_object(),
+            Num::Uint32(n) => n.to_py_object(py).into_object(),
+            Num::Uint64(n) => n.to_py_object(py).into_object(),
+            Num::Int8(n) => n.to_py_object(py).into_object(),
+            Num::Int16(n) => n.to_py_object(py).into_object(),
+            Num::Int32(n) => n.to_py_object(py).into_object(),
+            Num::Int64(n) => n.to_py_object(py).into_object(),
+            Num::Float(n) => f64::from(*n).to_py_object(py).into_object(),
+            Num::Double(n) => n.to_py_object(py).into_object(),
+            Num::Decimal(n) => py
+                .import("decimal")?
+                .call(py, "Decimal", (n.to_string(),), None)?,
+        },
+        Value::Date(d) => {
+            py.import("datetime")?
+                .call(py, "date", (d.year(), d.month(), d.day()), None)?
+        }
+        Value::Time(t) => py.import("datetime")?.call(
+            py,
+            "time",
+            (t.hour(), t.minute(), t.second(), t.nanosecond() / 1000),
+            None,
+        )?,
+        Value::Timestamp(d) => py_datetime(py, d, py.None())?,
+        Value::TimestampTz(d) => {
+            let datetime = py.import("datetime")?;
+            let offset = datetime.call(py, "timedelta", (0, d.offset().local_minus_utc()), None)?;
+            let tz = datetime.call(py, "timezone", (offset,), None)?;
+            py_datetime(py, &d.naive_local(), tz)?
+        }
+        Value::Binary(b) => PyBytes::new(py, b).into_object(),
+        Value::Array(a) => {
+            let items = a
+                .iter()
+                .map(|v| to_py(py, v))
+                .collect::<PyResult<Vec<PyObject>>>()?;
+            PyList::new(py, &items).into_object()
+        }
+        Value::Map(m) => {
+            let dict = PyDict::new(py);
+            for (key, value) in m.iter() {
+                dict.set_item(py, key, to_py(py, value)?)?;
+            }
+            dict.into_object()
+        }
+    };
+
+    Ok(obj)
+}
+
+/// Convert a python object into a value. Integers become `Int64` unless they only fit into a
+/// `Uint64`, and objects that don't map onto a value raise a `TypeError`.
+pub fn from_py(py: Python, obj: &PyObject) -> PyResult<Value> {
+    if obj == &py.None() {
+        return Ok(Value::Null);
+    }
+
+    // bool is a subclass of int, so it has to be checked first
+    if let Ok(b) = obj.cast_as::<PyBool>(py) {
+        return Ok(Value::Bool(b.is_true()));
+    }
+
+    if obj.cast_as::<PyLong>(py).is_ok() {
+        return obj
+            .extract::<i64>(py)
+            .map(Value::from)
+            .or_else(|_| obj.extract::<u64>(py).map(Value::from));
+    }
+
+    if obj.cast_as::<PyFloat>(py).is_ok() {
+        return obj.extract::<f64>(py).map(Value::from);
+    }
+
+    if let Ok(s) = obj.cast_as::<PyString>(py) {
+        return Ok(Value::String(s.to_string(py)?.into_owned()));
+    }
+
+    if let Ok(b) = obj.cast_as::<PyBytes>(py) {
+        return Ok(Value::Binary(b.data(py).to_vec()));
+    }
+
+    if let Ok(list) = obj.cast_as::<PyList>(py) {
+        return list
+            .iter(py)
+            .map(|item| from_py(py, &item))
+            .collect::<PyResult<Vec<Value>>>()
+            .map(Value::Array);
+    }
+
+    if let Ok(tuple) = obj.cast_as::<PyTuple>(py) {
+        return tuple
+            .iter(py)
+            .map(|item| from_py(py, item))
+            .collect::<PyResult<Vec<Value>>>()
+            .map(Value::Array);
+    }
+
+    if let Ok(dict) = obj.cast_as::<PyDict>(py) {
+        let mut map = Map::new();
+        for (key, value) in dict.items(py) {
+            map.insert(key.str(py)?.to_string(py)?, from_py(py, &value)?);
+        }
+        return Ok(Value::Map(map));
+    }
+
+    if is_instance(py, obj, "decimal", "Decimal")? {
+        let s = obj.str(py)?.to_string(py)?.into_owned();
+        return Decimal::from_str(&s)
+            .map(|d| Value::Number(Number(Num::Decimal(d))))
+            .map_err(|e| PyErr::new::<exc::ValueError, _>(py, e.to_string()));
+    }
+
+    let attr = |name: &str| -> PyResult<u32> { obj.getattr(py, name)?.extract::<u32>(py) };
+
+    // datetime is a subclass of date, so it has to be checked first
+    if is_instance(py, obj, "datetime", "datetime")? {
+        let datetime = NaiveDate::from_ymd_opt(attr("year")? as i32, attr("month")?, attr("day")?)
+            .and_then(|d| {
+                d.and_hms_micro_opt(
+                    attr("hour").ok()?,
+                    attr("minute").ok()?,
+                    attr("second").ok()?,
+                    attr("microsecond").ok()?,
+                )
+            })
+            .ok_or_else(|| PyErr::new::<exc::ValueError, _>(py, "invalid datetime"))?;
+
+        let offset = obj.call_method(py, "utcoffset", cpython::NoArgs, None)?;
+        if offset == py.None() {
+            return Ok(Value::Timestamp(datetime));
+        }
+
+        let seconds = offset
+            .call_method(py, "total_seconds", cpython::NoArgs, None)?
+            .extract::<f64>(py)?;
+        return FixedOffset::east_opt(seconds as i32)
+            .and_then(|offset| offset.from_local_datetime(&datetime).single())
+            .map(Value::TimestampTz)
+            .ok_or_else(|| PyErr::new::<exc::ValueError, _>(py, "invalid timezone offset"));
+    }
+
+    if is_instance(py, obj, "datetime", "date")? {
+        return NaiveDate::from_ymd_opt(attr("year")? as i32, attr("month")?, attr("day")?)
+            .map(Value::Date)
+            .ok_or_else(|| PyErr::new::<exc::ValueError, _>(py, "invalid date"));
+    }
+
+    if is_instance(py, obj, "datetime", "time")? {
+        return NaiveTime::from_hms_micro_opt(
+            attr("hour")?,
+            attr("minute")?,
+            attr("second")?,
+            attr("microsecond")?,
+        )
+        .map(Value::Time)
+        .ok_or_else(|| PyErr::new::<exc::ValueError, _>(py, "invalid time"));
+    }
+
+    Err(PyErr::new::<exc::TypeError, _>(
+        py,
+        format!(
+            "Cannot convert python type {} into a value",
+            obj.get_type(py).name(py)
+        ),
+    ))
+}
+
+/// Build a `datetime.datetime` from the naive date and time, in the python timezone
+fn py_datetime(py: Python, d: &NaiveDateTime, tz: PyObject) -> PyResult<PyObject> {
+    py.import("datetime")?.call(
+        py,
+        "datetime",
+        (
+            d.year(),
+            d.month(),
+            d.day(),
+            d.hour(),
+            d.minute(),
+            d.second(),
+            d.nanosecond() / 1000,
+            tz,
+        ),
+        None,
+    )
+}
+
+/// Check if the object is an instance of the class from the python module
+fn is_instance(py: Python, obj: &PyObject, module: &str, class: &str) -> PyResult<bool> {
+    let class = py.import(module)?.get(py, class)?.cast_into::<PyType>(py)?;
+    Ok(class.is_instance(py, obj))
+}
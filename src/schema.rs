@@ -1,16 +1,80 @@
-use crate::Value;
+use crate::{
+    traits::TypeOf,
+    value::number::{digits, Num},
+    Value,
+};
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use snafu::Snafu;
+use std::borrow::Cow;
 use std::collections::hash_map::HashMap;
+use std::convert::TryFrom;
 use std::ops::Index;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, PartialOrd)]
+/// The names of the types, the parameterised types take the types and sizes in the brackets
+pub const TYPE_NAMES: &[&str] = &[
+    "boolean",
+    "string",
+    "array",
+    "array<T>",
+    "object",
+    "map<T>",
+    "struct<name:T,...>",
+    "date",
+    "time",
+    "timestamp",
+    "timestamptz",
+    "binary",
+    "uint8",
+    "uint16",
+    "uint32",
+    "uint64",
+    "int8",
+    "int16",
+    "int32",
+    "int64",
+    "float",
+    "double",
+    "decimal",
+    "decimal(p,s)",
+    "any",
+    "null",
+];
+
+#[derive(Debug, Snafu, PartialEq)]
+pub enum Error {
+    #[snafu(display("{} is not a valid type, the valid types are {}", name, TYPE_NAMES.join(", ")))]
+    InvalidType { name: String },
+
+    #[snafu(display("{} is the name or an alias of both {} and {}", name, first, second))]
+    AliasCollision {
+        name: String,
+        first: String,
+        second: String,
+    },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub enum DataType {
     Bool,
     String,
-    Array,
-    Map,
+    /// An array of values of the type, `array<any>` when the elements can be of any type
+    Array(Box<DataType>),
+    /// A map with values of the type
+    Map(Box<DataType>),
+    /// A map with named fields of their own types, keys that aren't in the struct are not allowed
+    Struct(Vec<(String, DataType)>),
+    /// A calendar date
     Date,
+    /// A time of day
+    Time,
+    /// A date and time without a timezone
+    Timestamp,
+    /// A date and time at an offset from UTC
+    TimestampTz,
     Binary,
 
     Uint8,
@@ -24,7 +88,8 @@ pub enum DataType {
 
     Float,
     Double,
-    Decimal,
+    /// A decimal with an optional precision and scale, decimals of any size when there is none
+    Decimal(Option<(u32, u32)>),
 
     /// A field can be weakly typed with "any"
     Any,
@@ -33,13 +98,52 @@ pub enum DataType {
 }
 
 impl DataType {
-    pub fn as_str(&self) -> &str {
-        match self {
+    /// An array of the type
+    pub fn array(dtype: DataType) -> DataType {
+        DataType::Array(Box::new(dtype))
+    }
+
+    /// A map with values of the type
+    pub fn map(dtype: DataType) -> DataType {
+        DataType::Map(Box::new(dtype))
+    }
+
+    /// A struct with the fields
+    pub fn structure<S: Into<String>>(fields: Vec<(S, DataType)>) -> DataType {
+        DataType::Struct(
+            fields
+                .into_iter()
+                .map(|(name, dtype)| (name.into(), dtype))
+                .collect(),
+        )
+    }
+
+    /// A decimal with the number of digits and the number of them after the decimal point
+    pub fn decimal(precision: u32, scale: u32) -> DataType {
+        DataType::Decimal(Some((precision, scale)))
+    }
+
+    /// The name of the type, nested types and decimals include their parameters, such as
+    /// `array<int32>`, `struct<a:string,b:int64>` and `decimal(10,2)`
+    pub fn as_str(&self) -> Cow<'static, str> {
+        let name = match self {
             DataType::Bool => "boolean",
             DataType::String => "string",
-            DataType::Array => "array",
-            DataType::Map => "object",
+            DataType::Array(dtype) if dtype.is_any() => "array",
+            DataType::Array(dtype) => return format!("array<{}>", dtype).into(),
+            DataType::Map(dtype) if dtype.is_any() => "object",
+            DataType::Map(dtype) => return format!("map<{}>", dtype).into(),
+            DataType::Struct(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, dtype)| format!("{}:{}", name, dtype))
+                    .collect::<Vec<String>>();
+                return format!("struct<{}>", fields.join(",")).into();
+            }
             DataType::Date => "date",
+            DataType::Time => "time",
+            DataType::Timestamp => "timestamp",
+            DataType::TimestampTz => "timestamptz",
             DataType::Binary => "binary",
             DataType::Uint8 => "uint8",
             DataType::Uint16 => "uint16",
@@ -51,46 +155,51 @@ impl DataType {
             DataType::Int64 => "int64",
             DataType::Float => "float",
             DataType::Double => "double",
-            DataType::Decimal => "decimal",
+            DataType::Decimal(None) => "decimal",
+            DataType::Decimal(Some((precision, scale))) => {
+                return format!("decimal({},{})", precision, scale).into()
+            }
             DataType::Any => "any",
             DataType::Null => "null",
-        }
+        };
+
+        name.into()
     }
 
     pub fn has_default(&self) -> bool {
-        match self {
-            DataType::Bool => true,
-            DataType::String => true,
-            DataType::Uint8 => true,
-            DataType::Uint16 => true,
-            DataType::Uint32 => true,
-            DataType::Uint64 => true,
-            DataType::Int8 => true,
-            DataType::Int16 => true,
-            DataType::Int32 => true,
-            DataType::Int64 => true,
-            DataType::Float => true,
-            DataType::Double => true,
-            DataType::Decimal => true,
-            _ => false,
-        }
+        matches!(
+            self,
+            DataType::Bool
+                | DataType::String
+                | DataType::Uint8
+                | DataType::Uint16
+                | DataType::Uint32
+                | DataType::Uint64
+                | DataType::Int8
+                | DataType::Int16
+                | DataType::Int32
+                | DataType::Int64
+                | DataType::Float
+                | DataType::Double
+                | DataType::Decimal(_)
+        )
     }
 
     pub fn is_numeric(&self) -> bool {
-        match self {
+        matches!(
+            self,
             DataType::Int8
-            | DataType::Int16
-            | DataType::Int32
-            | DataType::Int64
-            | DataType::Uint8
-            | DataType::Uint16
-            | DataType::Uint32
-            | DataType::Uint64
-            | DataType::Float
-            | DataType::Decimal
-            | DataType::Double => true,
-            _ => false,
-        }
+                | DataType::Int16
+                | DataType::Int32
+                | DataType::Int64
+                | DataType::Uint8
+                | DataType::Uint16
+                | DataType::Uint32
+                | DataType::Uint64
+                | DataType::Float
+                | DataType::Decimal(_)
+                | DataType::Double
+        )
     }
 
     /// Get the default value for the data type or return null
@@ -102,9 +211,187 @@ impl DataType {
         self == &DataType::Any
     }
 
+    /// True for dates, times and timestamps
+    pub fn is_temporal(&self) -> bool {
+        matches!(
+            self,
+            DataType::Date | DataType::Time | DataType::Timestamp | DataType::TimestampTz
+        )
+    }
+
     pub fn is_null(&self) -> bool {
         self == &DataType::Null
     }
+
+    /// True when the value is of the type without needing a cast. Nulls are of every type, the
+    /// elements of arrays and the values of maps and structs have to be of their types too, and
+    /// decimals have to have the scale and fit in the precision.
+    pub fn matches(&self, value: &Value) -> bool {
+        match (self, value) {
+            (DataType::Any, _) | (_, Value::Null) => true,
+            (DataType::Array(dtype), Value::Array(values)) => {
+                values.iter().all(|value| dtype.matches(value))
+            }
+            (DataType::Map(dtype), Value::Map(map)) => {
+                map.iter().all(|(_, value)| dtype.matches(value))
+            }
+            (DataType::Struct(fields), Value::Map(map)) => map.iter().all(|(key, value)| {
+                fields
+                    .iter()
+                    .any(|(name, dtype)| name == key && dtype.matches(value))
+            }),
+            (DataType::Decimal(size), Value::Number(n)) => match (size, n.inner()) {
+                (None, Num::Decimal(_)) => true,
+                (Some((precision, scale)), Num::Decimal(d)) => {
+                    d.scale() == *scale && digits(d) <= *precision
+                }
+                _ => false,
+            },
+            (dtype, value) => &value.type_of() == dtype,
+        }
+    }
+
+    /// The narrowest type that both types' values are of: nulls are of the other type, arrays and
+    /// maps unify their element types, structs combine their fields, and anything else that
+    /// differs is any.
+    pub fn unify(&self, other: &DataType) -> DataType {
+        match (self, other) {
+            (DataType::Null, dtype) | (dtype, DataType::Null) => dtype.clone(),
+            (a, b) if a == b => a.clone(),
+            (DataType::Array(a), DataType::Array(b)) => DataType::array(a.unify(b)),
+            (DataType::Map(a), DataType::Map(b)) => DataType::map(a.unify(b)),
+            (DataType::Struct(a), DataType::Struct(b)) => {
+                let mut fields = a.clone();
+                for (name, dtype) in b {
+                    match fields.iter_mut().find(|(n, _)| n == name) {
+                        Some((_, existing)) => *existing = existing.unify(dtype),
+                        None => fields.push((name.clone(), dtype.clone())),
+                    }
+                }
+                DataType::Struct(fields)
+            }
+            (DataType::Map(dtype), DataType::Struct(fields))
+            | (DataType::Struct(fields), DataType::Map(dtype)) => DataType::map(
+                fields
+                    .iter()
+                    .fold((**dtype).clone(), |acc, (_, dtype)| acc.unify(dtype)),
+            ),
+            (DataType::Decimal(_), DataType::Decimal(_)) => DataType::Decimal(None),
+            _ => DataType::Any,
+        }
+    }
+
+    /// Parse a type's name, see as_str for the syntax. `array` and `object` are arrays and maps of
+    /// any type, and the names are case insensitive with common aliases from SQL and rust.
+    fn parse(name: &str) -> Result<DataType> {
+        let name = name.trim();
+
+        let dtype = match name.to_ascii_lowercase().as_str() {
+            "boolean" | "bool" => DataType::Bool,
+            "string" | "text" | "varchar" | "char" | "str" => DataType::String,
+            "array" => DataType::array(DataType::Any),
+            "object" | "map" => DataType::map(DataType::Any),
+            "date" => DataType::Date,
+            "time" => DataType::Time,
+            "timestamp" | "datetime" => DataType::Timestamp,
+            "timestamptz" | "timestamp with time zone" => DataType::TimestampTz,
+            "binary" | "bytes" | "blob" | "bytea" => DataType::Binary,
+            "uint8" | "u8" => DataType::Uint8,
+            "uint16" | "u16" => DataType::Uint16,
+            "uint32" | "u32" => DataType::Uint32,
+            "uint64" | "u64" => DataType::Uint64,
+            "int8" | "i8" | "tinyint" => DataType::Int8,
+            "int16" | "i16" | "smallint" => DataType::Int16,
+            "int32" | "i32" | "int" | "integer" => DataType::Int32,
+            "int64" | "i64" | "bigint" => DataType::Int64,
+            "float" | "f32" | "real" => DataType::Float,
+            "double" | "f64" | "double precision" => DataType::Double,
+            "decimal" | "numeric" => DataType::Decimal(None),
+            "any" => DataType::Any,
+            "null" => DataType::Null,
+            _ => return parse_parameterised(name),
+        };
+
+        Ok(dtype)
+    }
+}
+
+fn parse_parameterised(name: &str) -> Result<DataType> {
+    let invalid = || Error::InvalidType {
+        name: name.to_string(),
+    };
+
+    let open = name.find(['<', '(']).ok_or_else(invalid)?;
+    let kind = name[..open].trim().to_ascii_lowercase();
+
+    if name[open..].starts_with('(') {
+        let params = name[open + 1..]
+            .strip_suffix(')')
+            .ok_or_else(invalid)?
+            .split(',')
+            .map(|n| n.trim().parse::<u32>())
+            .collect::<Result<Vec<u32>, _>>()
+            .map_err(|_| invalid())?;
+
+        return match (kind.as_str(), params.as_slice()) {
+            ("decimal", [precision, scale]) | ("numeric", [precision, scale])
+                if scale <= precision =>
+            {
+                Ok(DataType::decimal(*precision, *scale))
+            }
+            ("decimal", [precision]) | ("numeric", [precision]) => {
+                Ok(DataType::decimal(*precision, 0))
+            }
+            ("varchar", [_]) | ("char", [_]) => Ok(DataType::String),
+            _ => Err(invalid()),
+        };
+    }
+
+    let inner = name[open + 1..].strip_suffix('>').ok_or_else(invalid)?;
+
+    match kind.as_str() {
+        "array" => Ok(DataType::array(DataType::parse(inner)?)),
+        "map" => Ok(DataType::map(DataType::parse(inner)?)),
+        "struct" => split_top_level(inner)
+            .into_iter()
+            .filter(|field| !field.trim().is_empty())
+            .map(|field| {
+                let colon = field.find(':').ok_or_else(invalid)?;
+                let field_name = field[..colon].trim();
+                if field_name.is_empty() {
+                    return Err(invalid());
+                }
+                Ok((
+                    field_name.to_string(),
+                    DataType::parse(&field[colon + 1..])?,
+                ))
+            })
+            .collect::<Result<Vec<(String, DataType)>>>()
+            .map(DataType::Struct),
+        _ => Err(invalid()),
+    }
+}
+
+/// Split on the commas that aren't inside of angle brackets or parentheses
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0i32;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(&s[start..]);
+    parts
 }
 
 impl std::fmt::Display for DataType {
@@ -113,34 +400,65 @@ impl std::fmt::Display for DataType {
     }
 }
 
-impl From<&str> for DataType {
-    fn from(name: &str) -> DataType {
-        match name {
-            "boolean" => DataType::Bool,
-            "string" => DataType::String,
-            "array" => DataType::Array,
-            "object" => DataType::Map,
-            "date" => DataType::Date,
-            "binary" => DataType::Binary,
-            "uint8" => DataType::Uint8,
-            "uint16" => DataType::Uint16,
-            "uint32" => DataType::Uint32,
-            "uint64" => DataType::Uint64,
-            "int8" => DataType::Int8,
-            "int16" => DataType::Int16,
-            "int32" => DataType::Int32,
-            "int64" => DataType::Int64,
-            "float" => DataType::Float,
-            "double" => DataType::Double,
-            "decimal" => DataType::Decimal,
-            "any" => DataType::Any,
-            "null" => DataType::Null,
-            _ => panic!("{} is not a valid type", name),
-        }
+impl FromStr for DataType {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<DataType> {
+        DataType::parse(name)
     }
 }
 
-// TODO fields with aliases
+impl TryFrom<&str> for DataType {
+    type Error = Error;
+
+    fn try_from(name: &str) -> Result<DataType> {
+        DataType::parse(name)
+    }
+}
+
+/// Types are serialized as their names
+impl Serialize for DataType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for DataType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<DataType, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(de::Error::custom)
+    }
+}
+
+/// A rule the values of a field have to follow, checked by Schema::validate
+#[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub enum Constraint {
+    /// Values are at least the value, numbers are compared by value regardless of their type
+    Min(Value),
+    /// Values are at most the value
+    Max(Value),
+    /// String values match the regex
+    Pattern(String),
+    /// Values are one of the values
+    AllowedValues(Vec<Value>),
+    /// Strings, arrays and binary values are no longer than the length
+    MaxLength(usize),
+    /// No two values are the same, nulls are ignored
+    Unique,
+}
+
+impl Constraint {
+    pub fn name(&self) -> &str {
+        match self {
+            Constraint::Min(_) => "min",
+            Constraint::Max(_) => "max",
+            Constraint::Pattern(_) => "pattern",
+            Constraint::AllowedValues(_) => "allowed_values",
+            Constraint::MaxLength(_) => "max_length",
+            Constraint::Unique => "unique",
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize, Serialize)]
 pub struct Field {
@@ -149,6 +467,11 @@ pub struct Field {
     pub(crate) default: Option<Value>,
     pub(crate) doc: Option<String>,
     pub(crate) dtype: DataType,
+    #[serde(default)]
+    pub(crate) constraints: Vec<Constraint>,
+    /// Other names the field has been known by
+    #[serde(default)]
+    pub(crate) aliases: Vec<String>,
 }
 
 impl Field {
@@ -159,6 +482,8 @@ impl Field {
             default: None,
             doc: None,
             dtype: DataType::Any,
+            constraints: vec![],
+            aliases: vec![],
         }
     }
 
@@ -169,12 +494,73 @@ impl Field {
             nullable: true,
             default: None,
             doc: None,
+            constraints: vec![],
+            aliases: vec![],
         }
     }
 
+    /// Set whether the field accepts nulls, fields are nullable by default
+    pub fn with_nullable(mut self, nullable: bool) -> Field {
+        self.nullable = nullable;
+        self
+    }
+
+    /// Set the value used in place of nulls when coercing data into the field
+    pub fn with_default(mut self, default: Value) -> Field {
+        self.default = Some(default);
+        self
+    }
+
+    /// Add a constraint that the field's values are validated against
+    pub fn with_constraint(mut self, constraint: Constraint) -> Field {
+        self.constraints.push(constraint);
+        self
+    }
+
+    /// Set the field's description
+    pub fn with_doc<S: Into<String>>(mut self, doc: S) -> Field {
+        self.doc = Some(doc.into());
+        self
+    }
+
+    /// Add another name that the field is known by
+    pub fn with_alias<S: Into<String>>(mut self, alias: S) -> Field {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
+    /// True when the name is the field's name or one of its aliases
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name == name || self.aliases.iter().any(|alias| alias == name)
+    }
+
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
     pub fn dtype(&self) -> &DataType {
         &self.dtype
     }
+
+    pub fn is_nullable(&self) -> bool {
+        self.nullable
+    }
+
+    pub fn default(&self) -> Option<&Value> {
+        self.default.as_ref()
+    }
 }
 
 impl From<String> for Field {
@@ -201,29 +587,62 @@ impl From<(&str, DataType)> for Field {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "SchemaData", into = "SchemaData")]
 pub struct Schema {
+    pub(crate) name: Option<String>,
+    pub(crate) doc: Option<String>,
+    fields: Vec<Field>,
+    index: HashMap<String, usize>,
+    /// The fields' aliases, names take precedence over aliases
+    aliases: HashMap<String, usize>,
+    /// The fields whose values identify a row, they can't be null
+    primary_key: Option<Vec<String>>,
+    /// Sets of fields whose values are unique when none of them are null
+    unique_keys: Vec<Vec<String>>,
+}
+
+/// A serialized schema, without the index and aliases which are rebuilt from the fields
+#[derive(Deserialize, Serialize)]
+struct SchemaData {
     name: Option<String>,
     doc: Option<String>,
     fields: Vec<Field>,
-    index: HashMap<String, usize>,
+    #[serde(default)]
+    primary_key: Option<Vec<String>>,
+    #[serde(default)]
+    unique_keys: Vec<Vec<String>>,
 }
 
-// TODO evaluate possibility of aliases for the fields
-// TODO evaluate a way to index the fields by order too - as of right now getting a field by order
-// requires iterating over all the values. It could be better to store the fields in a vec and have
-// a map that points the name/string index position
-impl Default for Schema {
-    fn default() -> Self {
-        Schema {
-            name: None,
-            fields: Vec::new(),
-            index: HashMap::new(),
-            doc: None,
+impl TryFrom<SchemaData> for Schema {
+    type Error = Error;
+
+    fn try_from(data: SchemaData) -> Result<Schema> {
+        let mut schema = Schema::with_fields(data.fields)?;
+        schema.name = data.name;
+        schema.doc = data.doc;
+        schema.primary_key = data.primary_key;
+        schema.unique_keys = data.unique_keys;
+        Ok(schema)
+    }
+}
+
+impl From<Schema> for SchemaData {
+    fn from(schema: Schema) -> SchemaData {
+        SchemaData {
+            name: schema.name,
+            doc: schema.doc,
+            fields: schema.fields,
+            primary_key: schema.primary_key,
+            unique_keys: schema.unique_keys,
         }
     }
 }
 
+// TODO evaluate a way to index the fields by order too - as of right now getting a field by order
+// requires iterating over all the values. It could be better to store the fields in a vec and have
+// a map that points the name/string index position
+
 impl Schema {
     pub fn new() -> Schema {
         Schema::default()
@@ -235,34 +654,133 @@ impl Schema {
             doc: None,
             fields: Vec::with_capacity(size),
             index: HashMap::with_capacity(size),
+            aliases: HashMap::new(),
+            primary_key: None,
+            unique_keys: vec![],
         }
     }
 
-    pub fn with_fields(fields: Vec<Field>) -> Schema {
+    /// Set the schema's name
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Schema {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the schema's description
+    pub fn with_doc<S: Into<String>>(mut self, doc: S) -> Schema {
+        self.doc = Some(doc.into());
+        self
+    }
+
+    /// Set the fields that identify a row. Dataframes with the schema reject rows with nulls in
+    /// the fields or with the same values as another row.
+    pub fn with_primary_key<S: AsRef<str>>(mut self, fields: &[S]) -> Schema {
+        self.primary_key = Some(fields.iter().map(|f| f.as_ref().to_string()).collect());
+        self
+    }
+
+    /// Add a set of fields whose values can't be the same as another row's. Rows with a null in
+    /// any of the fields aren't checked.
+    pub fn with_unique_key<S: AsRef<str>>(mut self, fields: &[S]) -> Schema {
+        self.unique_keys
+            .push(fields.iter().map(|f| f.as_ref().to_string()).collect());
+        self
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
+    pub fn primary_key(&self) -> Option<&[String]> {
+        self.primary_key.as_deref()
+    }
+
+    pub fn unique_keys(&self) -> &[Vec<String>] {
+        &self.unique_keys
+    }
+
+    /// Create the schema from the fields, it errors when a field's name or alias is already the
+    /// name or an alias of another field
+    pub fn with_fields(fields: Vec<Field>) -> Result<Schema> {
         let mut schema = Schema::with_size(fields.len());
 
         for field in fields {
-            schema.index.insert(field.name.clone(), schema.fields.len());
-            schema.fields.push(field);
+            schema.push_field(field)?;
         }
 
-        schema
+        Ok(schema)
     }
 
-    pub fn add_field<F: Into<Field> + Clone>(&mut self, field: F) -> usize {
+    pub fn add_field<F: Into<Field>>(&mut self, field: F) -> Result<usize> {
         self.push_field(field.into())
     }
 
-    pub fn push_field(&mut self, field: Field) -> usize {
+    /// Add the field, it errors when the field's name or alias is already the name or an alias of
+    /// another field
+    pub fn push_field(&mut self, field: Field) -> Result<usize> {
+        for name in std::iter::once(&field.name).chain(&field.aliases) {
+            if let Some(other) = self.find_index(name) {
+                return Err(Error::AliasCollision {
+                    name: name.clone(),
+                    first: self.fields[*other].name.clone(),
+                    second: field.name,
+                });
+            }
+        }
+
+        Ok(self.push_field_unchecked(field))
+    }
+
+    /// push_field without checking for collisions: a field with the name of another one replaces
+    /// it in lookups, and aliases that are already a name or an alias are ignored
+    pub fn push_field_unchecked(&mut self, field: Field) -> usize {
         let index = self.fields.len();
         self.index.insert(field.name.clone(), index);
+        self.aliases.remove(&field.name);
+        for alias in &field.aliases {
+            if !self.index.contains_key(alias) {
+                self.aliases.entry(alias.clone()).or_insert(index);
+            }
+        }
         self.fields.push(field);
 
         index
     }
 
+    /// Map the names, such as a file's headers, to the names of the fields they are a name or an
+    /// alias of. Names that aren't in the schema are kept, and it errors when two of the names
+    /// are of the same field.
+    pub fn canonical_names<S: AsRef<str>>(&self, names: &[S]) -> Result<Vec<String>> {
+        let mut seen: HashMap<usize, &str> = HashMap::new();
+
+        names
+            .iter()
+            .map(|name| {
+                let name = name.as_ref();
+                let index = match self.find_index(name) {
+                    Some(index) => *index,
+                    None => return Ok(name.to_string()),
+                };
+
+                if let Some(first) = seen.insert(index, name) {
+                    return Err(Error::AliasCollision {
+                        name: self.fields[index].name.clone(),
+                        first: first.to_string(),
+                        second: name.to_string(),
+                    });
+                }
+
+                Ok(self.fields[index].name.clone())
+            })
+            .collect()
+    }
+
     pub fn get_field_full(&self, name: &str) -> Option<(&usize, &Field)> {
-        self.index.get(name).and_then(|index| {
+        self.find_index(name).and_then(|index| {
             if let Some(field) = self.fields.get(*index) {
                 return Some((index, field));
             }
@@ -270,18 +788,18 @@ impl Schema {
         })
     }
 
+    /// Get the field by its name or one of its aliases
     pub fn get_field(&self, name: &str) -> Option<&Field> {
-        self.index
-            .get(name)
+        self.find_index(name)
             .and_then(|index| self.fields.get(*index))
     }
 
     pub fn has_field(&self, name: &str) -> bool {
-        self.index.contains_key(name)
+        self.find_index(name).is_some()
     }
 
     pub fn get_field_mut(&mut self, name: &str) -> Option<&mut Field> {
-        let index = *self.index.get(name)?;
+        let index = *self.find_index(name)?;
         self.fields.get_mut(index)
     }
 
@@ -290,8 +808,13 @@ impl Schema {
         self.get_field(&name)
     }
 
+    /// The position of the field with the name, or with the alias when no field has the name
     pub fn find_index(&self, name: &str) -> Option<&usize> {
-        self.index.get(name)
+        self.index.get(name).or_else(|| self.aliases.get(name))
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
     }
 
     pub fn find_by_index(&self, index: usize) -> Option<&Field> {
@@ -306,28 +829,56 @@ impl Schema {
     }
 
     pub fn field_exists(&self, name: &str) -> bool {
-        self.index.contains_key(name)
+        self.has_field(name)
     }
 
     pub fn remove(&mut self, name: &str) -> Option<Field> {
-        self.index
-            .remove(name)
-            .and_then(|index| Some(self.fields.remove(index)))
+        let index = *self.find_index(name)?;
+        let field = self.fields.remove(index);
+
+        // keys can't refer to the removed field
+        if let Some(key) = &self.primary_key {
+            if key.contains(&field.name) {
+                self.primary_key = None;
+            }
+        }
+        self.unique_keys.retain(|key| !key.contains(&field.name));
+
+        self.reindex();
+        Some(field)
     }
 
     pub fn rename_field(&mut self, old_name: &str, new_name: &str) -> Option<&String> {
-        let index = *self.index.get(old_name)?;
+        let index = *self.find_index(old_name)?;
 
-        self.index.remove(old_name);
-        self.index.insert(new_name.to_string(), index);
+        let old_name = std::mem::replace(&mut self.fields[index].name, new_name.to_string());
+        for key in self
+            .primary_key
+            .iter_mut()
+            .chain(self.unique_keys.iter_mut())
+        {
+            for name in key.iter_mut().filter(|name| **name == old_name) {
+                *name = new_name.to_string();
+            }
+        }
+        self.reindex();
+
+        Some(&self.fields[index].name)
+    }
 
-        let mut field = &mut self.fields[index];
-        field.name = new_name.to_string();
-        Some(&field.name)
+    /// Rebuild the indexes of the names and aliases after the fields changed
+    fn reindex(&mut self) {
+        let fields = std::mem::take(&mut self.fields);
+        self.index.clear();
+        self.aliases.clear();
+
+        for field in fields {
+            self.push_field_unchecked(field);
+        }
     }
 
     pub fn is_weak(&self) -> bool {
-        self.fields.iter().any(|field| field.dtype == DataType::Any)
+        self.fields.iter().any(|field| field.dtype.is_any())
     }
 
     pub fn len(&self) -> usize {
@@ -340,18 +891,22 @@ impl Schema {
 
     pub fn clear(&mut self) {
         self.fields.clear();
+        self.index.clear();
+        self.aliases.clear();
         self.name = None;
         self.doc = None;
+        self.primary_key = None;
+        self.unique_keys.clear();
     }
 }
 
 impl From<&[&str]> for Schema {
     fn from(columns: &[&str]) -> Schema {
-        let fields = columns
-            .iter()
-            .map(|name| Field::new(name.to_string()))
-            .collect::<Vec<Field>>();
-        Schema::with_fields(fields)
+        let mut schema = Schema::with_size(columns.len());
+        for name in columns {
+            schema.push_field_unchecked(Field::new(name.to_string()));
+        }
+        schema
     }
 }
 
@@ -375,18 +930,265 @@ mod schema_tests {
         dbg!(s);
     }
 
+    #[test]
+    fn it_parses_nested_types() {
+        let dtype: DataType = "struct<a:string, b:array<int32>, c:map<decimal(10,2)>>"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            dtype,
+            DataType::structure(vec![
+                ("a", DataType::String),
+                ("b", DataType::array(DataType::Int32)),
+                ("c", DataType::map(DataType::decimal(10, 2))),
+            ])
+        );
+        assert_eq!(
+            dtype.to_string(),
+            "struct<a:string,b:array<int32>,c:map<decimal(10,2)>>"
+        );
+        assert_eq!(dtype.as_str().parse(), Ok(dtype));
+
+        assert_eq!("array".parse(), Ok(DataType::array(DataType::Any)));
+        assert_eq!("object".parse(), Ok(DataType::map(DataType::Any)));
+        assert_eq!(DataType::array(DataType::Any).to_string(), "array");
+
+        assert!(DataType::parse("array<int32").is_err());
+        assert!(DataType::parse("decimal(2,4)").is_err());
+        assert!(DataType::parse("struct<a>").is_err());
+    }
+
+    #[test]
+    fn it_resolves_field_aliases() {
+        let mut schema = Schema::with_fields(vec![Field::new("customer_id")
+            .with_alias("cust_id")
+            .with_alias("CustomerID")])
+        .unwrap();
+        assert!(matches!(
+            schema.push_field(Field::new("name").with_alias("customer_id")),
+            Err(Error::AliasCollision { .. })
+        ));
+        schema.push_field_unchecked(Field::new("name").with_alias("customer_id"));
+
+        assert_eq!(schema.find_index("CustomerID"), Some(&0));
+        assert_eq!(schema.find_index("customer_id"), Some(&0));
+        assert_eq!(
+            schema.get_field("cust_id").map(Field::name),
+            Some("customer_id")
+        );
+        assert!(schema.has_field("cust_id"));
+
+        let df = crate::DataFrame::with_schema(schema.clone());
+        let row = vec![Value::from(1), Value::from("a")];
+        let view = crate::views::SubView::new(&df.schema, std::borrow::Cow::Borrowed(&row));
+        assert_eq!(crate::Get::get(&view, "cust_id"), Some(&Value::from(1)));
+
+        let mut renamed = schema.clone();
+        renamed.rename_field("cust_id", "id");
+        assert_eq!(renamed.find_index("CustomerID"), Some(&0));
+        assert_eq!(renamed.find_index("customer_id"), Some(&1));
+        assert_eq!(renamed.find_index("id"), Some(&0));
+
+        assert!(matches!(
+            Schema::with_fields(vec![
+                Field::new("customer_id").with_alias("id"),
+                Field::new("order_id").with_alias("id"),
+            ]),
+            Err(Error::AliasCollision { .. })
+        ));
+        assert!(matches!(
+            schema.canonical_names(&["cust_id", "CustomerID"]),
+            Err(Error::AliasCollision { .. })
+        ));
+    }
+
+    #[test]
+    fn it_builds_schema_metadata() {
+        let schema = Schema::with_fields(vec![
+            Field::with_type("id", DataType::Int64).with_doc("The order's id"),
+            Field::with_type("email", DataType::String),
+        ])
+        .unwrap()
+        .with_name("orders")
+        .with_doc("The orders")
+        .with_primary_key(&["id"])
+        .with_unique_key(&["email"]);
+
+        assert_eq!(schema.name(), Some("orders"));
+        assert_eq!(schema.doc(), Some("The orders"));
+        assert_eq!(schema[0].doc(), Some("The order's id"));
+        assert_eq!(schema.primary_key(), Some(&["id".to_string()][..]));
+
+        let mut renamed = schema.clone();
+        renamed.rename_field("email", "mail");
+        assert_eq!(renamed.unique_keys(), &[vec!["mail".to_string()]]);
+        renamed.remove("id");
+        assert_eq!(renamed.primary_key(), None);
+    }
+
+    #[test]
+    fn it_deserializes_schemas() {
+        // a schema from before fields had constraints and aliases, when the index was serialized
+        let json = serde_json::json!({
+            "name": null,
+            "doc": null,
+            "fields": [
+                { "name": "id", "nullable": false, "default": null, "doc": null, "dtype": "int64" }
+            ],
+            "index": { "id": 0 }
+        });
+        let schema: Schema = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            schema,
+            Schema::with_fields(vec![
+                Field::with_type("id", DataType::Int64).with_nullable(false)
+            ])
+            .unwrap()
+        );
+
+        // the lookups come from the fields, not a serialized index
+        let json = serde_json::json!({
+            "name": "orders",
+            "doc": null,
+            "fields": [
+                {
+                    "name": "customer_id",
+                    "nullable": true,
+                    "default": null,
+                    "doc": null,
+                    "dtype": "any",
+                    "aliases": ["cust_id"]
+                }
+            ],
+            "index": { "wrong": 0 }
+        });
+        let schema: Schema = serde_json::from_value(json).unwrap();
+        assert_eq!(schema.find_index("cust_id"), Some(&0));
+        assert_eq!(schema.find_index("wrong"), None);
+        assert_eq!(schema.name(), Some("orders"));
+
+        let schema = Schema::with_fields(vec![Field::new("a").with_alias("b")])
+            .unwrap()
+            .with_primary_key(&["a"]);
+        let bytes = bincode::serialize(&schema).unwrap();
+        assert_eq!(bincode::deserialize::<Schema>(&bytes).unwrap(), schema);
+
+        let json = serde_json::to_value(&schema).unwrap();
+        assert!(json.get("index").is_none() && json.get("aliases").is_none());
+
+        // colliding aliases are rejected rather than dropped
+        let json = serde_json::json!({
+            "name": null,
+            "doc": null,
+            "fields": [
+                { "name": "a", "nullable": true, "default": null, "doc": null, "dtype": "any" },
+                {
+                    "name": "b",
+                    "nullable": true,
+                    "default": null,
+                    "doc": null,
+                    "dtype": "any",
+                    "aliases": ["a"]
+                }
+            ]
+        });
+        assert!(serde_json::from_value::<Schema>(json).is_err());
+    }
+
+    #[test]
+    fn it_parses_aliases() {
+        let aliases = vec![
+            ("int", DataType::Int32),
+            ("INTEGER", DataType::Int32),
+            ("bigint", DataType::Int64),
+            ("bool", DataType::Bool),
+            ("text", DataType::String),
+            ("varchar(255)", DataType::String),
+            ("timestamp", DataType::Timestamp),
+            ("numeric(10, 2)", DataType::decimal(10, 2)),
+            ("f64", DataType::Double),
+            ("u32", DataType::Uint32),
+            ("array<bigint>", DataType::array(DataType::Int64)),
+        ];
+        for (name, dtype) in aliases {
+            assert_eq!(DataType::try_from(name), Ok(dtype));
+        }
+
+        let err = "int128".parse::<DataType>().unwrap_err();
+        assert_eq!(
+            err,
+            Error::InvalidType {
+                name: "int128".to_string()
+            }
+        );
+        assert!(err.to_string().contains("int64"));
+    }
+
+    #[test]
+    fn it_serializes_type_names() {
+        let field = Field::with_type("a", DataType::decimal(10, 2));
+        let json = serde_json::to_value(&field).unwrap();
+        assert_eq!(json["dtype"], "decimal(10,2)");
+
+        let mut json = json;
+        json["dtype"] = "numeric(4,1)".into();
+        let field: Field = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(field.dtype, DataType::decimal(4, 1));
+
+        json["dtype"] = "money".into();
+        assert!(serde_json::from_value::<Field>(json).is_err());
+    }
+
+    #[test]
+    fn it_types_nested_values() {
+        use crate::traits::TypeOf;
+
+        let array = Value::Array(vec![Value::from(1), Value::Null, Value::from(2)]);
+        assert_eq!(array.type_of(), DataType::array(DataType::Int32));
+        assert_eq!(
+            Value::Array(vec![Value::from(1), Value::from("a")]).type_of(),
+            DataType::array(DataType::Any)
+        );
+
+        let mut map = crate::value::map::Map::new();
+        map.insert("a", Value::from("x"));
+        let map = Value::Map(map);
+        assert_eq!(
+            map.type_of(),
+            DataType::structure(vec![("a", DataType::String)])
+        );
+
+        let dtype = DataType::structure(vec![("a", DataType::String), ("b", DataType::Int8)]);
+        assert!(dtype.matches(&map));
+        assert!(!DataType::structure(vec![("b", DataType::Int8)]).matches(&map));
+        assert!(DataType::map(DataType::String).matches(&map));
+        assert!(!DataType::array(DataType::Int64).matches(&array));
+
+        let decimal = Value::Number(crate::Number(Num::Decimal(rust_decimal::Decimal::new(
+            12345, 2,
+        ))));
+        assert!(DataType::decimal(5, 2).matches(&decimal));
+        assert!(!DataType::decimal(4, 2).matches(&decimal));
+        assert!(!DataType::decimal(6, 3).matches(&decimal));
+
+        assert_eq!(
+            DataType::array(DataType::Null).unify(&DataType::array(DataType::Int8)),
+            DataType::array(DataType::Int8)
+        );
+    }
+
     #[test]
     fn it_identifies_weak_vs_strong() {
         {
             let mut schema = Schema::new();
-            schema.add_field("a");
+            schema.add_field("a").unwrap();
             assert!(schema.is_weak());
         }
         {
             let mut schema = Schema::new();
             let mut field = Field::new("a");
             field.dtype = DataType::String;
-            schema.push_field(field);
+            schema.push_field(field).unwrap();
             assert!(!schema.is_weak());
         }
     }
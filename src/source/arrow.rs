@@ -0,0 +1,54 @@
+use crate::convert::arrow;
+use crate::DataFrame;
+
+use ::arrow::error::ArrowError;
+use ::arrow::ipc::reader::{FileReader, StreamReader};
+use snafu::{ResultExt, Snafu};
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to open the arrow file: {}", source))]
+    OpenError { source: std::io::Error },
+
+    #[snafu(display("Failed to read arrow: {}", source))]
+    ReadError { source: ArrowError },
+
+    #[snafu(display("Failed to convert the arrow data: {}", source))]
+    ConvertError { source: arrow::Error },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Read Arrow IPC data into a dataframe, the dataframe's schema is converted from the data's
+/// schema
+pub trait ArrowSource {
+    fn from_arrow_path<'a, P: AsRef<Path>>(path: P) -> Result<DataFrame<'a>> {
+        let file = std::fs::File::open(path).context(OpenError)?;
+        Self::read_arrow(std::io::BufReader::new(file))
+    }
+
+    /// Read an Arrow IPC file
+    fn read_arrow<'a, R: std::io::Read + std::io::Seek>(reader: R) -> Result<DataFrame<'a>> {
+        let reader = FileReader::try_new(reader, None).context(ReadError)?;
+        let schema = reader.schema();
+        let batches = reader
+            .collect::<std::result::Result<Vec<_>, ArrowError>>()
+            .context(ReadError)?;
+
+        DataFrame::from_record_batches(&schema, &batches).context(ConvertError)
+    }
+
+    /// Read an Arrow IPC stream
+    fn read_arrow_stream<'a, R: std::io::Read>(reader: R) -> Result<DataFrame<'a>> {
+        let reader = StreamReader::try_new(reader, None).context(ReadError)?;
+        let schema = reader.schema();
+        let batches = reader
+            .collect::<std::result::Result<Vec<_>, ArrowError>>()
+            .context(ReadError)?;
+
+        DataFrame::from_record_batches(&schema, &batches).context(ConvertError)
+    }
+}
+
+impl<'a> ArrowSource for DataFrame<'a> {}
@@ -0,0 +1,113 @@
+use crate::convert::avro::{self, from_avro_value};
+use crate::{DataFrame, Schema, Value};
+
+use apache_avro::{types::Value as AvroValue, Reader, Schema as AvroSchema};
+use snafu::{ResultExt, Snafu};
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to open the avro file: {}", source))]
+    OpenError { source: std::io::Error },
+
+    #[snafu(display("Failed to read avro: {}", source))]
+    ReadError { source: apache_avro::Error },
+
+    #[snafu(display("Failed to convert the avro schema: {}", source))]
+    SchemaError { source: avro::Error },
+
+    #[snafu(display("Failed to read the value of {} on row {}: {}", column, row, message))]
+    ValueError {
+        column: String,
+        row: usize,
+        message: String,
+    },
+
+    #[snafu(display("Expected a record on row {}", row))]
+    NotARecord { row: usize },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+pub trait AvroSource {
+    fn from_avro_path<'a, P: AsRef<Path>>(path: P) -> Result<DataFrame<'a>> {
+        let file = std::fs::File::open(path).context(OpenError)?;
+        Self::read_avro(file)
+    }
+
+    /// Read an avro container file, the dataframe's schema is converted from the file's schema
+    fn read_avro<'a, R: std::io::Read>(reader: R) -> Result<DataFrame<'a>> {
+        let reader = Reader::new(reader).context(ReadError)?;
+        let avro_schema = reader.writer_schema().clone();
+        let schema = Schema::from_avro_schema(&avro_schema).context(SchemaError)?;
+
+        let fields = match &avro_schema {
+            AvroSchema::Record(record) => record.fields.clone(),
+            _ => return Err(Error::NotARecord { row: 0 }),
+        };
+
+        let mut data = vec![];
+
+        for (row, record) in reader.enumerate() {
+            let values = match record.context(ReadError)? {
+                AvroValue::Record(values) => values,
+                _ => return Err(Error::NotARecord { row }),
+            };
+
+            data.push(
+                values
+                    .into_iter()
+                    .zip(&fields)
+                    .map(|((column, value), field)| {
+                        from_avro_value(value, &field.schema).map_err(|message| Error::ValueError {
+                            column,
+                            row,
+                            message,
+                        })
+                    })
+                    .collect::<Result<Vec<Value>>>()?,
+            );
+        }
+
+        let mut df = DataFrame::with_schema(schema);
+        df.extend_unchecked(data);
+
+        Ok(df)
+    }
+}
+
+impl<'a> AvroSource for DataFrame<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataType;
+
+    #[test]
+    fn it_reads_avro_files() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/users.avro");
+        let df = DataFrame::from_avro_path(path).unwrap();
+
+        assert_eq!(
+            df.columns(),
+            ["id", "name", "email", "balance", "created", "active", "tags"]
+        );
+        assert_eq!(df.size(), 3);
+        assert_eq!(df.schema().name, Some("user".to_string()));
+        assert_eq!(df.schema()[3].dtype(), &DataType::decimal(10, 2));
+        assert!(df.schema()[2].is_nullable());
+
+        assert_eq!(df.row(0).unwrap()[0], Value::from(1i64));
+        assert_eq!(df.row(0).unwrap()[3].to_string(), "123.45");
+        assert_eq!(df.row(1).unwrap()[2], Value::Null);
+        assert_eq!(df.row(1).unwrap()[3].to_string(), "-0.50");
+        assert_eq!(
+            df.row(0).unwrap()[4].to_string(),
+            "2019-09-05 18:14:04 +00:00"
+        );
+        assert_eq!(
+            df.row(2).unwrap()[6],
+            Value::Array(vec!["a".into(), "b".into()])
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::value::Value;
-use crate::DataFrame;
+use crate::{DataFrame, Schema};
 use snafu::{ResultExt, Snafu};
 
 #[derive(Debug, Snafu)]
@@ -7,6 +7,9 @@ pub enum Error {
     #[snafu(display("Failed to read csv: {}", source))]
     ReadError { source: csv::Error },
 
+    #[snafu(display("Failed to map the headers to the schema: {}", source))]
+    SchemaError { source: crate::schema::Error },
+
     #[snafu(display("Failed to perform operation on dataframe: {}", source))]
     OperationError { source: crate::error::Error },
 }
@@ -14,7 +17,7 @@ pub enum Error {
 type Result<T, E = Error> = std::result::Result<T, E>;
 
 pub trait CsvSource {
-    fn from_path(path: &str) -> Result<DataFrame> {
+    fn from_path<'a>(path: &str) -> Result<DataFrame<'a>> {
         let reader: csv::Reader<std::fs::File> = csv::Reader::from_path(path).context(ReadError)?;
         Self::read_csv(reader)
     }
@@ -23,60 +26,89 @@ pub trait CsvSource {
         Self::read_csv(csv::Reader::from_reader(reader))
     }
 
-    fn read_csv<'a, R: std::io::Read>(mut reader: csv::Reader<R>) -> Result<DataFrame<'a>> {
-        // convert all the records into vectors of values
-        let data = reader
-            .records()
-            .filter_map(|record| record.ok())
-            .map(|record| {
-                record
-                    .into_iter()
-                    .map(|value| match value.len() {
-                        0 => Value::Null,
-                        _ => Value::String(value.to_string()),
-                    })
-                    .collect::<Vec<Value>>()
-            })
-            .collect::<Vec<Vec<Value>>>();
-
-        // all the data should have the same number of rows which should equal the number of
-        // headers assuming that the CSV has headers
-        let expected_row_length = data.iter().map(|row| row.len()).max().unwrap_or(0);
-
-        // ensure that each record has the expected number of columns, otherwise fill with null
-        let data = data
-            .into_iter()
-            .map(|mut record| {
-                if record.len() != expected_row_length {
-                    record.resize(expected_row_length, Value::Null);
-                }
-                record
-            })
-            .collect::<Vec<Vec<Value>>>();
-
-        // get the headers or create default ones
-        let headers = match reader.headers() {
-            Ok(headers) => headers
-                .into_iter()
-                .map(|h| h.to_string())
-                .collect::<Vec<String>>(),
-            _ => (0..expected_row_length)
-                .map(|h| format!("{}", h))
-                .collect::<Vec<String>>(),
-        };
-
-        // create  the dataframe with the headers
-        let mut df = DataFrame::with_columns(&headers);
+    /// from_path, but with the headers that are aliases of the schema's fields renamed to the
+    /// fields' names. The values are left as strings, use evolve_to to reshape them into the
+    /// schema.
+    fn from_path_with_schema<'a>(path: &str, schema: &Schema) -> Result<DataFrame<'a>> {
+        let reader: csv::Reader<std::fs::File> = csv::Reader::from_path(path).context(ReadError)?;
+        read(reader, Some(schema))
+    }
 
-        // push data
-        df.extend(data).context(OperationError)?;
+    fn read_csv<'a, R: std::io::Read>(reader: csv::Reader<R>) -> Result<DataFrame<'a>> {
+        read(reader, None)
+    }
 
-        Ok(df)
+    /// read_csv, but with the aliased headers renamed the same way as from_path_with_schema
+    fn read_csv_with_schema<'a, R: std::io::Read>(
+        reader: csv::Reader<R>,
+        schema: &Schema,
+    ) -> Result<DataFrame<'a>> {
+        read(reader, Some(schema))
     }
 }
 
 impl<'a> CsvSource for DataFrame<'a> {}
 
+fn read<'a, R: std::io::Read>(
+    mut reader: csv::Reader<R>,
+    schema: Option<&Schema>,
+) -> Result<DataFrame<'a>> {
+    // convert all the records into vectors of values
+    let data = reader
+        .records()
+        .filter_map(|record| record.ok())
+        .map(|record| {
+            record
+                .into_iter()
+                .map(|value| match value.len() {
+                    0 => Value::Null,
+                    _ => Value::String(value.to_string()),
+                })
+                .collect::<Vec<Value>>()
+        })
+        .collect::<Vec<Vec<Value>>>();
+
+    // all the data should have the same number of rows which should equal the number of
+    // headers assuming that the CSV has headers
+    let expected_row_length = data.iter().map(|row| row.len()).max().unwrap_or(0);
+
+    // ensure that each record has the expected number of columns, otherwise fill with null
+    let data = data
+        .into_iter()
+        .map(|mut record| {
+            if record.len() != expected_row_length {
+                record.resize(expected_row_length, Value::Null);
+            }
+            record
+        })
+        .collect::<Vec<Vec<Value>>>();
+
+    // get the headers or create default ones
+    let headers = match reader.headers() {
+        Ok(headers) => headers
+            .into_iter()
+            .map(|h| h.to_string())
+            .collect::<Vec<String>>(),
+        _ => (0..expected_row_length)
+            .map(|h| format!("{}", h))
+            .collect::<Vec<String>>(),
+    };
+
+    // map the aliased headers to the names of the schema's fields
+    let headers = match schema {
+        Some(schema) => schema.canonical_names(&headers).context(SchemaError)?,
+        None => headers,
+    };
+
+    // create  the dataframe with the headers
+    let mut df = DataFrame::with_columns(&headers);
+
+    // push data
+    df.extend(data).context(OperationError)?;
+
+    Ok(df)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -91,7 +123,38 @@ mod test {
 
         assert_eq!(*cols, ["a", "b", "c"]);
         assert_eq!(df.size(), 2);
-        assert_eq!(df[0], ["1".into(), "2".into(), "3".into()]);
-        assert_eq!(df[1], ["4".into(), "5".into(), "6".into()]);
+        assert_eq!(
+            df.row(0).unwrap(),
+            &["1".into(), "2".into(), "3".into()][..]
+        );
+        assert_eq!(
+            df.row(1).unwrap(),
+            &["4".into(), "5".into(), "6".into()][..]
+        );
+    }
+
+    #[test]
+    fn it_maps_aliased_headers() {
+        use crate::Field;
+
+        let schema = Schema::with_fields(vec![
+            Field::new("customer_id")
+                .with_alias("cust_id")
+                .with_alias("CustomerID"),
+            Field::new("name"),
+        ])
+        .unwrap();
+
+        let raw_data = "CustomerID,name,other\r\n1,a,x\r";
+        let reader = csv::Reader::from_reader(raw_data.as_bytes());
+        let df = DataFrame::read_csv_with_schema(reader, &schema).unwrap();
+        assert_eq!(*df.columns(), ["customer_id", "name", "other"]);
+
+        let raw_data = "cust_id,customer_id\r\n1,2\r";
+        let reader = csv::Reader::from_reader(raw_data.as_bytes());
+        assert!(matches!(
+            DataFrame::read_csv_with_schema(reader, &schema),
+            Err(Error::SchemaError { .. })
+        ));
     }
 }
@@ -0,0 +1,345 @@
+use crate::ops::cast::try_cast;
+use crate::traits::TypeOf;
+use crate::value::number::Num;
+use crate::{DataFrame, DataType, Field, Schema, Value};
+
+use calamine::{open_workbook, Data, Range, Reader, Xlsx, XlsxError};
+use chrono::NaiveTime;
+use snafu::{ResultExt, Snafu};
+use std::io::{Read, Seek};
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to open the workbook: {}", source))]
+    OpenError { source: XlsxError },
+
+    #[snafu(display("Failed to read the sheet: {}", source))]
+    ReadError { source: XlsxError },
+
+    #[snafu(display("The workbook doesn't have the sheet {}", sheet))]
+    SheetNotFound { sheet: String },
+
+    #[snafu(display("{} is not a range of cells like A1:C10", range))]
+    InvalidRange { range: String },
+
+    #[snafu(display("The header is not a valid schema: {}", source))]
+    SchemaError { source: crate::schema::Error },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The sheet to read, by its name or its position in the workbook
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sheet {
+    Index(usize),
+    Name(String),
+}
+
+/// Options for reading a sheet, by default the whole of the first sheet is read and its first row
+/// is the header
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    sheet: Sheet,
+    header: bool,
+    range: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            sheet: Sheet::Index(0),
+            header: true,
+            range: None,
+        }
+    }
+}
+
+impl Options {
+    pub fn new() -> Options {
+        Options::default()
+    }
+
+    pub fn with_sheet_name<S: Into<String>>(mut self, name: S) -> Options {
+        self.sheet = Sheet::Name(name.into());
+        self
+    }
+
+    pub fn with_sheet_index(mut self, index: usize) -> Options {
+        self.sheet = Sheet::Index(index);
+        self
+    }
+
+    /// Whether the first row of the range is the header, columns are named column_1, column_2...
+    /// when there isn't one
+    pub fn with_header(mut self, header: bool) -> Options {
+        self.header = header;
+        self
+    }
+
+    /// Only read the cells in the range, in A1:C10 notation
+    pub fn with_range<S: Into<String>>(mut self, range: S) -> Options {
+        self.range = Some(range.into());
+        self
+    }
+}
+
+/// Read a sheet of an Excel workbook into a dataframe. Columns of whole numbers are int64 and
+/// other numeric columns are doubles, date cells are dates or timestamps when they have a time,
+/// and empty cells, the cells covered by merged cells and errors are null.
+pub trait ExcelSource {
+    fn from_excel_path<'a, P: AsRef<Path>>(path: P, options: &Options) -> Result<DataFrame<'a>> {
+        let workbook: Xlsx<_> = open_workbook(path).context(OpenError)?;
+        read(workbook, options)
+    }
+
+    fn read_excel<'a, R: Read + Seek>(reader: R, options: &Options) -> Result<DataFrame<'a>> {
+        let workbook = Xlsx::new(reader).context(OpenError)?;
+        read(workbook, options)
+    }
+}
+
+impl<'a> ExcelSource for DataFrame<'a> {}
+
+fn read<'a, R: Read + Seek>(mut workbook: Xlsx<R>, options: &Options) -> Result<DataFrame<'a>> {
+    let range = match &options.sheet {
+        Sheet::Name(name) => match workbook.sheet_names().contains(name) {
+            true => workbook.worksheet_range(name).context(ReadError)?,
+            false => {
+                return Err(Error::SheetNotFound {
+                    sheet: name.clone(),
+                })
+            }
+        },
+        Sheet::Index(index) => match workbook.worksheet_range_at(*index) {
+            Some(range) => range.context(ReadError)?,
+            None => {
+                return Err(Error::SheetNotFound {
+                    sheet: index.to_string(),
+                })
+            }
+        },
+    };
+
+    let range = match &options.range {
+        Some(cells) => {
+            let (start, end) = parse_range(cells).ok_or_else(|| Error::InvalidRange {
+                range: cells.clone(),
+            })?;
+            range.range(start, end)
+        }
+        None => range,
+    };
+
+    let mut rows = cells(&range);
+    let width = range.width();
+    let names = match options.header && !rows.is_empty() {
+        true => rows
+            .remove(0)
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| match value {
+                Value::Null => format!("column_{}", i + 1),
+                value => value.to_string(),
+            })
+            .collect::<Vec<String>>(),
+        false => (1..=width).map(|i| format!("column_{}", i)).collect(),
+    };
+
+    let mut fields = vec![];
+    for (i, name) in names.iter().enumerate() {
+        let dtype = column_type(rows.iter().map(|row| &row[i]));
+        for row in rows.iter_mut() {
+            let value = std::mem::replace(&mut row[i], Value::Null);
+            row[i] = into_type(value, &dtype);
+        }
+        fields.push(Field::with_type(name, dtype));
+    }
+
+    let mut df = DataFrame::with_schema(Schema::with_fields(fields).context(SchemaError)?);
+    df.extend_unchecked(rows);
+
+    Ok(df)
+}
+
+/// The zero based row and column of the first and last cells of a range like A1:C10
+fn parse_range(range: &str) -> Option<((u32, u32), (u32, u32))> {
+    let (start, end) = range.split_once(':')?;
+    let (start, end) = (parse_cell(start)?, parse_cell(end)?);
+
+    match start.0 <= end.0 && start.1 <= end.1 {
+        true => Some((start, end)),
+        false => None,
+    }
+}
+
+fn parse_cell(cell: &str) -> Option<(u32, u32)> {
+    let cell = cell.trim().to_uppercase();
+    let split = cell.find(|c: char| c.is_ascii_digit())?;
+    let (letters, digits) = cell.split_at(split);
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
+
+    let column = letters.chars().try_fold(0u32, |acc, c| {
+        acc.checked_mul(26)?.checked_add(c as u32 - 'A' as u32 + 1)
+    })?;
+    let row = digits.parse::<u32>().ok()?;
+
+    match row {
+        0 => None,
+        row => Some((row - 1, column - 1)),
+    }
+}
+
+fn cells(range: &Range<Data>) -> Vec<Vec<Value>> {
+    range
+        .rows()
+        .map(|row| row.iter().map(cell).collect())
+        .collect()
+}
+
+fn cell(data: &Data) -> Value {
+    match data {
+        Data::Empty | Data::Error(_) => Value::Null,
+        Data::Bool(b) => Value::Bool(*b),
+        Data::String(s) if s.is_empty() => Value::Null,
+        Data::String(s) | Data::DurationIso(s) => Value::String(s.clone()),
+        Data::Int(i) => Value::from(*i),
+        Data::Float(f) => Value::from(*f),
+        Data::DateTime(dt) if dt.is_datetime() => match dt.as_datetime() {
+            // serials without a day are times
+            Some(d) if dt.as_f64() < 1.0 => Value::Time(d.time()),
+            Some(d) if d.time() == NaiveTime::MIN => Value::Date(d.date()),
+            Some(d) => Value::Timestamp(d),
+            None => Value::from(dt.as_f64()),
+        },
+        Data::DateTime(dt) => Value::from(dt.as_f64()),
+        Data::DateTimeIso(s) => [DataType::Timestamp, DataType::Date, DataType::Time]
+            .iter()
+            .find_map(|dtype| try_cast(Value::String(s.clone()), dtype).ok())
+            .unwrap_or_else(|| Value::String(s.clone())),
+    }
+}
+
+/// The type of the column's values. Numbers are all stored as doubles, so columns of whole numbers
+/// are int64, and columns of dates and timestamps are timestamps.
+fn column_type<'v, I: Iterator<Item = &'v Value>>(values: I) -> DataType {
+    let mut dtype = DataType::Null;
+    let mut whole = true;
+
+    for value in values.filter(|value| !value.is_null()) {
+        let value_type = match value {
+            Value::Number(_) => {
+                whole &=
+                    as_f64(value).is_some_and(|f| f.fract() == 0.0 && f.abs() < i64::MAX as f64);
+                DataType::Double
+            }
+            value => value.type_of(),
+        };
+
+        dtype = match (dtype, value_type) {
+            (DataType::Date, DataType::Timestamp) | (DataType::Timestamp, DataType::Date) => {
+                DataType::Timestamp
+            }
+            (dtype, value_type) => dtype.unify(&value_type),
+        };
+    }
+
+    match dtype {
+        DataType::Double if whole => DataType::Int64,
+        DataType::Null => DataType::Any,
+        dtype => dtype,
+    }
+}
+
+fn into_type(value: Value, dtype: &DataType) -> Value {
+    match (as_f64(&value), dtype) {
+        (Some(f), DataType::Int64) => Value::from(f as i64),
+        (Some(f), DataType::Double) => Value::from(f),
+        (_, DataType::Timestamp) => try_cast(value, dtype).unwrap_or(Value::Null),
+        _ => value,
+    }
+}
+
+/// The number of a cell, which is either an int64 or a double
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => match n.inner() {
+            Num::Int64(i) => Some(*i as f64),
+            Num::Double(f) => Some(*f),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::row;
+
+    const PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/items.xlsx");
+
+    #[test]
+    fn it_reads_sheets() {
+        let options = Options::new().with_sheet_name("items").with_range("A2:E5");
+        let df = DataFrame::from_excel_path(PATH, &options).unwrap();
+
+        let date = chrono::NaiveDate::from_ymd_opt(2019, 9, 5).unwrap();
+        assert_eq!(
+            df.schema().field_names(),
+            vec!["id", "name", "price", "date", "active"]
+        );
+        assert_eq!(df.schema()[0].dtype(), &DataType::Int64);
+        assert_eq!(df.schema()[2].dtype(), &DataType::Double);
+        assert_eq!(df.schema()[3].dtype(), &DataType::Date);
+        assert_eq!(df.row(0).unwrap(), row![1i64, "apple", 1.5, date, true]);
+        // the empty cell and the error are null
+        assert_eq!(
+            df.row(1).unwrap(),
+            row![
+                2i64,
+                Value::Null,
+                2.0,
+                date.succ_opt().unwrap(),
+                Value::Null
+            ]
+        );
+        // the cells covered by a merged cell are null
+        assert_eq!(
+            df.row(2).unwrap(),
+            row![3i64, "merged", Value::Null, Value::Null, false]
+        );
+
+        let options = Options::new()
+            .with_sheet_index(1)
+            .with_header(false)
+            .with_range("A3:A5");
+        let df = DataFrame::from_excel_path(PATH, &options).unwrap();
+        assert_eq!(df.schema().field_names(), vec!["column_1"]);
+        assert_eq!(df.size(), 3);
+
+        let file = std::fs::File::open(PATH).unwrap();
+        let df = DataFrame::read_excel(file, &Options::new()).unwrap();
+        assert_eq!(df.schema().field_names(), vec!["ignore me"]);
+        assert_eq!(df.size(), 0);
+
+        assert!(matches!(
+            DataFrame::from_excel_path(PATH, &Options::new().with_sheet_name("missing")),
+            Err(Error::SheetNotFound { .. })
+        ));
+        assert!(matches!(
+            DataFrame::from_excel_path(PATH, &Options::new().with_range("C3:A1")),
+            Err(Error::InvalidRange { .. })
+        ));
+    }
+
+    #[test]
+    fn it_parses_ranges() {
+        assert_eq!(parse_range("b2:AA10"), Some(((1, 1), (9, 26))));
+        assert_eq!(parse_range("A0:B2"), None);
+        // columns past u32::MAX
+        assert_eq!(parse_range("A1:ZZZZZZZZ1"), None);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::convert::fixed_width::Layout;
+use crate::{DataFrame, Value};
+
+use snafu::{ResultExt, Snafu};
+use std::io::BufRead;
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to open the fixed width file: {}", source))]
+    OpenError { source: std::io::Error },
+
+    #[snafu(display("Failed to read the fixed width records: {}", source))]
+    ReadError { source: std::io::Error },
+
+    #[snafu(display(
+        "The value in column {} on row {} can't be read: {}",
+        column,
+        row,
+        message
+    ))]
+    ParseError {
+        column: String,
+        row: usize,
+        message: String,
+    },
+
+    #[snafu(display("The layout's fields are invalid: {}", source))]
+    SchemaError { source: crate::schema::Error },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Read fixed width records, one per line, into a dataframe with the layout's schema. Fields past
+/// the end of a short line are null and empty lines are skipped.
+pub trait FixedWidthSource {
+    fn from_fixed_width_path<'a, P: AsRef<Path>>(
+        path: P,
+        layout: &Layout,
+    ) -> Result<DataFrame<'a>> {
+        let file = std::fs::File::open(path).context(OpenError)?;
+        Self::read_fixed_width(std::io::BufReader::new(file), layout)
+    }
+
+    fn read_fixed_width<'a, R: BufRead>(reader: R, layout: &Layout) -> Result<DataFrame<'a>> {
+        let mut data = vec![];
+
+        for (row, line) in reader.split(b'\n').enumerate() {
+            let line = line.context(ReadError)?;
+            let line = String::from_utf8_lossy(&line);
+            let line = line.strip_suffix('\r').unwrap_or(&line);
+            if line.is_empty() {
+                continue;
+            }
+
+            // fields are sliced by characters rather than bytes
+            let chars = line.chars().collect::<Vec<char>>();
+            let values = layout
+                .fields()
+                .iter()
+                .map(|field| {
+                    let start = field.start().min(chars.len());
+                    let end = field.end().min(chars.len());
+                    let text = chars[start..end].iter().collect::<String>();
+
+                    field.parse(&text).map_err(|message| Error::ParseError {
+                        column: field.field().name.clone(),
+                        row,
+                        message,
+                    })
+                })
+                .collect::<Result<Vec<Value>>>()?;
+            data.push(values);
+        }
+
+        let mut df = DataFrame::with_schema(layout.schema().context(SchemaError)?);
+        df.extend_unchecked(data);
+
+        Ok(df)
+    }
+}
+
+impl<'a> FixedWidthSource for DataFrame<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::fixed_width::FixedField;
+    use crate::{row, DataType, Field};
+
+    #[test]
+    fn it_reads_fixed_width_records() {
+        let layout = Layout::new()
+            .with_field(FixedField::new(
+                Field::with_type("id", DataType::Int32),
+                0,
+                4,
+            ))
+            .with_field(FixedField::new(
+                Field::with_type("name", DataType::String),
+                4,
+                6,
+            ))
+            .with_field(
+                FixedField::new(Field::with_type("price", DataType::Double), 10, 6)
+                    .with_pad('0')
+                    .with_implied_decimals(2),
+            )
+            .with_field(FixedField::new(
+                Field::with_type("active", DataType::Bool),
+                16,
+                1,
+            ));
+
+        let data = "   1apple 000150Y\r\n  22      -00025N\n\n   3pear\n";
+        let df = DataFrame::read_fixed_width(data.as_bytes(), &layout).unwrap();
+
+        assert_eq!(df.schema(), &layout.schema().unwrap());
+        assert_eq!(df.size(), 3);
+        assert_eq!(df.row(0).unwrap(), row![1i32, "apple", 1.5, true]);
+        assert_eq!(df.row(1).unwrap(), row![22i32, Value::Null, -0.25, false]);
+        assert_eq!(
+            df.row(2).unwrap(),
+            row![3i32, "pear", Value::Null, Value::Null]
+        );
+
+        assert!(matches!(
+            DataFrame::read_fixed_width("   x\n".as_bytes(), &layout),
+            Err(Error::ParseError { .. })
+        ));
+    }
+}
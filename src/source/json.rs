@@ -0,0 +1,154 @@
+use crate::value::Value;
+use crate::{DataFrame, Schema};
+
+use serde_json::Value as JsonValue;
+use snafu::{ResultExt, Snafu};
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to open the json file: {}", source))]
+    OpenError { source: std::io::Error },
+
+    #[snafu(display("Failed to read json: {}", source))]
+    ReadError { source: serde_json::Error },
+
+    #[snafu(display("Row {} is not a json object", row))]
+    NotAnObject { row: usize },
+
+    #[snafu(display("Failed to map the keys to the schema: {}", source))]
+    SchemaError { source: crate::schema::Error },
+
+    #[snafu(display("Failed to perform operation on dataframe: {}", source))]
+    OperationError { source: crate::error::Error },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Read json objects into a dataframe, either as an array of objects or as one object per line.
+/// The columns are the objects' keys in the order they are first seen, and keys that an object
+/// doesn't have are null.
+pub trait JsonSource {
+    fn from_json_path<'a, P: AsRef<Path>>(path: P) -> Result<DataFrame<'a>> {
+        let file = std::fs::File::open(path).context(OpenError)?;
+        read(std::io::BufReader::new(file), None)
+    }
+
+    /// from_json_path, but with the keys that are aliases of the schema's fields renamed to the
+    /// fields' names
+    fn from_json_path_with_schema<'a, P: AsRef<Path>>(
+        path: P,
+        schema: &Schema,
+    ) -> Result<DataFrame<'a>> {
+        let file = std::fs::File::open(path).context(OpenError)?;
+        read(std::io::BufReader::new(file), Some(schema))
+    }
+
+    fn read_json<'a, R: std::io::Read>(reader: R) -> Result<DataFrame<'a>> {
+        read(reader, None)
+    }
+
+    fn read_json_with_schema<'a, R: std::io::Read>(
+        reader: R,
+        schema: &Schema,
+    ) -> Result<DataFrame<'a>> {
+        read(reader, Some(schema))
+    }
+}
+
+impl<'a> JsonSource for DataFrame<'a> {}
+
+fn read<'a, R: std::io::Read>(reader: R, schema: Option<&Schema>) -> Result<DataFrame<'a>> {
+    let mut objects = vec![];
+
+    for value in serde_json::Deserializer::from_reader(reader).into_iter::<JsonValue>() {
+        match value.context(ReadError)? {
+            JsonValue::Array(values) => objects.extend(values),
+            value => objects.push(value),
+        }
+    }
+
+    let mut keys: Vec<String> = vec![];
+
+    let objects = objects
+        .into_iter()
+        .enumerate()
+        .map(|(row, object)| match object {
+            JsonValue::Object(object) => {
+                for key in object.keys() {
+                    if !keys.contains(key) {
+                        keys.push(key.clone());
+                    }
+                }
+                Ok(object)
+            }
+            _ => Err(Error::NotAnObject { row }),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let data = objects
+        .into_iter()
+        .map(|mut object| {
+            keys.iter()
+                .map(|key| object.remove(key).map_or(Value::Null, Value::from))
+                .collect::<Vec<Value>>()
+        })
+        .collect::<Vec<Vec<Value>>>();
+
+    // map the aliased keys to the names of the schema's fields
+    let columns = match schema {
+        Some(schema) => schema.canonical_names(&keys).context(SchemaError)?,
+        None => keys,
+    };
+
+    let mut df = DataFrame::with_columns(&columns);
+    df.extend(data).context(OperationError)?;
+
+    Ok(df)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Field;
+
+    #[test]
+    fn it_reads_json_objects() {
+        let raw = r#"[{"a": 1, "b": "x"}, {"b": "y", "c": true}]"#;
+        let df = DataFrame::read_json(raw.as_bytes()).unwrap();
+
+        assert_eq!(*df.columns(), ["a", "b", "c"]);
+        assert_eq!(
+            df.row(0).unwrap(),
+            &[Value::from(1i64), "x".into(), Value::Null][..]
+        );
+        assert_eq!(
+            df.row(1).unwrap(),
+            &[Value::Null, "y".into(), true.into()][..]
+        );
+
+        let lines = "{\"a\": 1}\n{\"a\": 2}\n";
+        assert_eq!(DataFrame::read_json(lines.as_bytes()).unwrap().size(), 2);
+
+        assert!(matches!(
+            DataFrame::read_json("[1]".as_bytes()),
+            Err(Error::NotAnObject { row: 0 })
+        ));
+    }
+
+    #[test]
+    fn it_maps_aliased_keys() {
+        let schema =
+            Schema::with_fields(vec![Field::new("customer_id").with_alias("cust_id")]).unwrap();
+
+        let raw = r#"[{"cust_id": 1, "name": "a"}]"#;
+        let df = DataFrame::read_json_with_schema(raw.as_bytes(), &schema).unwrap();
+        assert_eq!(*df.columns(), ["customer_id", "name"]);
+
+        let raw = r#"[{"cust_id": 1}, {"customer_id": 2}]"#;
+        assert!(matches!(
+            DataFrame::read_json_with_schema(raw.as_bytes(), &schema),
+            Err(Error::SchemaError { .. })
+        ));
+    }
+}
@@ -1,2 +1,10 @@
+pub mod arrow;
+pub mod avro;
 pub mod csv;
+pub mod excel;
+pub mod fixed_width;
+pub mod json;
+pub mod parquet;
+pub mod postgres;
+pub mod sqlite;
 // pub mod singer
@@ -0,0 +1,170 @@
+use crate::convert::arrow;
+use crate::DataFrame;
+
+use ::arrow::error::ArrowError;
+use ::arrow::record_batch::{RecordBatch, RecordBatchReader};
+use parquet::arrow::arrow_reader::{ArrowReaderMetadata, ParquetRecordBatchReaderBuilder};
+use parquet::arrow::ProjectionMask;
+use parquet::errors::ParquetError;
+use parquet::file::reader::ChunkReader;
+use snafu::{ResultExt, Snafu};
+use std::fs::File;
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to open the parquet file: {}", source))]
+    OpenError { source: std::io::Error },
+
+    #[snafu(display("Failed to read parquet: {}", source))]
+    ReadError { source: ParquetError },
+
+    #[snafu(display("Failed to read the parquet record batches: {}", source))]
+    BatchError { source: ArrowError },
+
+    #[snafu(display("Failed to convert the parquet data: {}", source))]
+    ConvertError { source: arrow::Error },
+
+    #[snafu(display("The column {} doesn't exist in the parquet file", column))]
+    ColumnNotFound { column: String },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Read Parquet files into a dataframe, the dataframe's schema is converted from the file's
+/// schema. When only some of the columns are read, they keep the order they have in the file.
+pub trait ParquetSource {
+    fn from_parquet_path<'a, P: AsRef<Path>>(path: P) -> Result<DataFrame<'a>> {
+        let file = File::open(path).context(OpenError)?;
+        Self::read_parquet(file)
+    }
+
+    fn from_parquet_path_with_columns<'a, P: AsRef<Path>>(
+        path: P,
+        columns: &[&str],
+    ) -> Result<DataFrame<'a>> {
+        let file = File::open(path).context(OpenError)?;
+        Self::read_parquet_with_columns(file, columns)
+    }
+
+    fn read_parquet<'a, R: ChunkReader + 'static>(reader: R) -> Result<DataFrame<'a>> {
+        read(reader, None)
+    }
+
+    /// Read only the columns from the parquet data
+    fn read_parquet_with_columns<'a, R: ChunkReader + 'static>(
+        reader: R,
+        columns: &[&str],
+    ) -> Result<DataFrame<'a>> {
+        read(reader, Some(columns))
+    }
+
+    /// Read the parquet file one row group at a time, each row group is read into its own
+    /// dataframe
+    fn parquet_row_groups<P: AsRef<Path>>(path: P, columns: Option<&[&str]>) -> Result<RowGroups> {
+        let file = File::open(path).context(OpenError)?;
+        let metadata = ArrowReaderMetadata::load(&file, Default::default()).context(ReadError)?;
+        let mask = projection(&metadata, columns)?;
+
+        Ok(RowGroups {
+            file,
+            metadata,
+            mask,
+            next: 0,
+        })
+    }
+}
+
+impl<'a> ParquetSource for DataFrame<'a> {}
+
+/// An iterator over the row groups of a parquet file
+pub struct RowGroups {
+    file: File,
+    metadata: ArrowReaderMetadata,
+    mask: ProjectionMask,
+    next: usize,
+}
+
+impl RowGroups {
+    /// The number of row groups in the file
+    pub fn len(&self) -> usize {
+        self.metadata.metadata().num_row_groups()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn read<'a>(&self, row_group: usize) -> Result<DataFrame<'a>> {
+        let file = self.file.try_clone().context(OpenError)?;
+        let builder =
+            ParquetRecordBatchReaderBuilder::new_with_metadata(file, self.metadata.clone())
+                .with_projection(self.mask.clone())
+                .with_row_groups(vec![row_group]);
+
+        collect(builder)
+    }
+}
+
+impl Iterator for RowGroups {
+    type Item = Result<DataFrame<'static>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.len() {
+            return None;
+        }
+
+        let df = self.read(self.next);
+        self.next += 1;
+
+        Some(df)
+    }
+}
+
+fn read<'a, R: ChunkReader + 'static>(
+    reader: R,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame<'a>> {
+    let metadata = ArrowReaderMetadata::load(&reader, Default::default()).context(ReadError)?;
+    let mask = projection(&metadata, columns)?;
+    let builder =
+        ParquetRecordBatchReaderBuilder::new_with_metadata(reader, metadata).with_projection(mask);
+
+    collect(builder)
+}
+
+/// The mask of the root columns to read, all of them when there aren't any columns
+fn projection(metadata: &ArrowReaderMetadata, columns: Option<&[&str]>) -> Result<ProjectionMask> {
+    let columns = match columns {
+        Some(columns) => columns,
+        None => return Ok(ProjectionMask::all()),
+    };
+
+    let indices = columns
+        .iter()
+        .map(|column| {
+            metadata
+                .schema()
+                .index_of(column)
+                .map_err(|_| Error::ColumnNotFound {
+                    column: column.to_string(),
+                })
+        })
+        .collect::<Result<Vec<usize>>>()?;
+
+    Ok(ProjectionMask::roots(metadata.parquet_schema(), indices))
+}
+
+fn collect<'a, R: ChunkReader + 'static>(
+    builder: ParquetRecordBatchReaderBuilder<R>,
+) -> Result<DataFrame<'a>> {
+    // The projected schema doesn't keep the file schema's metadata, which has its name and doc
+    let metadata = builder.schema().metadata().clone();
+    let reader = builder.build().context(ReadError)?;
+    let schema = reader.schema().as_ref().clone().with_metadata(metadata);
+    let batches = reader
+        .collect::<std::result::Result<Vec<RecordBatch>, ArrowError>>()
+        .context(BatchError)?;
+
+    DataFrame::from_record_batches(&schema, &batches).context(ConvertError)
+}
@@ -0,0 +1,258 @@
+use crate::ops::cast::try_cast;
+use crate::value::number::Number;
+use crate::{DataFrame, DataType, Schema, Value};
+
+use chrono::{DateTime, FixedOffset};
+use snafu::{ResultExt, Snafu};
+use std::io::BufRead;
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to open the copy file: {}", source))]
+    OpenError { source: std::io::Error },
+
+    #[snafu(display("Failed to read the copy data: {}", source))]
+    ReadError { source: std::io::Error },
+
+    #[snafu(display("Row {} has {} columns but the schema has {}", row, found, expected))]
+    ColumnCountError {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    #[snafu(display(
+        "The value in column {} on row {} can't be read: {}",
+        column,
+        row,
+        message
+    ))]
+    ParseError {
+        column: String,
+        row: usize,
+        message: String,
+    },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Read PostgreSQL COPY text data into a dataframe. The data doesn't have the columns' names or
+/// types, so the schema has the columns in the order they were copied.
+pub trait PostgresSource {
+    fn from_copy_path<'a, P: AsRef<Path>>(path: P, schema: &Schema) -> Result<DataFrame<'a>> {
+        let file = std::fs::File::open(path).context(OpenError)?;
+        Self::read_copy(std::io::BufReader::new(file), schema)
+    }
+
+    fn read_copy<'a, R: BufRead>(reader: R, schema: &Schema) -> Result<DataFrame<'a>> {
+        let fields = schema.fields();
+        let mut data = vec![];
+
+        for (row, line) in reader.split(b'\n').enumerate() {
+            let line = line.context(ReadError)?;
+            let line = String::from_utf8_lossy(&line);
+            let line = line.strip_suffix('\r').unwrap_or(&line);
+
+            // psql ends the data with a line of \.
+            if line == "\\." {
+                break;
+            }
+
+            let columns = line.split('\t').collect::<Vec<&str>>();
+            if columns.len() != fields.len() {
+                return Err(Error::ColumnCountError {
+                    row,
+                    expected: fields.len(),
+                    found: columns.len(),
+                });
+            }
+
+            let values = columns
+                .into_iter()
+                .zip(fields)
+                .map(|(column, field)| {
+                    parse(column, &field.dtype).map_err(|message| Error::ParseError {
+                        column: field.name.clone(),
+                        row,
+                        message,
+                    })
+                })
+                .collect::<Result<Vec<Value>>>()?;
+            data.push(values);
+        }
+
+        let mut df = DataFrame::with_schema(schema.clone());
+        df.extend_unchecked(data);
+
+        Ok(df)
+    }
+}
+
+impl<'a> PostgresSource for DataFrame<'a> {}
+
+/// Parse the column's text into a value of the type
+fn parse(column: &str, dtype: &DataType) -> std::result::Result<Value, String> {
+    if column == "\\N" {
+        return Ok(Value::Null);
+    }
+
+    let text = unescape(column)?;
+    let value = match dtype {
+        DataType::String | DataType::Any => Value::String(text),
+        DataType::Bool => match text.to_lowercase().as_str() {
+            "t" | "true" | "y" | "yes" | "on" | "1" => Value::Bool(true),
+            "f" | "false" | "n" | "no" | "off" | "0" => Value::Bool(false),
+            _ => return Err(format!("{} isn't a boolean", text)),
+        },
+        DataType::Binary => match text.strip_prefix("\\x") {
+            Some(hex) if hex.len() % 2 == 0 => (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+                .collect::<std::result::Result<Vec<u8>, _>>()
+                .map(Value::Binary)
+                .map_err(|e| e.to_string())?,
+            _ => return Err(format!("{} isn't hex encoded bytes", text)),
+        },
+        DataType::TimestampTz => {
+            DateTime::<FixedOffset>::parse_from_str(&text, "%Y-%m-%d %H:%M:%S%.f%#z")
+                .map(Value::TimestampTz)
+                .map_err(|e| format!("{} isn't a timestamp: {}", text, e))?
+        }
+        DataType::Array(_) | DataType::Map(_) | DataType::Struct(_) => {
+            let json = serde_json::from_str::<serde_json::Value>(&text)
+                .map_err(|e| format!("{} isn't json: {}", text, e))?;
+            try_cast(Value::from(json), dtype).map_err(|e| e.to_string())?
+        }
+        dtype if dtype.is_numeric() => Number::from_str(&text, dtype)
+            .map(Value::Number)
+            .map_err(|e| e.to_string())?,
+        dtype => try_cast(Value::String(text), dtype).map_err(|e| e.to_string())?,
+    };
+
+    Ok(value)
+}
+
+/// Replace the escape sequences with the characters they are of: the backslash sequences of C, and
+/// octal and hex bytes
+fn unescape(column: &str) -> std::result::Result<String, String> {
+    if !column.contains('\\') {
+        return Ok(column.to_string());
+    }
+
+    let mut bytes = vec![];
+    let mut chars = column.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+
+        let escaped = chars.next().ok_or("the value ends with a backslash")?;
+        match escaped {
+            'b' => bytes.push(8),
+            'f' => bytes.push(12),
+            'n' => bytes.push(b'\n'),
+            'r' => bytes.push(b'\r'),
+            't' => bytes.push(b'\t'),
+            'v' => bytes.push(11),
+            '0'..='7' => {
+                let mut digits = escaped.to_string();
+                while digits.len() < 3 && chars.peek().is_some_and(|c| ('0'..='7').contains(c)) {
+                    digits.push(chars.next().unwrap());
+                }
+                bytes.push(u8::from_str_radix(&digits, 8).map_err(|e| e.to_string())?);
+            }
+            'x' if chars.peek().is_some_and(|c| c.is_ascii_hexdigit()) => {
+                let mut digits = String::new();
+                while digits.len() < 2 && chars.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                    digits.push(chars.next().unwrap());
+                }
+                bytes.push(u8::from_str_radix(&digits, 16).map_err(|e| e.to_string())?);
+            }
+            c => {
+                let mut buf = [0; 4];
+                bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            }
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::destination::postgres::{Format, PostgresDestination};
+    use crate::{row, Field};
+
+    #[test]
+    fn it_reads_copy_text() {
+        let schema = Schema::with_fields(vec![
+            Field::with_type("id", DataType::Int32),
+            Field::with_type("name", DataType::String),
+            Field::with_type("active", DataType::Bool),
+            Field::with_type("bytes", DataType::Binary),
+            Field::with_type("at", DataType::TimestampTz),
+        ])
+        .unwrap();
+
+        let data = "1\ta\\tb\\\\c\\nd\tt\t\\\\x00ff\t2019-09-05 18:14:04.5+02\n\
+                    2\t\\N\tf\t\\N\t\\N\n\
+                    \\.\n";
+        let df = DataFrame::read_copy(data.as_bytes(), &schema).unwrap();
+
+        let at = DateTime::parse_from_rfc3339("2019-09-05T18:14:04.5+02:00").unwrap();
+        assert_eq!(
+            df.row(0).unwrap(),
+            row![1i32, "a\tb\\c\nd", true, Value::Binary(vec![0, 255]), at]
+        );
+        assert_eq!(
+            df.row(1).unwrap(),
+            row![2i32, Value::Null, false, Value::Null, Value::Null]
+        );
+
+        assert!(matches!(
+            DataFrame::read_copy("1\ta\n".as_bytes(), &schema),
+            Err(Error::ColumnCountError { .. })
+        ));
+        assert!(matches!(
+            DataFrame::read_copy("x\ta\tt\t\\N\t\\N\n".as_bytes(), &schema),
+            Err(Error::ParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn it_round_trips_copy_text() {
+        let date = chrono::NaiveDate::from_ymd_opt(2019, 9, 5).unwrap();
+        let schema = Schema::with_fields(vec![
+            Field::with_type("id", DataType::Int64),
+            Field::with_type("name", DataType::String),
+            Field::with_type("price", DataType::decimal(10, 2)),
+            Field::with_type("ratio", DataType::Double),
+            Field::with_type("date", DataType::Date),
+            Field::with_type("at", DataType::Timestamp),
+            Field::with_type("tags", DataType::array(DataType::String)),
+        ])
+        .unwrap();
+        let mut df = DataFrame::with_schema(schema.clone());
+        df.extend_unchecked(vec![
+            row![
+                1i64,
+                "tab\there\r\n\\",
+                1.5,
+                f64::INFINITY,
+                date,
+                date.and_hms_micro_opt(1, 2, 3, 4).unwrap(),
+                Value::Array(vec!["x\ty".into()])
+            ],
+            vec![Value::Null; 7],
+        ]);
+        df.coherce_data();
+
+        let copy = df.to_copy(vec![], Format::Text).unwrap();
+        let read = DataFrame::read_copy(&copy[..], &schema).unwrap();
+        assert_eq!(read, df);
+    }
+}
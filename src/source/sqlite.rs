@@ -0,0 +1,300 @@
+use crate::ops::cast::{self, try_cast};
+use crate::traits::TypeOf;
+use crate::value::number::Num;
+use crate::{DataFrame, DataType, Field, Schema, Value};
+
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags};
+use snafu::{ResultExt, Snafu};
+use std::path::Path;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to open the sqlite database: {}", source))]
+    OpenError { source: rusqlite::Error },
+
+    #[snafu(display("Failed to run the query: {}", source))]
+    QueryError { source: rusqlite::Error },
+
+    #[snafu(display(
+        "The value in column {} on row {} can't be read: {}",
+        column,
+        row,
+        source
+    ))]
+    ValueError {
+        column: String,
+        row: usize,
+        source: cast::Error,
+    },
+
+    #[snafu(display("The query's columns are invalid: {}", source))]
+    SchemaError { source: crate::schema::Error },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Read the result of a query against a SQLite database into a dataframe. The columns are of the
+/// type they were declared as when it's one the sqlite destination creates, otherwise they are of
+/// the type of their values' storage classes: integers are int64, reals are doubles, text is
+/// strings and blobs are binary.
+pub trait SqliteSource {
+    /// Open the database read only and run the query
+    fn from_sqlite_path<'a, P: AsRef<Path>>(path: P, query: &str) -> Result<DataFrame<'a>> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .context(OpenError)?;
+        Self::read_sqlite(&conn, query)
+    }
+
+    fn read_sqlite<'a>(conn: &Connection, query: &str) -> Result<DataFrame<'a>> {
+        let mut stmt = conn.prepare(query).context(QueryError)?;
+        let declared = stmt
+            .columns()
+            .iter()
+            .map(|column| {
+                let dtype = column.decl_type().and_then(declared_type);
+                (column.name().to_string(), dtype)
+            })
+            .collect::<Vec<(String, Option<DataType>)>>();
+
+        let mut data = vec![];
+        let mut rows = stmt.query([]).context(QueryError)?;
+        while let Some(row) = rows.next().context(QueryError)? {
+            let values = (0..declared.len())
+                .map(|i| row.get_ref(i).map(from_sql))
+                .collect::<rusqlite::Result<Vec<Value>>>()
+                .context(QueryError)?;
+            data.push(values);
+        }
+
+        let mut fields = vec![];
+        for (i, (name, dtype)) in declared.into_iter().enumerate() {
+            let dtype = match dtype {
+                Some(dtype) => dtype,
+                None => data
+                    .iter()
+                    .map(|row| row[i].type_of())
+                    .filter(|dtype| !dtype.is_null())
+                    .fold(DataType::Null, |acc, dtype| match acc {
+                        DataType::Null => dtype,
+                        acc => acc.unify(&dtype),
+                    }),
+            };
+            let dtype = match dtype {
+                DataType::Null => DataType::Any,
+                dtype => dtype,
+            };
+
+            for (row, values) in data.iter_mut().enumerate() {
+                let value = std::mem::replace(&mut values[i], Value::Null);
+                values[i] = into_type(value, &dtype).context(ValueError {
+                    column: name.clone(),
+                    row,
+                })?;
+            }
+
+            fields.push(Field::with_type(&name, dtype));
+        }
+
+        let mut df = DataFrame::with_schema(Schema::with_fields(fields).context(SchemaError)?);
+        df.extend_unchecked(data);
+
+        Ok(df)
+    }
+}
+
+impl<'a> SqliteSource for DataFrame<'a> {}
+
+/// The type of a declared column type, for the names the sqlite destination creates tables with
+/// and their common aliases
+fn declared_type(decl: &str) -> Option<DataType> {
+    let decl = decl.trim().to_uppercase();
+    let (name, size) = match decl.split_once('(') {
+        Some((name, size)) => (name.trim(), Some(size.strip_suffix(')')?)),
+        None => (decl.as_str(), None),
+    };
+
+    let dtype = match name {
+        "BOOLEAN" | "BOOL" => DataType::Bool,
+        "TEXT" | "VARCHAR" | "CHAR" | "CLOB" => DataType::String,
+        "JSON" => DataType::Any,
+        "DATE" => DataType::Date,
+        "TIME" => DataType::Time,
+        "TIMESTAMP" | "DATETIME" => DataType::Timestamp,
+        "TIMESTAMPTZ" => DataType::TimestampTz,
+        "BLOB" => DataType::Binary,
+        "UINT8" => DataType::Uint8,
+        "UINT16" => DataType::Uint16,
+        "UINT32" => DataType::Uint32,
+        "UINT64" => DataType::Uint64,
+        "TINYINT" => DataType::Int8,
+        "SMALLINT" => DataType::Int16,
+        "INT32" => DataType::Int32,
+        // sqlite integers are 64 bits whatever their declared name
+        "INT" | "INTEGER" | "BIGINT" => DataType::Int64,
+        "FLOAT" => DataType::Float,
+        "REAL" | "DOUBLE" | "DOUBLE PRECISION" => DataType::Double,
+        "DECIMAL" | "DECIMAL_TEXT" | "NUMERIC" => {
+            let size = size.and_then(|size| {
+                let mut parts = size.split(',').map(|part| part.trim().parse::<u32>());
+                match (parts.next(), parts.next()) {
+                    (Some(Ok(precision)), Some(Ok(scale))) => Some((precision, scale)),
+                    (Some(Ok(precision)), None) => Some((precision, 0)),
+                    _ => None,
+                }
+            });
+            DataType::Decimal(size)
+        }
+        _ => return None,
+    };
+
+    Some(dtype)
+}
+
+fn from_sql(value: ValueRef<'_>) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::from(i),
+        ValueRef::Real(f) => Value::from(f),
+        ValueRef::Text(s) => Value::from(String::from_utf8_lossy(s).into_owned()),
+        ValueRef::Blob(b) => Value::Binary(b.to_vec()),
+    }
+}
+
+/// Convert the value read from its storage class into the column's type. Booleans are stored as
+/// integers, and arrays and maps as JSON.
+fn into_type(value: Value, dtype: &DataType) -> Result<Value, cast::Error> {
+    match (value, dtype) {
+        (Value::Number(n), DataType::Bool) => match n.inner() {
+            Num::Int64(i) => Ok(Value::Bool(*i != 0)),
+            _ => try_cast(Value::Number(n), dtype),
+        },
+        (Value::Number(n), DataType::Float) => n
+            .into_float()
+            .map(Value::Number)
+            .map_err(|source| cast::Error::FailedNumericCast { source }),
+        (Value::String(s), DataType::Any) if s.starts_with('[') || s.starts_with('{') => {
+            match serde_json::from_str::<serde_json::Value>(&s) {
+                Ok(json) => Ok(Value::from(json)),
+                Err(_) => Ok(Value::String(s)),
+            }
+        }
+        (value, DataType::Any) => Ok(value),
+        (value, dtype) => try_cast(value, dtype),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::destination::sqlite::{Mode, SqliteDestination};
+    use crate::{row, value::map::Map};
+
+    #[test]
+    fn it_reads_queries() {
+        let date = chrono::NaiveDate::from_ymd_opt(2019, 9, 5).unwrap();
+        let timestamp = date.and_hms_opt(18, 14, 4).unwrap();
+        let mut point = Map::new();
+        point.insert("x", Value::from(1i64));
+
+        let mut df = DataFrame::with_schema(
+            Schema::with_fields(vec![
+                Field::with_type("id", DataType::Int32).with_nullable(false),
+                Field::with_type("active", DataType::Bool),
+                Field::with_type("price", DataType::decimal(10, 2)),
+                Field::with_type("date", DataType::Date),
+                Field::with_type("at", DataType::Timestamp),
+                Field::with_type("point", DataType::structure(vec![("x", DataType::Int64)])),
+                Field::with_type("bytes", DataType::Binary),
+            ])
+            .unwrap(),
+        );
+        df.extend_unchecked(vec![
+            row![
+                1i32,
+                true,
+                1.5,
+                date,
+                timestamp,
+                Value::Map(point),
+                Value::Binary(vec![0, 1])
+            ],
+            vec![Value::from(2i32)]
+                .into_iter()
+                .chain(vec![Value::Null; 6])
+                .collect(),
+        ]);
+        df.coherce_data();
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        df.to_sqlite(&mut conn, "items", Mode::Create).unwrap();
+
+        let read = DataFrame::read_sqlite(&conn, "SELECT * FROM items ORDER BY id").unwrap();
+        for (read, field) in read.schema().fields().iter().zip(df.schema().fields()) {
+            match field.dtype() {
+                DataType::Struct(_) => assert_eq!(read.dtype(), &DataType::Any),
+                dtype => assert_eq!(read.dtype(), dtype),
+            }
+        }
+        assert_eq!(read.row(0).unwrap().data(), df.row(0).unwrap().data());
+        assert_eq!(read.row(1).unwrap().data(), df.row(1).unwrap().data());
+
+        // expressions don't have a declared type, so they are of their storage class's type
+        let read =
+            DataFrame::read_sqlite(&conn, "SELECT id * 2 AS double, 'x' AS x FROM items").unwrap();
+        assert_eq!(read.schema()[0].dtype(), &DataType::Int64);
+        assert_eq!(read.schema()[1].dtype(), &DataType::String);
+        assert_eq!(
+            read.column_values("double")
+                .unwrap()
+                .collect::<Vec<Value>>(),
+            vec![Value::from(2i64), Value::from(4i64)]
+        );
+
+        assert!(matches!(
+            DataFrame::read_sqlite(&conn, "SELECT * FROM missing"),
+            Err(Error::QueryError { .. })
+        ));
+    }
+
+    #[test]
+    fn it_reads_declared_types() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE items (id INT, n INT32);
+             INSERT INTO items VALUES (9007199254740993, 1);",
+        )
+        .unwrap();
+
+        let read = DataFrame::read_sqlite(&conn, "SELECT * FROM items").unwrap();
+        assert_eq!(read.schema()[0].dtype(), &DataType::Int64);
+        assert_eq!(read.schema()[1].dtype(), &DataType::Int32);
+        assert_eq!(
+            read.row(0).unwrap(),
+            vec![Value::from(9_007_199_254_740_993i64), Value::from(1i32)]
+        );
+
+        assert_eq!(
+            declared_type("decimal(10, 2)"),
+            Some(DataType::decimal(10, 2))
+        );
+        assert_eq!(declared_type("DECIMAL(10, 2"), None);
+    }
+
+    #[test]
+    fn it_reads_decimals_without_rounding() {
+        let price: Value = Value::from("12345678901234567.89");
+        let mut df = DataFrame::with_schema(
+            Schema::with_fields(vec![Field::with_type("price", DataType::decimal(20, 2))]).unwrap(),
+        );
+        df.extend_unchecked(vec![vec![price]]);
+        df.coherce_data();
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        df.to_sqlite(&mut conn, "prices", Mode::Create).unwrap();
+
+        let read = DataFrame::read_sqlite(&conn, "SELECT * FROM prices").unwrap();
+        assert_eq!(read.schema()[0].dtype(), &DataType::decimal(20, 2));
+        assert_eq!(read.row(0).unwrap()[0].to_string(), "12345678901234567.89");
+    }
+}
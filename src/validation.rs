@@ -0,0 +1,438 @@
+use crate::{
+    ops::keys::KeyValue, schema::Constraint, traits::TypeOf, value::number::Num, DataFrame,
+    DataType, Field, Number, Schema, Value,
+};
+
+use regex::Regex;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{hash_map::Entry, BTreeMap, HashMap};
+
+/// The rule a value or column broke
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Rule {
+    /// The schema has a field that the dataframe doesn't have a column for
+    MissingColumn,
+    /// The dataframe has a column that isn't in the schema
+    ExtraColumn,
+    /// The value isn't of the field's type
+    Type(DataType),
+    /// The value is null, but the field isn't nullable
+    NotNull,
+    Constraint(Constraint),
+}
+
+impl Rule {
+    pub fn name(&self) -> &str {
+        match self {
+            Rule::MissingColumn => "missing_column",
+            Rule::ExtraColumn => "extra_column",
+            Rule::Type(_) => "type",
+            Rule::NotNull => "not_null",
+            Rule::Constraint(c) => c.name(),
+        }
+    }
+}
+
+/// A single broken rule. Column level violations, like a missing column, don't have a row or
+/// value.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Violation {
+    pub row: Option<usize>,
+    pub column: String,
+    pub value: Option<Value>,
+    pub rule: Rule,
+    pub message: String,
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.row {
+            Some(row) => write!(f, "row {}, column {}: {}", row, self.column, self.message),
+            None => write!(f, "column {}: {}", self.column, self.message),
+        }
+    }
+}
+
+/// Counts of the violations in a report
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Summary {
+    pub rows: usize,
+    pub violations: usize,
+    /// The number of rows with at least one violation
+    pub invalid_rows: usize,
+    pub by_column: BTreeMap<String, usize>,
+    pub by_rule: BTreeMap<String, usize>,
+}
+
+/// Every violation found when validating a dataframe against a schema
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ValidationReport {
+    pub rows: usize,
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    pub fn summary(&self) -> Summary {
+        let mut summary = Summary {
+            rows: self.rows,
+            violations: self.violations.len(),
+            ..Summary::default()
+        };
+
+        let mut rows = self
+            .violations
+            .iter()
+            .filter_map(|v| v.row)
+            .collect::<Vec<usize>>();
+        rows.sort_unstable();
+        rows.dedup();
+        summary.invalid_rows = rows.len();
+
+        for violation in &self.violations {
+            *summary
+                .by_column
+                .entry(violation.column.clone())
+                .or_default() += 1;
+            *summary
+                .by_rule
+                .entry(violation.rule.name().to_string())
+                .or_default() += 1;
+        }
+
+        summary
+    }
+
+    /// The summary and the violations as json
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "summary": self.summary(),
+            "violations": self.violations,
+        })
+    }
+}
+
+impl std::fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let summary = self.summary();
+
+        writeln!(
+            f,
+            "{} violations in {} of {} rows",
+            summary.violations, summary.invalid_rows, summary.rows
+        )?;
+
+        for (rule, count) in &summary.by_rule {
+            writeln!(f, "  {}: {}", rule, count)?;
+        }
+
+        for violation in &self.violations {
+            writeln!(f, "{}", violation)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Schema {
+    /// Check the dataframe's columns and values against the schema's fields, types, nullability
+    /// and constraints. Columns are matched by name.
+    pub fn validate(&self, df: &DataFrame) -> ValidationReport {
+        let mut violations = vec![];
+
+        for field in self.fields() {
+            if !df.schema.field_exists(&field.name) {
+                violations.push(Violation {
+                    row: None,
+                    column: field.name.clone(),
+                    value: None,
+                    rule: Rule::MissingColumn,
+                    message: "the column is missing".to_string(),
+                });
+            }
+        }
+
+        for column in df.columns() {
+            if !self.field_exists(column) {
+                violations.push(Violation {
+                    row: None,
+                    column: column.clone(),
+                    value: None,
+                    rule: Rule::ExtraColumn,
+                    message: "the column is not in the schema".to_string(),
+                });
+            }
+        }
+
+        for field in self.fields() {
+            if let Ok(values) = df.column_values(&field.name) {
+                validate_column(field, values, &mut violations);
+            }
+        }
+
+        violations.sort_by_key(|v| v.row);
+
+        ValidationReport {
+            rows: df.size(),
+            violations,
+        }
+    }
+}
+
+fn validate_column<I>(field: &Field, values: I, violations: &mut Vec<Violation>)
+where
+    I: Iterator<Item = Value>,
+{
+    let mut violation = |row: usize, value: &Value, rule: Rule, message: String| {
+        violations.push(Violation {
+            row: Some(row),
+            column: field.name.clone(),
+            value: Some(value.clone()),
+            rule,
+            message,
+        })
+    };
+
+    let mut patterns = HashMap::new();
+    for constraint in &field.constraints {
+        if let Constraint::Pattern(pattern) = constraint {
+            patterns.insert(pattern.as_str(), Regex::new(pattern));
+        }
+    }
+
+    let mut seen: HashMap<KeyValue, usize> = HashMap::new();
+
+    for (row, value) in values.enumerate() {
+        let value = &value;
+        if value.is_null() {
+            if !field.nullable {
+                violation(row, value, Rule::NotNull, "the value is null".to_string());
+            }
+            continue;
+        }
+
+        if !field.dtype.matches(value) {
+            violation(
+                row,
+                value,
+                Rule::Type(field.dtype.clone()),
+                format!("expected {} but found {}", field.dtype, value.type_of()),
+            );
+        }
+
+        for constraint in &field.constraints {
+            let broken = match constraint {
+                Constraint::Min(min) => match compare(value, min) {
+                    Some(Ordering::Less) => Some(format!("{} is less than {}", value, min)),
+                    _ => None,
+                },
+                Constraint::Max(max) => match compare(value, max) {
+                    Some(Ordering::Greater) => Some(format!("{} is greater than {}", value, max)),
+                    _ => None,
+                },
+                Constraint::Pattern(pattern) => match (value, &patterns[pattern.as_str()]) {
+                    (_, Err(e)) => Some(format!("the pattern {} is invalid: {}", pattern, e)),
+                    (Value::String(s), Ok(regex)) if !regex.is_match(s) => {
+                        Some(format!("{} does not match {}", s, pattern))
+                    }
+                    _ => None,
+                },
+                Constraint::AllowedValues(allowed) => {
+                    if allowed
+                        .iter()
+                        .any(|a| compare(value, a) == Some(Ordering::Equal))
+                    {
+                        None
+                    } else {
+                        Some(format!("{} is not an allowed value", value))
+                    }
+                }
+                Constraint::MaxLength(max) => match length(value) {
+                    Some(len) if len > *max => {
+                        Some(format!("the length {} is longer than {}", len, max))
+                    }
+                    _ => None,
+                },
+                Constraint::Unique => {
+                    // values are duplicates when they'd be the same key, so values of different
+                    // types that display the same aren't
+                    match seen.entry(KeyValue(value.clone())) {
+                        Entry::Occupied(first) => {
+                            Some(format!("{} is a duplicate of row {}", value, first.get()))
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert(row);
+                            None
+                        }
+                    }
+                }
+            };
+
+            if let Some(message) = broken {
+                violation(row, value, Rule::Constraint(constraint.clone()), message);
+            }
+        }
+    }
+}
+
+/// Compare the values, numbers are compared by value whatever their type. Values of different
+/// kinds can't be compared.
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => match (a.inner(), b.inner()) {
+            (Num::Float(_), _) | (Num::Double(_), _) | (_, Num::Float(_)) | (_, Num::Double(_)) => {
+                to_f64(a.inner())?.partial_cmp(&to_f64(b.inner())?)
+            }
+            _ => {
+                let a = a.clone().into_decimal().ok()?;
+                let b = b.clone().into_decimal().ok()?;
+                a.partial_cmp(&b)
+            }
+        },
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Date(a), Value::Date(b)) => Some(a.cmp(b)),
+        (Value::Time(a), Value::Time(b)) => Some(a.cmp(b)),
+        (Value::Timestamp(a), Value::Timestamp(b)) => Some(a.cmp(b)),
+        (Value::TimestampTz(a), Value::TimestampTz(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (a, b) if a == b => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+fn to_f64(n: &Num) -> Option<f64> {
+    use rust_decimal::prelude::ToPrimitive;
+
+    match n {
+        Num::Decimal(d) => d.to_f64(),
+        n => Number(n.clone()).as_f64().into(),
+    }
+}
+
+fn length(value: &Value) -> Option<usize> {
+    match value {
+        Value::String(s) => Some(s.chars().count()),
+        Value::Array(a) => Some(a.len()),
+        Value::Binary(b) => Some(b.len()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::row;
+
+    fn schema() -> Schema {
+        Schema::with_fields(vec![
+            Field::with_type("id", DataType::Int64)
+                .with_nullable(false)
+                .with_constraint(Constraint::Unique)
+                .with_constraint(Constraint::Min(Value::from(1))),
+            Field::with_type("code", DataType::String)
+                .with_constraint(Constraint::Pattern("^[A-Z]{2}$".to_string()))
+                .with_constraint(Constraint::MaxLength(2)),
+            Field::with_type("status", DataType::String).with_constraint(
+                Constraint::AllowedValues(vec![Value::from("open"), Value::from("closed")]),
+            ),
+            Field::with_type("missing", DataType::Bool),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn it_validates_dataframes() {
+        let mut df = DataFrame::with_columns(&["id", "code", "status", "extra"]);
+        assert!(df
+            .extend(vec![
+                row![1i64, "AB", "open", 1],
+                row![1i64, "abc", "closed", 2],
+                row![0i64, "CD", "pending", 3],
+                row![Value::Null, 5, Value::Null, 4],
+            ])
+            .is_ok());
+
+        let report = schema().validate(&df);
+        assert!(!report.is_valid());
+
+        let found = report
+            .violations
+            .iter()
+            .map(|v| (v.row, v.column.as_str(), v.rule.name()))
+            .collect::<Vec<(Option<usize>, &str, &str)>>();
+
+        assert_eq!(
+            found,
+            vec![
+                (None, "missing", "missing_column"),
+                (None, "extra", "extra_column"),
+                (Some(1), "id", "unique"),
+                (Some(1), "code", "pattern"),
+                (Some(1), "code", "max_length"),
+                (Some(2), "id", "min"),
+                (Some(2), "status", "allowed_values"),
+                (Some(3), "id", "not_null"),
+                (Some(3), "code", "type"),
+            ]
+        );
+
+        let summary = report.summary();
+        assert_eq!(summary.rows, 4);
+        assert_eq!(summary.violations, 9);
+        assert_eq!(summary.invalid_rows, 3);
+        assert_eq!(summary.by_column["code"], 3);
+
+        let json = report.to_json();
+        assert_eq!(json["summary"]["by_rule"]["unique"], 1);
+        assert_eq!(json["violations"][2]["row"], 1);
+
+        assert!(report
+            .to_string()
+            .starts_with("9 violations in 3 of 4 rows"));
+    }
+
+    #[test]
+    fn it_compares_numbers_by_value() {
+        assert_eq!(
+            compare(&Value::from(2u8), &Value::from(2i64)),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            compare(&Value::from(1.5), &Value::from(2)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(compare(&Value::from("a"), &Value::from(1)), None);
+
+        let df = DataFrame::with_columns(&["id", "code", "status", "missing"]);
+        assert!(schema().validate(&df).is_valid());
+    }
+
+    #[test]
+    fn it_finds_duplicates_like_keys() {
+        let schema = Schema::with_fields(vec![
+            Field::with_type("x", DataType::Any).with_constraint(Constraint::Unique)
+        ])
+        .unwrap();
+        let mut df = DataFrame::with_columns(&["x"]);
+        df.extend_unchecked(vec![
+            row![0.0],
+            row![-0.0],
+            row![f64::NAN],
+            row![f64::NAN],
+            row![1i64],
+            row![1i32],
+        ]);
+
+        let duplicates = schema
+            .validate(&df)
+            .violations
+            .iter()
+            .map(|v| v.row)
+            .collect::<Vec<Option<usize>>>();
+        assert_eq!(duplicates, vec![Some(1), Some(3)]);
+    }
+}
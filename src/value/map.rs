@@ -6,19 +6,11 @@ use std::hash::Hash;
 use std::iter::FromIterator;
 use std::ops::{Deref, Index, IndexMut};
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, PartialOrd)]
 pub struct Map {
     inner: BTreeMap<String, Value>,
 }
 
-impl Default for Map {
-    fn default() -> Map {
-        Map {
-            inner: BTreeMap::<String, Value>::new(),
-        }
-    }
-}
-
 impl Map {
     pub fn new() -> Map {
         Default::default()
@@ -28,33 +20,33 @@ impl Map {
         self.inner.clear()
     }
 
-    pub fn contains_key<K: ?Sized>(&self, key: &K) -> bool
+    pub fn contains_key<K>(&self, key: &K) -> bool
     where
         String: Borrow<K>,
-        K: Ord + Eq + Hash,
+        K: ?Sized + Ord + Eq + Hash,
     {
         self.get(key).is_some()
     }
 
-    pub fn entry<K>(&mut self, key: K) -> Entry<String, Value>
+    pub fn entry<K>(&mut self, key: K) -> Entry<'_, String, Value>
     where
         K: Into<String>,
     {
         self.inner.entry(key.into())
     }
 
-    pub fn get<K: ?Sized>(&self, key: &K) -> Option<&Value>
+    pub fn get<K>(&self, key: &K) -> Option<&Value>
     where
         String: Borrow<K>,
-        K: Ord + Eq + Hash,
+        K: ?Sized + Ord + Eq + Hash,
     {
         self.inner.get(key)
     }
 
-    pub fn get_mut<K: ?Sized>(&mut self, key: &K) -> Option<&mut Value>
+    pub fn get_mut<K>(&mut self, key: &K) -> Option<&mut Value>
     where
         String: Borrow<K>,
-        K: Ord + Eq + Hash,
+        K: ?Sized + Ord + Eq + Hash,
     {
         self.inner.get_mut(key)
     }
@@ -70,15 +62,15 @@ impl Map {
         self.inner.is_empty()
     }
 
-    pub fn iter(&self) -> Iter<String, Value> {
+    pub fn iter(&self) -> Iter<'_, String, Value> {
         self.inner.iter()
     }
 
-    pub fn iter_mut(&mut self) -> IterMut<String, Value> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, String, Value> {
         self.inner.iter_mut()
     }
 
-    pub fn keys(&self) -> Keys<String, Value> {
+    pub fn keys(&self) -> Keys<'_, String, Value> {
         self.inner.keys()
     }
 
@@ -89,20 +81,29 @@ impl Map {
     pub fn remove<K>(&mut self, key: &K) -> Option<Value>
     where
         String: Borrow<K>,
-        K: Ord + Eq + Hash,
+        K: ?Sized + Ord + Eq + Hash,
     {
         self.inner.remove(key)
     }
 
-    pub fn values(&self) -> Values<String, Value> {
+    pub fn values(&self) -> Values<'_, String, Value> {
         self.inner.values()
     }
 
-    pub fn values_mut(&mut self) -> ValuesMut<String, Value> {
+    pub fn values_mut(&mut self) -> ValuesMut<'_, String, Value> {
         self.inner.values_mut()
     }
 }
 
+impl IntoIterator for Map {
+    type Item = (String, Value);
+    type IntoIter = std::collections::btree_map::IntoIter<String, Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+}
+
 impl FromIterator<(String, Value)> for Map {
     fn from_iter<T>(iter: T) -> Self
     where
@@ -128,7 +129,7 @@ impl Clone for Map {
 //     }
 // }
 
-impl<'a> Index<&'a str> for Map {
+impl Index<&str> for Map {
     type Output = Value;
 
     fn index(&self, index: &str) -> &Self::Output {
@@ -155,7 +156,7 @@ impl<'a> Index<&'a String> for Map {
     }
 }
 
-impl<'a> IndexMut<&'a str> for Map {
+impl IndexMut<&str> for Map {
     fn index_mut(&mut self, index: &str) -> &mut Value {
         self.entry(index).or_insert(Value::Null)
     }
@@ -5,7 +5,7 @@ use crate::{schema::DataType, traits::TypeOf};
 use map::Map;
 use number::Number;
 
-use chrono::NaiveDateTime;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::convert::From;
 use std::ops::{Deref, Index};
@@ -18,41 +18,85 @@ pub enum Value {
     Array(Vec<Value>),
     Map(Map),
     Number(Number),
-    Date(NaiveDateTime),
+    /// A calendar date
+    Date(NaiveDate),
+    /// A time of day
+    Time(NaiveTime),
+    /// A date and time without a timezone
+    Timestamp(NaiveDateTime),
+    /// A date and time at an offset from UTC
+    TimestampTz(DateTime<FixedOffset>),
     Binary(Vec<u8>),
 }
 
 impl Value {
     pub fn is_numeric(&self) -> bool {
-        match self {
-            Value::Number(_) => true,
-            _ => false,
-        }
+        matches!(self, Value::Number(_))
     }
 
     pub fn is_null(&self) -> bool {
-        match self {
-            Value::Null => true,
-            _ => false,
-        }
+        matches!(self, Value::Null)
     }
 }
 
 impl TypeOf for Value {
-    fn type_of(&self) -> &DataType {
+    /// The type of the value. Arrays are of their elements' unified type, and maps are structs
+    /// of their keys.
+    fn type_of(&self) -> DataType {
         match self {
-            Value::Bool(_) => &DataType::Bool,
-            Value::String(_) => &DataType::String,
-            Value::Array(_) => &DataType::Array,
-            Value::Map(_) => &DataType::Map,
+            Value::Bool(_) => DataType::Bool,
+            Value::String(_) => DataType::String,
+            Value::Array(values) => DataType::array(
+                values
+                    .iter()
+                    .fold(DataType::Null, |dtype, v| dtype.unify(&v.type_of())),
+            ),
+            Value::Map(map) => DataType::Struct(
+                map.iter()
+                    .map(|(key, value)| (key.clone(), value.type_of()))
+                    .collect(),
+            ),
             Value::Number(n) => n.type_of(),
-            Value::Date(_) => &DataType::Date,
-            Value::Binary(_) => &DataType::Binary,
-            _ => &DataType::Any,
+            Value::Date(_) => DataType::Date,
+            Value::Time(_) => DataType::Time,
+            Value::Timestamp(_) => DataType::Timestamp,
+            Value::TimestampTz(_) => DataType::TimestampTz,
+            Value::Binary(_) => DataType::Binary,
+            Value::Null => DataType::Null,
         }
     }
 }
 
+impl From<NaiveDate> for Value {
+    fn from(d: NaiveDate) -> Value {
+        Value::Date(d)
+    }
+}
+
+impl From<NaiveTime> for Value {
+    fn from(t: NaiveTime) -> Value {
+        Value::Time(t)
+    }
+}
+
+impl From<NaiveDateTime> for Value {
+    fn from(d: NaiveDateTime) -> Value {
+        Value::Timestamp(d)
+    }
+}
+
+impl From<DateTime<FixedOffset>> for Value {
+    fn from(d: DateTime<FixedOffset>) -> Value {
+        Value::TimestampTz(d)
+    }
+}
+
+impl From<DateTime<Utc>> for Value {
+    fn from(d: DateTime<Utc>) -> Value {
+        Value::TimestampTz(d.fixed_offset())
+    }
+}
+
 impl From<bool> for Value {
     fn from(b: bool) -> Value {
         Value::Bool(b)
@@ -77,9 +121,9 @@ impl<'a> From<&'a str> for Value {
 //     }
 // }
 
-impl Into<String> for Value {
-    fn into(self) -> String {
-        format!("{}", self)
+impl From<Value> for String {
+    fn from(value: Value) -> String {
+        format!("{}", value)
     }
 }
 
@@ -133,6 +177,9 @@ impl std::fmt::Display for Value {
             Value::Number(n) => write!(f, "{}", n),
             Value::Bool(b) => write!(f, "{}", b),
             Value::Date(d) => write!(f, "{}", d),
+            Value::Time(t) => write!(f, "{}", t),
+            Value::Timestamp(d) => write!(f, "{}", d),
+            Value::TimestampTz(d) => write!(f, "{}", d),
             Value::Map(_m) => write!(f, "display not implemented for map"),
             Value::Array(_a) => write!(f, "display not implemented for array"),
             Value::Binary(_) => write!(f, "[bin data]"),
@@ -3,7 +3,6 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use snafu::{IntoError, Snafu};
 use std::convert::{From, Into, TryInto};
-use std::error::Error as ErrorTrait;
 use std::str::FromStr;
 
 use std::ops::Add as AddTrait;
@@ -53,6 +52,13 @@ pub enum Error {
     InvalidDataType {
         datatype: DataType,
     },
+
+    #[snafu(display("{} doesn't fit in a decimal({},{})", value, precision, scale))]
+    DecimalOverflow {
+        value: String,
+        precision: u32,
+        scale: u32,
+    },
 }
 
 impl From<std::convert::Infallible> for Error {
@@ -64,7 +70,7 @@ impl From<std::convert::Infallible> for Error {
 impl From<std::num::TryFromIntError> for Error {
     fn from(err: std::num::TryFromIntError) -> Error {
         Error::CastError {
-            description: err.description().to_string(),
+            description: err.to_string(),
         }
     }
 }
@@ -91,7 +97,7 @@ macro_rules! impl_op {
         |lhs: Number, rhs: Number| -> Result<Number, Error> {
             let lhs = lhs.0;
             let rhs = rhs.0;
-            perform_op!($op, $checked_op, rhs, lhs).and_then(|num| Ok(Number(num)))
+            perform_op!($op, $checked_op, rhs, lhs).map(Number)
         }
     }};
 }
@@ -157,10 +163,10 @@ macro_rules! impl_is_type {
         pub fn $func(&self) -> bool {
             match self.0 {
                 $is(_) => true,
-                _ => false
+                _ => false,
             }
         }
-    }
+    };
 }
 
 macro_rules! impl_as_primative {
@@ -180,7 +186,7 @@ macro_rules! impl_as_primative {
                 Num::Decimal(_) => 0 as $type,
             }
         }
-    }
+    };
 }
 
 macro_rules! impl_cast_num {
@@ -189,7 +195,7 @@ macro_rules! impl_cast_num {
             let num = self.0;
             cast_num!(num, $into)
         }
-    }
+    };
 }
 
 macro_rules! impl_from_primative {
@@ -232,37 +238,19 @@ macro_rules! impl_traits {
 }
 
 impl TypeOf for Num {
-    fn type_of(&self) -> &DataType {
-        match self {
-            Num::Uint8(_) => &DataType::Uint8,
-            Num::Uint16(_) => &DataType::Uint16,
-            Num::Uint32(_) => &DataType::Uint32,
-            Num::Uint64(_) => &DataType::Uint64,
-            Num::Int8(_) => &DataType::Int8,
-            Num::Int16(_) => &DataType::Int16,
-            Num::Int32(_) => &DataType::Int32,
-            Num::Int64(_) => &DataType::Int64,
-            Num::Float(_) => &DataType::Float,
-            Num::Double(_) => &DataType::Double,
-            Num::Decimal(_) => &DataType::Decimal,
-        }
-    }
-}
-
-impl Num {
-    pub fn to_string(&self) -> String {
+    fn type_of(&self) -> DataType {
         match self {
-            Num::Uint8(n) => n.to_string(),
-            Num::Uint16(n) => n.to_string(),
-            Num::Uint32(n) => n.to_string(),
-            Num::Uint64(n) => n.to_string(),
-            Num::Int8(n) => n.to_string(),
-            Num::Int16(n) => n.to_string(),
-            Num::Int32(n) => n.to_string(),
-            Num::Int64(n) => n.to_string(),
-            Num::Float(n) => n.to_string(),
-            Num::Double(n) => n.to_string(),
-            Num::Decimal(n) => n.to_string(),
+            Num::Uint8(_) => DataType::Uint8,
+            Num::Uint16(_) => DataType::Uint16,
+            Num::Uint32(_) => DataType::Uint32,
+            Num::Uint64(_) => DataType::Uint64,
+            Num::Int8(_) => DataType::Int8,
+            Num::Int16(_) => DataType::Int16,
+            Num::Int32(_) => DataType::Int32,
+            Num::Int64(_) => DataType::Int64,
+            Num::Float(_) => DataType::Float,
+            Num::Double(_) => DataType::Double,
+            Num::Decimal(_) => DataType::Decimal(None),
         }
     }
 }
@@ -294,15 +282,38 @@ macro_rules! try_from_str {
 
 impl std::fmt::Display for Num {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
-        f.write_str(&format!("{}", self))
+        match self {
+            Num::Uint8(n) => write!(f, "{}", n),
+            Num::Uint16(n) => write!(f, "{}", n),
+            Num::Uint32(n) => write!(f, "{}", n),
+            Num::Uint64(n) => write!(f, "{}", n),
+            Num::Int8(n) => write!(f, "{}", n),
+            Num::Int16(n) => write!(f, "{}", n),
+            Num::Int32(n) => write!(f, "{}", n),
+            Num::Int64(n) => write!(f, "{}", n),
+            Num::Float(n) => write!(f, "{}", n),
+            Num::Double(n) => write!(f, "{}", n),
+            Num::Decimal(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+/// The number of digits in the decimal, ignoring leading zeros
+pub(crate) fn digits(d: &Decimal) -> u32 {
+    let mut mantissa = d.mantissa().unsigned_abs();
+    let mut digits = 1;
+    while mantissa >= 10 {
+        mantissa /= 10;
+        digits += 1;
     }
+    digits
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd)]
 pub struct Number(pub(crate) Num);
 
 impl TypeOf for Number {
-    fn type_of(&self) -> &DataType {
+    fn type_of(&self) -> DataType {
         self.0.type_of()
     }
 }
@@ -315,7 +326,7 @@ impl<N: Into<Num>> From<N> for Number {
 
 impl std::fmt::Display for Number {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
-        f.write_str(&self.to_string())
+        write!(f, "{}", self.0)
     }
 }
 
@@ -393,6 +404,25 @@ impl Number {
         .map(|n| Number(Num::Decimal(n)))
     }
 
+    /// Round a decimal to the scale and check that it fits in the precision, decimals without a
+    /// size are unchanged
+    pub fn with_size(self, size: Option<(u32, u32)>) -> Result<Number, Error> {
+        match (self.0, size) {
+            (Num::Decimal(mut d), Some((precision, scale))) => {
+                d.rescale(scale);
+                if d.scale() != scale || digits(&d) > precision {
+                    return Err(Error::DecimalOverflow {
+                        value: d.to_string(),
+                        precision,
+                        scale,
+                    });
+                }
+                Ok(Number(Num::Decimal(d)))
+            }
+            (n, _) => Ok(Number(n)),
+        }
+    }
+
     impl_is_type!(is_u8, is_uint8, Num::Uint8);
     impl_is_type!(is_u16, is_uint16, Num::Uint16);
     impl_is_type!(is_u32, is_uint32, Num::Uint32);
@@ -425,10 +455,6 @@ impl Number {
         impl_op!(add, checked_add)(self, lhs)
     }
 
-    pub fn to_string(&self) -> String {
-        self.0.to_string()
-    }
-
     pub fn inner(&self) -> &Num {
         &self.0
     }
@@ -447,10 +473,10 @@ impl Number {
 
             DataType::Float => try_from_str!(f32, Num::Float, s, ParseFloatError),
             DataType::Double => try_from_str!(f64, Num::Double, s, ParseFloatError),
-            DataType::Decimal => Decimal::from_str(s)
+            DataType::Decimal(_) => Decimal::from_str(s)
                 .map_err(|e| Error::ParseDecimalError {
                     from_str: s.into(),
-                    description: e.description().into(),
+                    description: e.to_string(),
                 })
                 .map(|d| Number(Num::Decimal(d))),
             _ => Err(Error::InvalidDataType {
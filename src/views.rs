@@ -20,18 +20,10 @@ impl<'a, 'b> Iterator for View<'a, 'b> {
     type Item = SubView<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let (start, end) = self.df.dim.get_row_range(self.ptr);
-
-        if end > self.df.data.len() {
-            return None;
-        }
-
+        let row = self.df.row(self.ptr)?;
         self.ptr += 1;
 
-        Some(SubView::new(
-            &self.df.schema,
-            Cow::Borrowed(&self.df.data[start..end]),
-        ))
+        Some(row)
     }
 }
 
@@ -62,11 +54,11 @@ impl<'a> SubView<'a> {
         self.schema.field_exists(name)
     }
 
-    pub fn iter(&self) -> std::slice::Iter<Value> {
+    pub fn iter(&self) -> std::slice::Iter<'_, Value> {
         self.data.iter()
     }
 
-    pub fn iter_mut(&mut self) -> std::slice::IterMut<Value> {
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, Value> {
         self.data.to_mut().iter_mut()
     }
 }
@@ -118,7 +110,7 @@ impl<'a> Get<&str> for SubView<'a> {
     fn get_mut(&mut self, index: &str) -> Option<&mut Self::Output> {
         match self.column_index(index) {
             Some(index) => {
-                let index = index.clone();
+                let index = *index;
                 self.data.to_mut().get_mut(index)
             }
             None => None,